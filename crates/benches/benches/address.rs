@@ -1,5 +1,6 @@
 use alloy_primitives::{B256, b256};
 use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
+use nectar_primitives::xor_metric::nearest_peer;
 use nectar_primitives::{OverlayAddress, XorMetric};
 use rand::prelude::*;
 
@@ -117,6 +118,24 @@ pub fn address_benchmarks(c: &mut Criterion) {
         })
     });
 
+    // Nearest peer in a routing-table sized pool: the naive comparator scan
+    // against the word-wise one.
+    let peers: Vec<OverlayAddress> = (0..4096).map(|_| OverlayAddress::random()).collect();
+    let target = OverlayAddress::random();
+    group.bench_function("nearest_peer_naive_4096", |b| {
+        b.iter(|| {
+            let nearest = peers
+                .iter()
+                .enumerate()
+                .min_by(|(_, x), (_, y)| target.distance_order(*x, *y))
+                .map(|(index, _)| index);
+            black_box(nearest)
+        })
+    });
+    group.bench_function("nearest_peer_4096", |b| {
+        b.iter(|| black_box(nearest_peer(&target, &peers)))
+    });
+
     // Benchmark batch proximity calculation (a common operation in Kademlia)
     let batch_sizes = [10, 100, 500];
     for &size in &batch_sizes {
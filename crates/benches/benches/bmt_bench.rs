@@ -31,6 +31,28 @@ fn bench_bmt_hash(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_bmt_hash_reused(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bmt_hash_reused");
+
+    // One hasher reset between bodies, as a hot loop over many chunks runs.
+    for size in [1024, 4096].iter() {
+        let mut data = vec![0u8; *size];
+        rng().fill_bytes(&mut data);
+
+        let mut hasher = DefaultHasher::new();
+        group.bench_with_input(BenchmarkId::from_parameter(size), &data, |b, data| {
+            b.iter(|| {
+                hasher.reset();
+                hasher.set_span(data.len() as u64);
+                hasher.update(data);
+                hasher.sum()
+            });
+        });
+    }
+
+    group.finish();
+}
+
 fn bench_bmt_hash_prefixed(c: &mut Criterion) {
     let mut group = c.benchmark_group("bmt_hash_prefixed");
 
@@ -304,6 +326,7 @@ fn bench_bmt_zero_tree_optimization(c: &mut Criterion) {
 criterion_group!(
     benches,
     bench_bmt_hash,
+    bench_bmt_hash_reused,
     bench_bmt_hash_prefixed,
     bench_content_chunk_creation,
     bench_single_owner_chunk_creation,
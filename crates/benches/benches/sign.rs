@@ -6,6 +6,7 @@ use alloy_primitives::{B256, Signature, U256};
 use alloy_signer::SignerSync;
 use alloy_signer_local::PrivateKeySigner;
 use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+use nectar_postage::{calculate_bucket, calculate_buckets};
 use nectar_postage_issuer::{
     BatchId, BatchStamper, BucketDepth, MemoryIssuer, ShardedIssuer, SigningError, Stamper,
     sign_stamps_parallel,
@@ -173,12 +174,39 @@ fn bench_sign_comparison(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_bucket_comparison(c: &mut Criterion) {
+    let addresses: Vec<ChunkAddress> = (0..4096).map(|_| random_address()).collect();
+    let mut buckets = vec![0u32; addresses.len()];
+
+    let mut group = c.benchmark_group("bucket_4096_comparison");
+    group.throughput(Throughput::Elements(4096));
+
+    group.bench_function("scalar", |b| {
+        b.iter(|| {
+            for (bucket, address) in buckets.iter_mut().zip(&addresses) {
+                *bucket = calculate_bucket(black_box(address), 16);
+            }
+            black_box(&buckets);
+        })
+    });
+
+    group.bench_function("batched", |b| {
+        b.iter(|| {
+            calculate_buckets(black_box(&addresses), 16, &mut buckets).unwrap();
+            black_box(&buckets);
+        })
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_stamper_mock,
     bench_ecdsa_sign_sequential,
     bench_ecdsa_sign_parallel,
     bench_sign_comparison,
+    bench_bucket_comparison,
 );
 
 criterion_main!(benches);
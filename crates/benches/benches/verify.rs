@@ -7,10 +7,14 @@ use alloy_signer::SignerSync;
 use alloy_signer_local::PrivateKeySigner;
 use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
 use nectar_postage::{
-    Batch, BatchId, BucketDepth, Stamp, StampBytes, StampDigest, StampIndex,
-    parallel::{verify_stamps_parallel, verify_stamps_parallel_with_pubkey},
+    Batch, BatchId, BucketDepth, PostageContext, Stamp, StampBytes, StampDigest, StampIndex,
+    StampValidator, calculate_bucket,
+    parallel::{
+        validate_stamps_parallel, verify_stamps_parallel, verify_stamps_parallel_with_pubkey,
+    },
 };
 use nectar_primitives::ChunkAddress;
+use nectar_testing::Permissive;
 use rand::RngExt;
 
 /// Generate a random stamp for benchmarking.
@@ -403,6 +407,51 @@ fn bench_verify_comparison(c: &mut Criterion) {
     group.finish();
 }
 
+// Full Batch-Aware Validation: Sequential vs Parallel
+
+fn bench_validate_comparison(c: &mut Criterion) {
+    let signer = PrivateKeySigner::random();
+    let batch_id = BatchId::ZERO;
+    let batch: Batch = Batch::new(
+        batch_id,
+        1_000,
+        0,
+        signer.address(),
+        20,
+        BucketDepth::new(16).unwrap(),
+        false,
+    );
+    let state = PostageContext::new(100, 0);
+
+    let items: Vec<(Stamp, ChunkAddress, Batch)> = (0..1000)
+        .map(|_| {
+            let address = random_address();
+            let index = StampIndex::new(calculate_bucket(&address, 16), 0);
+            let prehash = StampDigest::new(address, batch_id, index, 12345).to_prehash();
+            let sig = signer.sign_message_sync(prehash.as_slice()).unwrap();
+            let stamp = Stamp::with_index(batch_id, index, 12345, sig);
+            (stamp, address, batch.clone())
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("validate_1000_comparison");
+    group.throughput(Throughput::Elements(1000));
+
+    group.bench_function("sequential", |b| {
+        b.iter(|| {
+            for (stamp, address, batch) in &items {
+                black_box(Permissive.validate_with_state(stamp, address, batch, &state)).unwrap();
+            }
+        })
+    });
+
+    group.bench_function("parallel", |b| {
+        b.iter(|| black_box(validate_stamps_parallel(&Permissive, &state, &items)))
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_stamp_roundtrip,
@@ -415,6 +464,7 @@ criterion_group!(
     bench_ecdsa_verify_parallel,
     bench_ecdsa_verify_parallel_with_pubkey,
     bench_verify_comparison,
+    bench_validate_comparison,
 );
 
 criterion_main!(benches);
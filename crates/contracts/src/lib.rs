@@ -50,7 +50,11 @@
     )
 )]
 
-use alloy_primitives::{Address, address};
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use alloy_primitives::{Address, B256, address};
 use alloy_sol_types::sol;
 
 // Deployment Info Macro
@@ -114,6 +118,79 @@ define_deployment!(
     SwapPriceOracle
 );
 
+/// Every contract deployment of one network, for code that watches them all.
+///
+/// Use [`mainnet::DEPLOYMENTS`] or [`testnet::DEPLOYMENTS`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deployments {
+    /// BZZ token.
+    pub bzz_token: Token,
+    /// Postage stamp contract.
+    pub postage_stamp: PostageStamp,
+    /// Stake registry contract.
+    pub staking: StakeRegistry,
+    /// Redistribution contract.
+    pub redistribution: Redistribution,
+    /// Storage price oracle contract.
+    pub storage_price_oracle: StoragePriceOracle,
+    /// Chequebook factory contract.
+    pub chequebook_factory: ChequebookFactory,
+    /// Swap price oracle contract.
+    pub swap_price_oracle: SwapPriceOracle,
+}
+
+impl Deployments {
+    /// Yields `(name, address, block)` for every contract, named as the
+    /// per-network constants are (lowercased).
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, Address, u64)> {
+        [
+            ("bzz_token", self.bzz_token.address, self.bzz_token.block),
+            (
+                "postage_stamp",
+                self.postage_stamp.address,
+                self.postage_stamp.block,
+            ),
+            ("staking", self.staking.address, self.staking.block),
+            (
+                "redistribution",
+                self.redistribution.address,
+                self.redistribution.block,
+            ),
+            (
+                "storage_price_oracle",
+                self.storage_price_oracle.address,
+                self.storage_price_oracle.block,
+            ),
+            (
+                "chequebook_factory",
+                self.chequebook_factory.address,
+                self.chequebook_factory.block,
+            ),
+            (
+                "swap_price_oracle",
+                self.swap_price_oracle.address,
+                self.swap_price_oracle.block,
+            ),
+        ]
+        .into_iter()
+    }
+
+    /// The lowest deployment block across the contracts: a start block from
+    /// which a backfill sees every event.
+    ///
+    /// A block of 0 marks a deployment with no recorded block (the bridged
+    /// token) and is skipped, so it cannot drag the start back to genesis.
+    /// Returns 0 only when no contract records a block.
+    #[must_use]
+    pub fn earliest_block(&self) -> u64 {
+        self.iter()
+            .map(|(_, _, block)| block)
+            .filter(|&block| block > 0)
+            .min()
+            .unwrap_or(0)
+    }
+}
+
 // Token Interface
 
 sol! {
@@ -135,6 +212,32 @@ sol! {
     }
 }
 
+/// Pre-encoded calldata for the approve-then-create-batch flow.
+///
+/// Thin wrappers over the generated [`IERC20`] call types, for callers that
+/// only need bytes to hand to a provider.
+pub mod token {
+    use alloy_primitives::{Address, Bytes, U256};
+    use alloy_sol_types::SolCall;
+
+    use crate::IERC20;
+
+    /// Calldata for `approve(spender, amount)`.
+    pub fn approve_call(spender: Address, amount: U256) -> Bytes {
+        IERC20::approveCall { spender, amount }.abi_encode().into()
+    }
+
+    /// Calldata for `allowance(owner, spender)`.
+    pub fn allowance_call(owner: Address, spender: Address) -> Bytes {
+        IERC20::allowanceCall { owner, spender }.abi_encode().into()
+    }
+
+    /// Decodes the return data of an `allowance` call.
+    pub fn decode_allowance(data: &[u8]) -> Result<U256, alloy_sol_types::Error> {
+        IERC20::allowanceCall::abi_decode_returns(data)
+    }
+}
+
 // Storage Incentive Contract Interfaces
 
 sol! {
@@ -143,6 +246,16 @@ sol! {
     /// Manages postage stamp batches required for uploading data to Swarm.
     #[derive(Debug, PartialEq, Eq)]
     interface IPostageStamp {
+        function createBatch(
+            address _owner,
+            uint256 _initialBalancePerChunk,
+            uint8 _depth,
+            uint8 _bucketDepth,
+            bytes32 _nonce,
+            bool _immutable
+        ) external;
+        function topUp(bytes32 _batchId, uint256 _topupAmountPerChunk) external;
+        function increaseDepth(bytes32 _batchId, uint8 _newDepth) external;
         function withdraw(address beneficiary) external;
         function setPrice(uint256 price) external;
         function validChunkCount() external view returns (uint256);
@@ -160,6 +273,18 @@ sol! {
             uint256 normalisedBalance,
             uint256 lastUpdatedBlockNumber
         );
+
+        event BatchCreated(
+            bytes32 indexed batchId,
+            uint256 totalAmount,
+            uint256 normalisedBalance,
+            address owner,
+            uint8 depth,
+            uint8 bucketDepth,
+            bool immutableFlag
+        );
+        event BatchTopUp(bytes32 indexed batchId, uint256 topupAmount, uint256 normalisedBalance);
+        event BatchDepthIncrease(bytes32 indexed batchId, uint8 newDepth, uint256 normalisedBalance);
     }
 
     /// Stake registry contract interface.
@@ -236,6 +361,167 @@ sol! {
     }
 }
 
+// Storage Price Oracle Simulation
+
+/// Fixed-point base of the oracle's change rates (`2^20`): a rate equal to it
+/// leaves the price unchanged.
+const PRICE_BASE: u64 = 1_048_576;
+
+/// Bits of extra precision the oracle keeps in its upscaled price.
+const PRICE_UPSCALE_BITS: u32 = 10;
+
+/// Per-round change rates indexed by reported redundancy, copied from the
+/// deployed `PriceOracle.changeRate`. Index 4 (the target redundancy) equals
+/// [`PRICE_BASE`]; lower redundancy raises the price, higher lowers it.
+const PRICE_CHANGE_RATES: [u64; 9] = [
+    1_049_417, 1_049_206, 1_048_996, 1_048_786, 1_048_576, 1_048_366, 1_048_156, 1_047_946,
+    1_047_736,
+];
+
+/// Predicts the price [`IStoragePriceOracle::adjustPriceCall`] would set,
+/// without sending a transaction.
+///
+/// Mirrors one round of the deployed oracle: `redundancy` is clamped to 8
+/// (target 4 plus at most 4 extra), the matching change rate is applied to
+/// the upscaled price, and the result is floored at `minimum`. `current` and
+/// `minimum` are the values returned by `currentPrice()` and `minimumPrice()`.
+///
+/// The contract keeps 10 extra bits of precision that `currentPrice()` drops,
+/// so the prediction can differ from the on-chain result by one unit when
+/// those bits were non-zero. Skipped rounds, which the contract charges at
+/// the maximum increase rate, are not modelled. A zero `redundancy` reverts
+/// on chain; here it leaves the price unchanged. A result beyond `u32`
+/// saturates where the contract's cast would truncate.
+///
+/// ```
+/// use nectar_contracts::predict_adjusted_price;
+///
+/// // At the target redundancy the price holds.
+/// assert_eq!(predict_adjusted_price(24_000, 4, 24_000), 24_000);
+/// // Under-replication raises it; over-replication lowers it to the floor.
+/// assert_eq!(predict_adjusted_price(24_000, 1, 24_000), 24_014);
+/// assert_eq!(predict_adjusted_price(24_000, 8, 24_000), 24_000);
+/// ```
+#[must_use]
+pub fn predict_adjusted_price(current: u32, redundancy: u16, minimum: u32) -> u32 {
+    if redundancy == 0 {
+        return current;
+    }
+    let index = usize::from(redundancy).min(PRICE_CHANGE_RATES.len().saturating_sub(1));
+    let Some(&rate) = PRICE_CHANGE_RATES.get(index) else {
+        return current;
+    };
+
+    // A u32 price upscaled by 2^10 times a rate below 2^21 stays under 2^63,
+    // so the saturating ops never engage.
+    let upscaled = u64::from(current) << PRICE_UPSCALE_BITS;
+    let adjusted = rate.saturating_mul(upscaled) / PRICE_BASE;
+    let floored = adjusted.max(u64::from(minimum) << PRICE_UPSCALE_BITS);
+    u32::try_from(floored >> PRICE_UPSCALE_BITS).unwrap_or(u32::MAX)
+}
+
+// Redistribution Claim Proofs
+
+/// Sibling hashes in a BMT inclusion proof for one segment of a full chunk:
+/// `log2` of the 128 segments of a 4096-byte body.
+pub const CLAIM_PROOF_SEGMENTS: usize = 7;
+
+/// Errors from validating a [`ClaimProof`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ClaimProofError {
+    /// A proof array does not hold [`CLAIM_PROOF_SEGMENTS`] hashes.
+    #[error("{field} holds {actual} segments, expected {expected}")]
+    InvalidSegmentCount {
+        /// The offending field.
+        field: &'static str,
+        /// Required number of segments.
+        expected: usize,
+        /// Number of segments present.
+        actual: usize,
+    },
+}
+
+/// The arguments of [`IRedistribution::claimCall`] under meaningful names.
+///
+/// Each `proof_segments*` array is a BMT inclusion proof of
+/// [`CLAIM_PROOF_SEGMENTS`] sibling hashes; [`from_call`](Self::from_call)
+/// and [`validate`](Self::validate) reject any other length.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClaimProof {
+    /// Proof of the witness chunk's segment in the reserve commitment.
+    pub proof_segments: Vec<B256>,
+    /// The segment proven by [`proof_segments`](Self::proof_segments).
+    pub prove_segment: B256,
+    /// Proof of the segment in the witness chunk's original content.
+    pub proof_segments2: Vec<B256>,
+    /// The segment proven by [`proof_segments2`](Self::proof_segments2).
+    pub prove_segment2: B256,
+    /// Span of the witness chunk.
+    pub chunk_span: u64,
+    /// Proof of the segment in the witness chunk's transformed content.
+    pub proof_segments3: Vec<B256>,
+}
+
+impl ClaimProof {
+    /// Checks every proof array holds [`CLAIM_PROOF_SEGMENTS`] hashes.
+    ///
+    /// # Errors
+    ///
+    /// [`ClaimProofError::InvalidSegmentCount`] for the first array that does
+    /// not.
+    pub fn validate(&self) -> Result<(), ClaimProofError> {
+        for (field, segments) in [
+            ("proof_segments", &self.proof_segments),
+            ("proof_segments2", &self.proof_segments2),
+            ("proof_segments3", &self.proof_segments3),
+        ] {
+            if segments.len() != CLAIM_PROOF_SEGMENTS {
+                return Err(ClaimProofError::InvalidSegmentCount {
+                    field,
+                    expected: CLAIM_PROOF_SEGMENTS,
+                    actual: segments.len(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Builds the contract call carrying this proof.
+    ///
+    /// The call is built as given; [`validate`](Self::validate) first to
+    /// avoid sending a claim the contract will revert.
+    pub fn to_call(&self) -> IRedistribution::claimCall {
+        IRedistribution::claimCall {
+            proofSegments: self.proof_segments.clone(),
+            proveSegment: self.prove_segment,
+            proofSegments2: self.proof_segments2.clone(),
+            proveSegment2: self.prove_segment2,
+            chunkSpan: self.chunk_span,
+            proofSegments3: self.proof_segments3.clone(),
+        }
+    }
+
+    /// Reads the proof out of a decoded contract call.
+    ///
+    /// # Errors
+    ///
+    /// [`ClaimProofError::InvalidSegmentCount`] when a proof array has the
+    /// wrong length.
+    pub fn from_call(call: IRedistribution::claimCall) -> Result<Self, ClaimProofError> {
+        let proof = Self {
+            proof_segments: call.proofSegments,
+            prove_segment: call.proveSegment,
+            proof_segments2: call.proofSegments2,
+            prove_segment2: call.proveSegment2,
+            chunk_span: call.chunkSpan,
+            proof_segments3: call.proofSegments3,
+        };
+        proof.validate()?;
+        Ok(proof)
+    }
+}
+
 // Swap Contract Interfaces (Chequebook)
 
 #[cfg(feature = "serde")]
@@ -246,7 +532,9 @@ sol! {
     /// The EIP-712 domain uses:
     /// - Name: "Chequebook"
     /// - Version: "1.0"
-    /// - ChainId: network-specific (100 for Gnosis, 11155111 for Sepolia)
+    /// - ChainId: network-specific ([`GNOSIS_CHAIN_ID`], [`SEPOLIA_CHAIN_ID`])
+    ///
+    /// Sign the hash from [`SwapChainConfig::cheque_signing_hash`].
     #[derive(Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
     struct Cheque {
         address chequebook;
@@ -263,7 +551,9 @@ sol! {
     /// The EIP-712 domain uses:
     /// - Name: "Chequebook"
     /// - Version: "1.0"
-    /// - ChainId: network-specific (100 for Gnosis, 11155111 for Sepolia)
+    /// - ChainId: network-specific ([`GNOSIS_CHAIN_ID`], [`SEPOLIA_CHAIN_ID`])
+    ///
+    /// Sign the hash from [`SwapChainConfig::cheque_signing_hash`].
     #[derive(Debug, PartialEq, Eq)]
     struct Cheque {
         address chequebook;
@@ -350,6 +640,132 @@ sol! {
     }
 }
 
+impl ChequebookFactory {
+    /// Returns the chequebook address from the `SimpleSwapDeployed` event
+    /// this factory emitted among `logs`, typically a `deploySimpleSwap`
+    /// receipt's.
+    ///
+    /// Logs from any other emitter are skipped, so a contract the deployment
+    /// calls into cannot spoof the result. Returns `None` when no such event
+    /// is present.
+    pub fn parse_deployed(&self, logs: &[alloy_primitives::Log]) -> Option<Address> {
+        use alloy_sol_types::SolEvent;
+
+        logs.iter()
+            .filter(|log| log.address == self.address)
+            .find_map(|log| IChequebookFactory::SimpleSwapDeployed::decode_log(log).ok())
+            .map(|event| event.contractAddress)
+    }
+
+    /// Predicts the chequebook address `deploySimpleSwap(_, _, salt)` will
+    /// return when sent to this factory by `deployer`, before deploying it.
+    ///
+    /// The factory deploys each chequebook as an EIP-1167 minimal proxy of
+    /// its `master` implementation through CREATE2, salted with
+    /// `keccak256(abi.encode(msg.sender, salt))`. The proxy's init code, and
+    /// so its hash, depends only on `master`, which the factory reports
+    /// through [`IChequebookFactory::masterCall`]. Bee sends the deployment
+    /// from the issuer's own account, so `deployer` is usually the issuer.
+    ///
+    /// Lets a client fund a chequebook address before deploying it.
+    #[must_use]
+    pub fn predict_address(&self, master: Address, deployer: Address, salt: B256) -> Address {
+        let init_code = [
+            CLONE_INIT_PREFIX.as_slice(),
+            master.as_slice(),
+            CLONE_INIT_SUFFIX.as_slice(),
+        ]
+        .concat();
+
+        let mut salt_preimage = [0u8; 64];
+        let (word, rest) = salt_preimage.split_at_mut(32);
+        if let Some(tail) = word.get_mut(12..) {
+            tail.copy_from_slice(deployer.as_slice());
+        }
+        rest.copy_from_slice(salt.as_slice());
+
+        self.address
+            .create2_from_code(alloy_primitives::keccak256(salt_preimage), init_code)
+    }
+}
+
+/// EIP-1167 minimal proxy init code before the implementation address, as
+/// emitted by OpenZeppelin's `Clones.cloneDeterministic`.
+const CLONE_INIT_PREFIX: [u8; 20] =
+    alloy_primitives::hex!("3d602d80600a3d3981f3363d3d373d3d3d363d73");
+
+/// EIP-1167 minimal proxy init code after the implementation address.
+const CLONE_INIT_SUFFIX: [u8; 15] = alloy_primitives::hex!("5af43d82803e903d91602b57fd5bf3");
+
+/// Chain id of Gnosis Chain, where mainnet Swarm settles.
+pub const GNOSIS_CHAIN_ID: u64 = 100;
+
+/// Chain id of Sepolia, where testnet Swarm settles.
+pub const SEPOLIA_CHAIN_ID: u64 = 11155111;
+
+/// The chain a chequebook network settles on.
+///
+/// Ties the EIP-712 domain a [`Cheque`] is signed under to the factory that
+/// deploys that network's chequebooks. Use [`mainnet::SWAP_CHAIN`] or
+/// [`testnet::SWAP_CHAIN`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapChainConfig {
+    /// Chain id of the settlement chain.
+    pub chain_id: u64,
+    /// Chequebook factory deployed on that chain.
+    pub chequebook_factory: Address,
+}
+
+impl SwapChainConfig {
+    /// Creates a configuration for a chain.
+    #[must_use]
+    pub const fn new(chain_id: u64, chequebook_factory: Address) -> Self {
+        Self {
+            chain_id,
+            chequebook_factory,
+        }
+    }
+
+    /// The EIP-712 domain cheques on this chain are signed under.
+    ///
+    /// Matches the chequebook contract: name `Chequebook`, version `1.0`, this
+    /// chain id, and no verifying contract.
+    #[must_use]
+    pub const fn eip712_domain(&self) -> alloy_sol_types::Eip712Domain {
+        alloy_sol_types::Eip712Domain::new(
+            Some(alloc::borrow::Cow::Borrowed("Chequebook")),
+            Some(alloc::borrow::Cow::Borrowed("1.0")),
+            Some(alloy_primitives::U256::from_limbs([self.chain_id, 0, 0, 0])),
+            None,
+            None,
+        )
+    }
+
+    /// The EIP-712 hash the chequebook issuer signs for `cheque`.
+    ///
+    /// ```
+    /// use alloy_primitives::{Address, U256};
+    /// use nectar_contracts::{Cheque, mainnet, testnet};
+    ///
+    /// let cheque = Cheque {
+    ///     chequebook: Address::repeat_byte(1),
+    ///     beneficiary: Address::repeat_byte(2),
+    ///     cumulativePayout: U256::from(500),
+    /// };
+    /// // The chain id keeps a cheque from being replayed on the other network.
+    /// assert_ne!(
+    ///     mainnet::SWAP_CHAIN.cheque_signing_hash(&cheque),
+    ///     testnet::SWAP_CHAIN.cheque_signing_hash(&cheque),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn cheque_signing_hash(&self, cheque: &Cheque) -> B256 {
+        use alloy_sol_types::SolStruct;
+
+        cheque.eip712_signing_hash(&self.eip712_domain())
+    }
+}
+
 // Gnosis Chain Mainnet Deployments
 
 /// Gnosis Chain mainnet contract deployments.
@@ -395,6 +811,21 @@ pub mod mainnet {
         address!("A57A50a831B31c904A770edBCb706E03afCdbd94"),
         39939970,
     );
+
+    /// Every deployment above, for iteration.
+    pub const DEPLOYMENTS: Deployments = Deployments {
+        bzz_token: BZZ_TOKEN,
+        postage_stamp: POSTAGE_STAMP,
+        staking: STAKING,
+        redistribution: REDISTRIBUTION,
+        storage_price_oracle: STORAGE_PRICE_ORACLE,
+        chequebook_factory: CHEQUEBOOK_FACTORY,
+        swap_price_oracle: SWAP_PRICE_ORACLE,
+    };
+
+    /// Gnosis Chain, where mainnet chequebooks settle.
+    pub const SWAP_CHAIN: SwapChainConfig =
+        SwapChainConfig::new(GNOSIS_CHAIN_ID, CHEQUEBOOK_FACTORY.address);
 }
 
 // Sepolia Testnet Deployments
@@ -442,6 +873,21 @@ pub mod testnet {
         address!("1814e9b3951Df0CB8e12b2bB99c5594514588936"),
         4752810,
     );
+
+    /// Every deployment above, for iteration.
+    pub const DEPLOYMENTS: Deployments = Deployments {
+        bzz_token: BZZ_TOKEN,
+        postage_stamp: POSTAGE_STAMP,
+        staking: STAKING,
+        redistribution: REDISTRIBUTION,
+        storage_price_oracle: STORAGE_PRICE_ORACLE,
+        chequebook_factory: CHEQUEBOOK_FACTORY,
+        swap_price_oracle: SWAP_PRICE_ORACLE,
+    };
+
+    /// Sepolia, where testnet chequebooks settle.
+    pub const SWAP_CHAIN: SwapChainConfig =
+        SwapChainConfig::new(SEPOLIA_CHAIN_ID, CHEQUEBOOK_FACTORY.address);
 }
 
 #[cfg(test)]
@@ -470,6 +916,222 @@ mod tests {
         assert_ne!(testnet::SWAP_PRICE_ORACLE.address, Address::ZERO);
     }
 
+    #[test]
+    fn test_deployments_iter_covers_every_contract() {
+        for deployments in [mainnet::DEPLOYMENTS, testnet::DEPLOYMENTS] {
+            let entries: Vec<_> = deployments.iter().collect();
+            assert_eq!(entries.len(), 7);
+            assert!(
+                entries
+                    .iter()
+                    .all(|(_, address, _)| *address != Address::ZERO)
+            );
+        }
+        assert!(
+            mainnet::DEPLOYMENTS
+                .iter()
+                .any(|entry| entry == ("postage_stamp", mainnet::POSTAGE_STAMP.address, 31305656))
+        );
+    }
+
+    #[test]
+    fn test_deployments_earliest_block_skips_unrecorded() {
+        assert_eq!(mainnet::DEPLOYMENTS.earliest_block(), 31305656);
+        assert_eq!(testnet::DEPLOYMENTS.earliest_block(), 4752810);
+    }
+
+    #[test]
+    fn test_token_calldata_helpers() {
+        use alloy_primitives::U256;
+        use alloy_sol_types::SolCall;
+
+        let owner = Address::repeat_byte(1);
+        let spender = mainnet::POSTAGE_STAMP.address;
+
+        let approve = token::approve_call(spender, U256::from(1000));
+        assert_eq!(approve[..4], IERC20::approveCall::SELECTOR);
+        let decoded = IERC20::approveCall::abi_decode(&approve).unwrap();
+        assert_eq!(
+            (decoded.spender, decoded.amount),
+            (spender, U256::from(1000))
+        );
+
+        let allowance = token::allowance_call(owner, spender);
+        let decoded = IERC20::allowanceCall::abi_decode(&allowance).unwrap();
+        assert_eq!((decoded.owner, decoded.spender), (owner, spender));
+
+        let returned = U256::from(42).to_be_bytes::<32>();
+        assert_eq!(token::decode_allowance(&returned).unwrap(), U256::from(42));
+        assert!(token::decode_allowance(&returned[..31]).is_err());
+    }
+
+    #[test]
+    fn test_claim_proof_round_trips_through_calldata() {
+        use alloy_sol_types::SolCall;
+
+        let segments = |byte| vec![B256::repeat_byte(byte); CLAIM_PROOF_SEGMENTS];
+        let proof = ClaimProof {
+            proof_segments: segments(1),
+            prove_segment: B256::repeat_byte(2),
+            proof_segments2: segments(3),
+            prove_segment2: B256::repeat_byte(4),
+            chunk_span: 4096,
+            proof_segments3: segments(5),
+        };
+
+        let calldata = proof.to_call().abi_encode();
+        let call = IRedistribution::claimCall::abi_decode(&calldata).unwrap();
+        assert_eq!(ClaimProof::from_call(call).unwrap(), proof);
+    }
+
+    #[test]
+    fn test_claim_proof_rejects_wrong_segment_counts() {
+        let mut proof = ClaimProof {
+            proof_segments: vec![B256::ZERO; CLAIM_PROOF_SEGMENTS],
+            prove_segment: B256::ZERO,
+            proof_segments2: vec![B256::ZERO; CLAIM_PROOF_SEGMENTS],
+            prove_segment2: B256::ZERO,
+            chunk_span: 0,
+            proof_segments3: vec![B256::ZERO; CLAIM_PROOF_SEGMENTS + 1],
+        };
+        let err = ClaimProofError::InvalidSegmentCount {
+            field: "proof_segments3",
+            expected: CLAIM_PROOF_SEGMENTS,
+            actual: CLAIM_PROOF_SEGMENTS + 1,
+        };
+        assert_eq!(proof.validate(), Err(err.clone()));
+        assert_eq!(ClaimProof::from_call(proof.to_call()), Err(err));
+
+        proof.proof_segments3.truncate(CLAIM_PROOF_SEGMENTS);
+        proof.proof_segments.clear();
+        assert!(matches!(
+            proof.validate(),
+            Err(ClaimProofError::InvalidSegmentCount {
+                field: "proof_segments",
+                actual: 0,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_postage_event_topics_match_bee() {
+        use alloy_primitives::b256;
+        use alloy_sol_types::SolEvent;
+
+        assert_eq!(
+            IPostageStamp::BatchCreated::SIGNATURE_HASH,
+            b256!("0x9b088e2c89b322a3c1d81515e1c88db3d386d022926f0e2d0b9b5813b7413d58")
+        );
+    }
+
+    #[test]
+    fn test_parse_deployed_chequebook() {
+        use alloy_primitives::{Log, b256};
+        use alloy_sol_types::SolEvent;
+
+        assert_eq!(
+            IChequebookFactory::SimpleSwapDeployed::SIGNATURE_HASH,
+            b256!("0xc0ffc525a1c7689549d7f79b49eca900e61ac49b43d977f680bcc3b36224c004")
+        );
+
+        let factory = mainnet::CHEQUEBOOK_FACTORY;
+        let chequebook = Address::repeat_byte(0xcb);
+        let deployed = |emitter: Address, contract: Address| Log {
+            address: emitter,
+            data: IChequebookFactory::SimpleSwapDeployed {
+                contractAddress: contract,
+            }
+            .encode_log_data(),
+        };
+        let transfer = Log {
+            address: mainnet::BZZ_TOKEN.address,
+            data: IERC20::Transfer {
+                from: Address::ZERO,
+                to: chequebook,
+                value: U256::from(1),
+            }
+            .encode_log_data(),
+        };
+
+        let logs = [
+            transfer,
+            deployed(Address::repeat_byte(9), Address::repeat_byte(0xee)),
+            deployed(factory.address, chequebook),
+        ];
+        assert_eq!(factory.parse_deployed(&logs), Some(chequebook));
+        assert_eq!(factory.parse_deployed(&logs[..2]), None);
+        assert_eq!(testnet::CHEQUEBOOK_FACTORY.parse_deployed(&logs), None);
+    }
+
+    #[test]
+    fn test_predict_chequebook_address() {
+        let factory = mainnet::CHEQUEBOOK_FACTORY;
+        let master = Address::repeat_byte(0x11);
+        let deployer = Address::repeat_byte(0x22);
+        let salt = B256::repeat_byte(0x33);
+
+        // A regression value for synthetic inputs, not a chain deployment:
+        // it guards the init code and salt layout against change but does
+        // not show they match the deployed factory.
+        let predicted = factory.predict_address(master, deployer, salt);
+        assert_eq!(
+            predicted,
+            address!("19fd14b9cd052631fb836fdff66c02fce391bf90")
+        );
+
+        // Every input moves the address.
+        assert_ne!(
+            factory.predict_address(master, Address::repeat_byte(0x23), salt),
+            predicted
+        );
+        assert_ne!(
+            factory.predict_address(Address::repeat_byte(0x12), deployer, salt),
+            predicted
+        );
+        assert_ne!(
+            factory.predict_address(master, deployer, B256::repeat_byte(0x34)),
+            predicted
+        );
+        assert_ne!(
+            testnet::CHEQUEBOOK_FACTORY.predict_address(master, deployer, salt),
+            predicted
+        );
+    }
+
+    /// A `deploySimpleSwap` deployment read from Gnosis Chain, one
+    /// `key = value` per line: `tx` (the deployment transaction), `master`
+    /// (the factory's `master()`), `deployer` (the transaction sender),
+    /// `salt` and `chequebook` (the `SimpleSwapDeployed` address).
+    const MAINNET_DEPLOYMENT: &str = "tests/fixtures/mainnet_simple_swap_deployment.txt";
+
+    #[test]
+    #[ignore = "needs a deploySimpleSwap deployment recorded at tests/fixtures/mainnet_simple_swap_deployment.txt"]
+    fn test_predict_matches_mainnet_deployment() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(MAINNET_DEPLOYMENT);
+        let text = std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("no deployment at {}: {e}", path.display()));
+        let fields: std::collections::BTreeMap<&str, &str> = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let (key, value) = line.split_once('=').expect("key = value");
+                (key.trim(), value.trim())
+            })
+            .collect();
+        assert!(!fields["tx"].is_empty());
+        let master: Address = fields["master"].parse().unwrap();
+        let deployer: Address = fields["deployer"].parse().unwrap();
+        let salt: B256 = fields["salt"].parse().unwrap();
+        let chequebook: Address = fields["chequebook"].parse().unwrap();
+
+        assert_eq!(
+            mainnet::CHEQUEBOOK_FACTORY.predict_address(master, deployer, salt),
+            chequebook
+        );
+    }
+
     #[test]
     fn test_sol_types_generated() {
         let _ = IERC20::balanceOfCall {
@@ -488,4 +1150,74 @@ mod tests {
             salt: [0u8; 32].into(),
         };
     }
+
+    #[test]
+    fn test_predict_adjusted_price_known_pairs() {
+        // (current, redundancy, minimum) -> expected, computed from the
+        // deployed oracle's integer arithmetic.
+        let cases = [
+            ((24_000, 1, 24_000), 24_014),
+            ((24_000, 4, 24_000), 24_000),
+            ((24_000, 8, 0), 23_980),
+            ((30_000, 8, 24_000), 29_975),
+            ((100_000, 2, 24_000), 100_040),
+            ((24_001, 6, 24_000), 24_000),
+        ];
+        for ((current, redundancy, minimum), expected) in cases {
+            assert_eq!(
+                predict_adjusted_price(current, redundancy, minimum),
+                expected,
+                "current={current} redundancy={redundancy} minimum={minimum}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_predict_adjusted_price_clamps_redundancy() {
+        for redundancy in [9, 100, u16::MAX] {
+            assert_eq!(
+                predict_adjusted_price(24_000, redundancy, 0),
+                predict_adjusted_price(24_000, 8, 0)
+            );
+        }
+    }
+
+    #[test]
+    fn test_predict_adjusted_price_edges() {
+        // Zero redundancy reverts on chain: the price is left as is.
+        assert_eq!(predict_adjusted_price(24_000, 0, 0), 24_000);
+        // The floor applies even when the current price is below it.
+        assert_eq!(predict_adjusted_price(10, 4, 24_000), 24_000);
+        // Overflow past u32 saturates.
+        assert_eq!(predict_adjusted_price(u32::MAX, 1, 0), u32::MAX);
+    }
+
+    #[test]
+    fn test_swap_chain_domain() {
+        use alloy_primitives::keccak256;
+        use alloy_sol_types::SolValue;
+
+        assert_eq!(mainnet::SWAP_CHAIN.chain_id, GNOSIS_CHAIN_ID);
+        assert_eq!(
+            mainnet::SWAP_CHAIN.chequebook_factory,
+            mainnet::CHEQUEBOOK_FACTORY.address
+        );
+        assert_eq!(testnet::SWAP_CHAIN.chain_id, SEPOLIA_CHAIN_ID);
+        assert_eq!(
+            testnet::SWAP_CHAIN.chequebook_factory,
+            testnet::CHEQUEBOOK_FACTORY.address
+        );
+
+        // The separator the chequebook contract derives for its domain.
+        let expected = keccak256(
+            (
+                keccak256("EIP712Domain(string name,string version,uint256 chainId)"),
+                keccak256("Chequebook"),
+                keccak256("1.0"),
+                U256::from(GNOSIS_CHAIN_ID),
+            )
+                .abi_encode(),
+        );
+        assert_eq!(mainnet::SWAP_CHAIN.eip712_domain().separator(), expected);
+    }
 }
@@ -146,11 +146,11 @@ pub use geometry::{DEFAULT_BODY_SIZE, Mode, branches, max_depth};
     not(target_arch = "wasm32"),
     not(feature = "unsync")
 ))]
-pub use parallel::{ReadAt, ReadAtError, split_read_at};
+pub use parallel::{ReadAt, ReadAtError, hash_read_at, split_read_at};
 #[cfg(feature = "std")]
 pub use read::{
     AnyFile, CollectError, DownloadBuilder, DownloadError, File, FileFrames, FileReader,
-    FileStream, OpenError, Progress, ProgressFn, ReadBuilder, SeekPastEnd,
+    FileStream, OpenError, Progress, ProgressFn, ProveError, ReadBuilder, SeekPastEnd,
 };
 #[cfg(feature = "std")]
 pub use sink::FsSink;
@@ -159,7 +159,10 @@ pub use sink::{DataSink, MemSink, MemSinkError};
 #[cfg_attr(docsrs, doc(cfg(feature = "encryption")))]
 pub use split::{KeyError, KeySource, RandomKeys};
 #[cfg(feature = "std")]
-pub use split::{SealError, Sealed, Split, SplitError, SplitMode, SplitStats, collect_into};
+pub use split::{
+    SealError, Sealed, Split, SplitError, SplitMode, SplitStats, collect_chunks, collect_into,
+    intermediate_chunk,
+};
 #[cfg(feature = "std")]
 pub use store::{BoxedStore, BoxedStoreError, DynAnyFile, DynFile, DynFileReader, DynFileStream};
 #[cfg(feature = "std")]
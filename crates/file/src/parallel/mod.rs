@@ -4,7 +4,8 @@
 //! a hash-windowed [`Split`], so leaf sealing runs on the rayon pool while
 //! the ascent, intermediate sealing and put dispatch stay on the calling
 //! task. Retained memory stays at the hash window of leaf bodies plus the
-//! spine and the put window.
+//! spine and the put window. [`hash_read_at`] runs the same ingest without
+//! a store, for callers that need only the root.
 
 mod error;
 mod source;
@@ -12,9 +13,10 @@ mod source;
 mod tests;
 
 use alloc::vec;
+use core::convert::Infallible;
 use core::future::poll_fn;
 
-use nectar_primitives::chunk::AnyChunkSet;
+use nectar_primitives::chunk::{AnyChunkSet, Chunk, Verified};
 use nectar_primitives::store::ChunkPut;
 
 pub use error::ReadAtError;
@@ -86,6 +88,47 @@ where
         .map_err(ReadAtError::from)
 }
 
+/// Compute the root of `source` under mode `M`, sealing leaves on the rayon
+/// pool and discarding every sealed chunk.
+///
+/// The store-less companion to [`split_read_at`]: the tree is built by the
+/// same ascent, so for deterministic modes the root equals the streaming
+/// split of the same bytes. Only source failures can surface; the discarding
+/// store never refuses a put.
+///
+/// ```
+/// use nectar_file::{Plain, hash_read_at};
+///
+/// # nectar_testing::run(async {
+/// let data = vec![7u8; 10_000];
+/// let root = hash_read_at::<_, Plain, 4096>(data).await.unwrap();
+/// assert_eq!(root.as_bytes().len(), 32);
+/// # });
+/// ```
+pub async fn hash_read_at<R, M, const B: usize>(
+    source: R,
+) -> Result<M::Root, ReadAtError<Infallible>>
+where
+    R: ReadAt,
+    M: SplitMode + Default + Clone,
+    M::Ref: Send,
+{
+    split_read_at::<R, Discard, M, B>(source, Discard, PutWindow::DEFAULT).await
+}
+
+/// Store that accepts and drops every chunk, so a split yields only its
+/// root.
+#[derive(Clone, Copy, Debug, Default)]
+struct Discard;
+
+impl<const B: usize> ChunkPut<AnyChunkSet<B>> for Discard {
+    type Error = Infallible;
+
+    async fn put(&self, _chunk: Chunk<Verified, AnyChunkSet<B>>) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
 /// Fill `buf` from `offset`, looping over short reads; running out of
 /// source is an error, never a silent truncation.
 fn read_full<R, E>(source: &R, offset: u64, buf: &mut [u8]) -> Result<(), ReadAtError<E>>
@@ -435,3 +435,27 @@ fn read_at_sources_honour_offsets_and_ends() {
     assert_eq!(buf, data[60..]);
     assert_eq!(ReadAt::len(&owned).unwrap(), 100);
 }
+
+/// Property: the store-less pool hash is byte-identical to the serial split
+/// root over arbitrary bytes.
+mod properties {
+    use proptest::prelude::*;
+
+    use super::{BRANCHES, TINY, stream_split};
+    use crate::parallel::hash_read_at;
+    use crate::walk::Plain;
+    use nectar_testing::run;
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn hashed_root_matches_the_streaming_engine(
+            data in proptest::collection::vec(any::<u8>(), 0..6 * BRANCHES * TINY)
+        ) {
+            let (streamed_root, _) = stream_split::<TINY>(&data);
+            let root = run(hash_read_at::<_, Plain, TINY>(data)).unwrap();
+            prop_assert_eq!(root, streamed_root);
+        }
+    }
+}
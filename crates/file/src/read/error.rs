@@ -2,6 +2,7 @@
 
 use alloc::collections::TryReserveError;
 
+use nectar_primitives::PrimitivesError;
 use nectar_primitives::chunk::ChunkAddress;
 
 use crate::walk::{DecodeError, WalkError};
@@ -75,3 +76,22 @@ pub struct SeekPastEnd {
     /// The reader's effective length.
     pub effective_len: u64,
 }
+
+/// Failure proving a file offset against the root.
+#[derive(Debug, thiserror::Error)]
+pub enum ProveError<E> {
+    /// The offset lies at or past the end of the file.
+    #[error("offset {offset} past file length {len}")]
+    PastEnd {
+        /// Requested offset.
+        offset: u64,
+        /// File length in bytes.
+        len: u64,
+    },
+    /// The descent to the leaf failed.
+    #[error(transparent)]
+    Walk(#[from] WalkError<E>),
+    /// A chunk-level proof could not be generated.
+    #[error(transparent)]
+    Proof(#[from] PrimitivesError),
+}
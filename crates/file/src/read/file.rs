@@ -3,17 +3,19 @@
 use alloc::vec::Vec;
 use core::fmt;
 
+use nectar_primitives::bmt::{FileProof, HASH_SIZE, Hasher, Prover, Span};
 use nectar_primitives::chunk::encryption::{EncryptedChunkRef, EncryptionKey, transcrypt_in_place};
 use nectar_primitives::chunk::{AnyChunkSet, ChunkAddress, ChunkOps};
 use nectar_primitives::store::TrustedGet;
 use nectar_primitives::{DEFAULT_BODY_SIZE, EntryRef};
 
 use super::download::DownloadBuilder;
-use super::error::{CollectError, OpenError};
+use super::error::{CollectError, OpenError, ProveError};
 use super::reader::ReadBuilder;
 use crate::config::Window;
 use crate::geometry::Mode;
-use crate::walk::{DecodeError, Encrypted, Plain, WalkMode};
+use crate::num::{fan_out, u64_from_u32, u64_from_usize};
+use crate::walk::{DecodeError, Encrypted, Plain, ShapeError, WalkError, WalkMode, child_subspan};
 
 /// One opened file: the root reference resolved to its address, context and
 /// total span. Opening fetches the root chunk once; reads re-fetch it so the
@@ -97,6 +99,66 @@ where
     }
 }
 
+impl<S> File<S, Plain>
+where
+    S: TrustedGet<AnyChunkSet>,
+{
+    /// Prove the 32-byte segment holding `offset` against the file root.
+    ///
+    /// Descends root to leaf, fetching one chunk per level, and chains the
+    /// leaf's segment proof with each parent's proof of its child reference.
+    /// Check the result with
+    /// [`verify_file_proof`](nectar_primitives::bmt::verify_file_proof).
+    pub async fn prove(&self, offset: u64) -> Result<FileProof, ProveError<S::Error>> {
+        if offset >= self.span {
+            return Err(ProveError::PastEnd {
+                offset,
+                len: self.span,
+            });
+        }
+        let body = u64_from_usize(DEFAULT_BODY_SIZE);
+        let branches = fan_out(body, u64_from_u32(Plain::MODE.ref_size()));
+        let mut address = self.root;
+        let mut start = 0u64;
+        let mut span = self.span;
+        let mut path = Vec::new();
+        loop {
+            let chunk = fetch_node(&self.store, address).await?;
+            let data = chunk.data();
+            let mut hasher: Hasher = Hasher::new();
+            hasher.set_span(chunk.span());
+            let within = offset.saturating_sub(start);
+            if span <= body {
+                let segment = within
+                    .checked_div(u64_from_usize(HASH_SIZE))
+                    .unwrap_or_default();
+                let leaf = hasher.generate_proof(data, usize_from_u64(segment))?;
+                path.reverse();
+                return Ok(FileProof::new(leaf, path));
+            }
+            let sub = child_subspan(span, body, branches);
+            // A positive span keeps the child span positive.
+            let index = within.checked_div(sub).unwrap_or_default();
+            let mut refs = data
+                .get(usize_from_u64(index).saturating_mul(HASH_SIZE)..)
+                .unwrap_or_default();
+            let Some((child, ())) = Plain::take_ref(&mut refs) else {
+                return Err(WalkError::from(ShapeError::Arity {
+                    offset: start,
+                    expected: span.div_ceil(sub),
+                    have: index,
+                })
+                .into());
+            };
+            path.push(hasher.generate_proof(data, usize_from_u64(index))?);
+            let delta = index.saturating_mul(sub);
+            start = start.saturating_add(delta);
+            span = sub.min(span.saturating_sub(delta));
+            address = child;
+        }
+    }
+}
+
 impl<S, const B: usize> File<S, Encrypted, B>
 where
     S: TrustedGet<AnyChunkSet<B>> + Clone + 'static,
@@ -115,9 +177,9 @@ where
         }
         // The span header rides its own keystream offset past the body's
         // block count, so its bytes never share keystream with body bytes.
-        let mut span_bytes = chunk.span().to_le_bytes();
+        let mut span_bytes = Span::new(chunk.span()).to_le_bytes();
         transcrypt_in_place(&key, span_counter(B), &mut span_bytes);
-        let span = u64::from_le_bytes(span_bytes);
+        let span = Span::from_le_bytes(span_bytes).get();
         Ok(Self {
             store,
             root: address,
@@ -215,6 +277,35 @@ impl<S, const B: usize> From<File<S, Encrypted, B>> for AnyFile<S, B> {
     }
 }
 
+/// Why a checked fetch failed, before the caller names it in its own error.
+enum FetchFailure<E> {
+    /// The store failed the fetch.
+    Store(E),
+    /// The store answered with the chunk at this other address.
+    Mismatch(ChunkAddress),
+}
+
+/// Fetch one envelope, insisting the store answered for the requested
+/// address; `map_err` names a failure in the caller's error type.
+async fn fetch_checked<S, E, const B: usize>(
+    store: &S,
+    address: ChunkAddress,
+    map_err: impl FnOnce(FetchFailure<S::Error>) -> E,
+) -> Result<<AnyChunkSet<B> as nectar_primitives::chunk::ChunkRegistry>::Envelope, E>
+where
+    S: TrustedGet<AnyChunkSet<B>>,
+{
+    let chunk = match store.get(&address).await {
+        Ok(chunk) => chunk,
+        Err(source) => return Err(map_err(FetchFailure::Store(source))),
+    };
+    let returned = *chunk.address();
+    if returned != address {
+        return Err(map_err(FetchFailure::Mismatch(returned)));
+    }
+    Ok(chunk.into_envelope())
+}
+
 /// Fetch the root envelope, insisting the store answered for the requested
 /// address.
 async fn fetch_root<S, const B: usize>(
@@ -227,18 +318,42 @@ async fn fetch_root<S, const B: usize>(
 where
     S: TrustedGet<AnyChunkSet<B>>,
 {
-    let chunk = store
-        .get(&address)
-        .await
-        .map_err(|source| OpenError::Fetch { address, source })?;
-    let returned = *chunk.address();
-    if returned != address {
-        return Err(OpenError::AddressMismatch {
+    fetch_checked(store, address, |failure| match failure {
+        FetchFailure::Store(source) => OpenError::Fetch { address, source },
+        FetchFailure::Mismatch(returned) => OpenError::AddressMismatch {
             requested: address,
             returned,
-        });
-    }
-    Ok(chunk.into_envelope())
+        },
+    })
+    .await
+}
+
+/// Fetch one tree node, insisting the store answered for the requested
+/// address.
+async fn fetch_node<S, const B: usize>(
+    store: &S,
+    address: ChunkAddress,
+) -> Result<
+    <AnyChunkSet<B> as nectar_primitives::chunk::ChunkRegistry>::Envelope,
+    WalkError<S::Error>,
+>
+where
+    S: TrustedGet<AnyChunkSet<B>>,
+{
+    fetch_checked(store, address, |failure| match failure {
+        FetchFailure::Store(source) => WalkError::Fetch { address, source },
+        FetchFailure::Mismatch(returned) => WalkError::AddressMismatch {
+            requested: address,
+            returned,
+        },
+    })
+    .await
+}
+
+/// Narrow an in-body index; an index past the address width saturates and
+/// fails the proof's bounds check.
+fn usize_from_u64(value: u64) -> usize {
+    usize::try_from(value).unwrap_or(usize::MAX)
 }
 
 /// Span-header keystream counter: the body's 32-byte block count, one past
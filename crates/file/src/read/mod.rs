@@ -21,7 +21,7 @@ mod reader;
 mod tests;
 
 pub use download::{DownloadBuilder, Progress, ProgressFn};
-pub use error::{CollectError, DownloadError, OpenError, SeekPastEnd};
+pub use error::{CollectError, DownloadError, OpenError, ProveError, SeekPastEnd};
 pub use file::{AnyFile, File};
 pub use frames::FileFrames;
 pub use reader::{FileReader, FileStream, ReadBuilder};
@@ -21,7 +21,7 @@ use nectar_testing::{run, split_fixture};
 #[cfg(feature = "encryption")]
 use crate::testutil::split_encrypted_fixture;
 
-use super::{AnyFile, CollectError, File, FileReader, OpenError, SeekPastEnd};
+use super::{AnyFile, CollectError, File, FileReader, OpenError, ProveError, SeekPastEnd};
 use crate::config::Window;
 use crate::geometry::Mode;
 use crate::walk::{DecodeError, Encrypted, Plain, WalkError, WalkMode};
@@ -596,6 +596,112 @@ fn collect_assembles_the_clipped_range() {
     assert!(run(empty.collect(0)).unwrap().is_empty());
 }
 
+/// A proof ties any byte's leaf chunk to the root of a three-level tree, and
+/// fails against the wrong leaf, a tampered path, or the wrong root.
+#[test]
+fn prove_chains_leaf_to_root() {
+    use nectar_primitives::DEFAULT_BODY_SIZE as BODY;
+    use nectar_primitives::bmt::verify_file_proof;
+
+    // 129 full leaves plus a tail: a root over two intermediates.
+    let data = fill(129 * BODY + 100);
+    let (root, store) = split_fixture::<BODY>(&data);
+    let file = run(File::<_, Plain>::open(store, root)).unwrap();
+
+    for offset in [0, 4_000, 128 * BODY + 64, data.len() - 1] {
+        let proof = run(file.prove(offset as u64)).unwrap();
+        let start = offset / BODY * BODY;
+        let end = (start + BODY).min(data.len());
+        let leaf = ContentChunk::<BODY>::new(data[start..end].to_vec()).unwrap();
+        let leaf = *leaf.address();
+
+        assert_eq!(proof.path.len(), 2);
+        assert_eq!(proof.leaf.segment_index, offset % BODY / 32);
+        let (at, len) = (offset as u64, data.len() as u64);
+        assert!(verify_file_proof(
+            &root.into(),
+            &leaf.into(),
+            at,
+            len,
+            &proof
+        ));
+        assert!(proof.verify(&root.into(), &leaf.into(), at, len));
+        assert!(!verify_file_proof(
+            &leaf.into(),
+            &leaf.into(),
+            at,
+            len,
+            &proof
+        ));
+        assert!(!verify_file_proof(
+            &root.into(),
+            &root.into(),
+            at,
+            len,
+            &proof
+        ));
+
+        let mut tampered = proof.clone();
+        tampered.path[0].proof_segments[0].0[0] ^= 1;
+        assert!(!verify_file_proof(
+            &root.into(),
+            &leaf.into(),
+            at,
+            len,
+            &tampered
+        ));
+    }
+
+    // A valid proof for one offset does not vouch for another: not in
+    // another leaf, another segment of the same leaf, past the end, or
+    // in a file of another length.
+    let len = data.len() as u64;
+    let proof = run(file.prove(0)).unwrap();
+    let leaf = *ContentChunk::<BODY>::new(data[..BODY].to_vec())
+        .unwrap()
+        .address();
+    assert!(verify_file_proof(
+        &root.into(),
+        &leaf.into(),
+        31,
+        len,
+        &proof
+    ));
+    for (offset, span) in [
+        ((128 * BODY + 64) as u64, len),
+        (BODY as u64, len),
+        (32, len),
+        (len, len),
+        (0, len + 1),
+    ] {
+        assert!(!verify_file_proof(
+            &root.into(),
+            &leaf.into(),
+            offset,
+            span,
+            &proof
+        ));
+    }
+
+    assert!(matches!(
+        run(file.prove(data.len() as u64)),
+        Err(ProveError::PastEnd { .. })
+    ));
+
+    // A single-chunk file proves with an empty path.
+    let (small_root, store) = split_fixture::<BODY>(b"hello swarm");
+    let small = run(File::<_, Plain>::open(store, small_root)).unwrap();
+    let proof = run(small.prove(3)).unwrap();
+    assert!(proof.path.is_empty());
+    assert!(verify_file_proof(
+        &small_root.into(),
+        &small_root.into(),
+        3,
+        11,
+        &proof
+    ));
+}
+
 /// The encrypted width collects the same bytes through the same bound.
 #[cfg(feature = "encryption")]
 #[test]
@@ -12,7 +12,7 @@ use core::task::{Context, Poll, Waker};
 
 use bytes::Bytes;
 use nectar_primitives::DEFAULT_BODY_SIZE;
-use nectar_primitives::bmt::SPAN_SIZE;
+use nectar_primitives::bmt::{SPAN_SIZE, Span};
 use nectar_primitives::chunk::{AnyChunkSet, Chunk, ChunkAddress, Verified};
 use nectar_primitives::store::ChunkPut;
 
@@ -547,7 +547,7 @@ where
     fn spill_leaf(&mut self, mut payload: Vec<u8>) -> Result<(), SplitError<S::Error>> {
         let span = u64_from_usize(payload.len().saturating_sub(SPAN_SIZE));
         if let Some((head, _)) = payload.split_first_chunk_mut::<SPAN_SIZE>() {
-            *head = span.to_le_bytes();
+            *head = Span::new(span).to_le_bytes();
         }
         #[cfg(all(
             feature = "rayon",
@@ -695,7 +695,7 @@ where
         let ref_size = usize::try_from(M::MODE.ref_size()).unwrap_or(usize::MAX);
         let capacity = SPAN_SIZE.saturating_add(refs.len().saturating_mul(ref_size));
         let mut payload = Vec::with_capacity(capacity);
-        payload.extend_from_slice(&span.to_le_bytes());
+        payload.extend_from_slice(&Span::new(span).to_le_bytes());
         for reference in refs {
             M::write_ref(reference, &mut payload);
         }
@@ -46,12 +46,20 @@ use core::convert::Infallible;
 use core::future::poll_fn;
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex, PoisonError};
+use std::vec::Vec;
 
+use bytes::Bytes;
 use nectar_marker::MaybeSync;
-use nectar_primitives::chunk::{AnyChunkSet, Chunk, Verified};
+use nectar_primitives::PrimitivesError;
+use nectar_primitives::bmt::{SPAN_SIZE, Span};
+use nectar_primitives::chunk::{
+    AnyChunkSet, Chunk, ChunkAddress, ChunkError, ChunkOps, ContentChunk, Verified,
+};
 use nectar_primitives::store::ChunkPut;
 
 use crate::config::PutWindow;
+use crate::num::{fan_out, u64_from_usize};
+use crate::walk::child_subspan;
 
 #[cfg(feature = "encryption")]
 #[cfg_attr(docsrs, doc(cfg(feature = "encryption")))]
@@ -112,6 +120,102 @@ where
     Ok(root)
 }
 
+/// Split `data` in memory and return the root with every sealed chunk as
+/// `(address, wire bytes)`, in seal order.
+///
+/// The push-sync input of an upload: each entry is a chunk's address and its
+/// bare wire encoding (`header || span || payload`), leaves and
+/// intermediates alike. The whole tree is retained, so memory grows with
+/// `data`; feed a store through [`collect_into`] to stay within the put
+/// window instead.
+///
+/// ```
+/// # nectar_testing::run(async {
+/// use nectar_file::Plain;
+/// use nectar_file::split::collect_chunks;
+///
+/// let data = vec![7u8; 3 * 4096];
+/// let (root, chunks) = collect_chunks::<Plain, 4096>(&data).await.unwrap();
+/// // Three leaves and the intermediate above them, the root sealed last.
+/// assert_eq!(chunks.len(), 4);
+/// assert_eq!(chunks.last().map(|(address, _)| *address), Some(root));
+/// # });
+/// ```
+pub async fn collect_chunks<M, const B: usize>(
+    data: &[u8],
+) -> Result<(M::Root, Vec<(ChunkAddress, Bytes)>), SplitError<Infallible>>
+where
+    M: SplitMode + Default,
+{
+    let relay = Relay::<B>::default();
+    let root = Split::<_, M, B>::collect(relay.clone(), data).await?;
+    let chunks = core::iter::from_fn(|| relay.pop())
+        .map(|chunk| (*chunk.address(), chunk.into_envelope().into_bytes()))
+        .collect();
+    Ok((root, chunks))
+}
+
+/// Build the plain intermediate chunk over `children`, the references of a
+/// subtree whose data totals `subtree_span` bytes.
+///
+/// An intermediate node's span is the byte length of everything beneath it,
+/// not its own payload length; its payload is the children's addresses in
+/// order. This is the node the split seals at every level above the leaves,
+/// exposed for callers assembling tree shapes of their own. The split carries
+/// a lone trailing reference up a level unwrapped, so a single child is
+/// wrapped here only if asked.
+///
+/// The reference count must be the one `subtree_span` implies: a span within
+/// one body covers exactly the payload, and a larger span one child per
+/// full subtree below it.
+///
+/// ```
+/// use nectar_file::split::intermediate_chunk;
+/// use nectar_primitives::{ChunkOps, DefaultContentChunk};
+///
+/// let left = DefaultContentChunk::new(vec![1u8; 4096]).unwrap();
+/// let right = DefaultContentChunk::new(vec![2u8; 10]).unwrap();
+/// let parent = intermediate_chunk::<4096>(&[*left.address(), *right.address()], 4106).unwrap();
+/// assert_eq!(parent.span(), 4106);
+/// assert_eq!(parent.data().len(), 64);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error when the references overflow the `B`-byte body, or when
+/// their count disagrees with `subtree_span`.
+pub fn intermediate_chunk<const B: usize>(
+    children: &[ChunkAddress],
+    subtree_span: u64,
+) -> Result<ContentChunk<B>, PrimitivesError> {
+    let refs_len = children.len().saturating_mul(ChunkAddress::SIZE);
+    let body = u64_from_usize(B);
+    let implied = if subtree_span <= body {
+        usize::try_from(subtree_span).ok()
+    } else {
+        let branches = fan_out(body, u64_from_usize(ChunkAddress::SIZE));
+        let sub = child_subspan(subtree_span, body, branches);
+        usize::try_from(subtree_span.div_ceil(sub))
+            .ok()
+            .map(|refs| refs.saturating_mul(ChunkAddress::SIZE))
+    };
+    if implied != Some(refs_len) {
+        return Err(ChunkError::invalid_size(
+            "subtree span over a mismatched reference list",
+            implied.unwrap_or(usize::MAX),
+            refs_len,
+        )
+        .into());
+    }
+    let capacity = SPAN_SIZE.saturating_add(children.len().saturating_mul(ChunkAddress::SIZE));
+    let mut payload = Vec::with_capacity(capacity);
+    payload.extend_from_slice(&Span::new(subtree_span).to_le_bytes());
+    for child in children {
+        payload.extend_from_slice(child.as_bytes());
+    }
+    ContentChunk::try_from(Bytes::from(payload))
+}
+
 /// Widen the relay-backed split's error to the borrowed store's error. The
 /// relay is infallible, so the `Put` arm is unreachable.
 fn widen<E>(error: SplitError<Infallible>) -> SplitError<E> {
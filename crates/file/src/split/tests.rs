@@ -14,7 +14,9 @@ use std::vec;
 use std::vec::Vec;
 
 use futures::task::noop_waker;
-use nectar_primitives::chunk::{AnyChunkSet, Chunk, ChunkAddress, Verified};
+use nectar_primitives::chunk::{
+    AnyChunkSet, Chunk, ChunkAddress, ChunkOps, ContentChunk, Verified,
+};
 use nectar_primitives::store::{ChunkGet, ChunkPut, ChunkStoreError};
 use nectar_testing::{run, yield_now};
 
@@ -416,6 +418,54 @@ fn a_failed_put_poisons_the_fuse() {
     assert!(split.is_finished());
 }
 
+#[test]
+fn collected_chunks_rehash_to_their_addresses() {
+    let data = fill(70 * TINY + 5);
+    let (root, chunks) = run(super::collect_chunks::<Plain, TINY>(&data)).unwrap();
+    let (expected_root, store, _) = stream_split::<TINY>(&data, 4, 719, 0);
+    assert_eq!(root, expected_root);
+    assert_eq!(
+        sorted(chunks.iter().map(|(address, _)| *address).collect()),
+        sorted(store.log())
+    );
+    for (address, bytes) in chunks {
+        let chunk = ContentChunk::<TINY>::try_from(bytes).unwrap();
+        assert_eq!(*chunk.address(), address);
+    }
+}
+
+#[test]
+fn intermediate_chunk_matches_the_pinned_tree() {
+    const B: usize = nectar_primitives::DEFAULT_BODY_SIZE;
+    // 4097 bytes: a full leaf and a one-byte leaf under one intermediate.
+    let data = fill(B + 1);
+    let (left, right) = data.split_at(B);
+    let children = [
+        *ContentChunk::<B>::new(left.to_vec()).unwrap().address(),
+        *ContentChunk::<B>::new(right.to_vec()).unwrap().address(),
+    ];
+    let parent = super::intermediate_chunk::<B>(&children, (B + 1) as u64).unwrap();
+    // The 4097-byte root of `DEFAULT_ROOTS`: the crate's own splitter output,
+    // not a bee-computed address.
+    assert_eq!(
+        *parent.address(),
+        pinned("e4bd4e55076ef72a28d8efea9b927fdeaf6b8f2250af3ec586f14dc062237532")
+    );
+
+    // The span is the subtree's, not the payload's: the payload alone
+    // addresses differently.
+    let own_span = super::intermediate_chunk::<B>(&children, 64).unwrap();
+    assert_ne!(own_span.address(), parent.address());
+
+    // A span the references cannot cover is refused.
+    assert!(super::intermediate_chunk::<B>(&children, (3 * B) as u64).is_err());
+    assert!(super::intermediate_chunk::<B>(&children, 32).is_err());
+
+    // More references than the body holds do not fit.
+    let too_many = std::vec![ChunkAddress::ZERO; B / ChunkAddress::SIZE + 1];
+    assert!(super::intermediate_chunk::<B>(&too_many, u64::MAX).is_err());
+}
+
 /// A put failing after some succeed surfaces through the `collect_with`
 /// one-shot as a typed `Put` error; `FaultStore` drives the fault and its
 /// counter is shared across the split's per-put store clones.
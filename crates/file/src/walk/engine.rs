@@ -540,7 +540,7 @@ where
 
 /// Child span under a parent covering `span` bytes: the smallest
 /// `body * branches^k` whose full fan-out reaches the parent span.
-const fn child_subspan(span: u64, body: u64, branches: u64) -> u64 {
+pub(crate) const fn child_subspan(span: u64, body: u64, branches: u64) -> u64 {
     let mut sub = body;
     loop {
         match sub.checked_mul(branches) {
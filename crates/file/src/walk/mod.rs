@@ -33,6 +33,7 @@ mod tests;
 use bytes::Bytes;
 
 pub use engine::Walk;
+pub(crate) use engine::child_subspan;
 pub use error::{DecodeError, ShapeError, WalkError};
 pub use mode::{Encrypted, Plain, WalkMode};
 
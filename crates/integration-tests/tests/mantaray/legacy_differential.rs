@@ -3,14 +3,18 @@
 //! Identical submission-order op sequences are replayed on the pinned
 //! `mantaray-old` crate and on the editor; the resulting roots must match
 //! byte for byte. The legacy replay is a fresh single-session build with one
-//! save at the end, which is the sequence's well-defined root.
+//! save at the end, which is the sequence's well-defined root. A single-file
+//! manifest captured from bee, when present, anchors both sides to the
+//! network's own encoding.
 
 use std::collections::BTreeMap;
 
-use nectar_mantaray::ManifestEditor;
-use nectar_primitives::StandardChunkSet;
-use nectar_primitives::chunk::ChunkAddress;
-use nectar_primitives::store::MemoryStore;
+use alloy_primitives::hex;
+use nectar_mantaray::{ManifestEditor, Reader, metadata};
+use nectar_primitives::bmt::DEFAULT_BODY_SIZE;
+use nectar_primitives::chunk::{Chunk, ChunkAddress, ChunkRef, ContentChunk};
+use nectar_primitives::store::{ChunkPut, MemoryStore};
+use nectar_primitives::{EntryRef, StandardChunkSet};
 use nectar_testing::run;
 use proptest::prelude::*;
 
@@ -187,6 +191,17 @@ fn rm(p: &str) -> ScriptOp {
 /// and root metadata interleavings.
 fn corpora() -> Vec<Vec<ScriptOp>> {
     vec![
+        // The shape of a single-file upload: the file under its name with a
+        // content type, served as the index document.
+        vec![
+            ScriptOp::AddMeta(
+                "hello.txt".to_string(),
+                addr_bytes("hello"),
+                "Content-Type".to_string(),
+                "text/plain; charset=utf-8".to_string(),
+            ),
+            ScriptOp::SetIndex("hello.txt".to_string()),
+        ],
         vec![add("app.js.map"), add("app.js")],
         vec![add("app.js"), add("app.js.map")],
         vec![add("abcdef"), add("abc"), rm("abcdef"), add("abcxyz")],
@@ -421,3 +436,111 @@ proptest! {
         prop_assert_eq!(editor_outcome_split(&script, script.len() / 2), want);
     }
 }
+
+/// A single-file manifest captured from bee, one `key = value` per line:
+/// `bee_version`, `command` (the upload invocation), `root`, `file` (the
+/// uploaded data's reference), `filename`, `content_type`, and one `node` per
+/// manifest node as `GET /chunks/{address}` serves it (span and payload, hex).
+/// Lines starting with `#` are comments.
+///
+/// Capture against a dev node:
+///
+/// ```text
+/// bee dev
+/// curl -s -X POST -H "Swarm-Postage-Batch-Id: $BATCH" \
+///     -H "Content-Type: text/plain; charset=utf-8" \
+///     --data-binary @hello.txt "http://localhost:1633/bzz?name=hello.txt"
+/// curl -s http://localhost:1633/chunks/$ADDRESS | xxd -p | tr -d '\n'
+/// ```
+///
+/// starting from the returned root and following every fork reference.
+const BEE_SINGLE_FILE: &str = "tests/mantaray/bee/single_file.txt";
+
+/// Fields of a [`BEE_SINGLE_FILE`] capture.
+struct BeeCapture {
+    fields: BTreeMap<String, String>,
+    nodes: Vec<Vec<u8>>,
+}
+
+impl BeeCapture {
+    fn load(path: &str) -> Self {
+        let full = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(path);
+        let text = std::fs::read_to_string(&full)
+            .unwrap_or_else(|e| panic!("no bee capture at {}: {e}", full.display()));
+        let mut fields = BTreeMap::new();
+        let mut nodes = Vec::new();
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line.split_once('=').expect("key = value");
+            let (key, value) = (key.trim(), value.trim());
+            if key == "node" {
+                nodes.push(hex::decode(value).unwrap());
+            } else {
+                fields.insert(key.to_string(), value.to_string());
+            }
+        }
+        Self { fields, nodes }
+    }
+
+    fn field(&self, key: &str) -> &str {
+        self.fields
+            .get(key)
+            .unwrap_or_else(|| panic!("bee capture lacks `{key}`"))
+    }
+
+    fn address(&self, key: &str) -> ChunkAddress {
+        let bytes: [u8; 32] = hex::decode(self.field(key)).unwrap().try_into().unwrap();
+        ChunkAddress::from(bytes)
+    }
+}
+
+#[test]
+#[ignore = "needs a single-file manifest captured from bee at tests/mantaray/bee/single_file.txt"]
+fn bee_single_file_manifest_round_trips() {
+    let capture = BeeCapture::load(BEE_SINGLE_FILE);
+    assert!(!capture.field("bee_version").is_empty());
+    assert!(!capture.field("command").is_empty());
+    let root = capture.address("root");
+    let file = capture.address("file");
+    let filename = capture.field("filename");
+    let meta: BTreeMap<String, String> = [
+        (
+            "Content-Type".to_string(),
+            capture.field("content_type").to_string(),
+        ),
+        ("Filename".to_string(), filename.to_string()),
+    ]
+    .into();
+
+    let store = Store::new();
+    for image in &capture.nodes {
+        let chunk = ContentChunk::<DEFAULT_BODY_SIZE>::try_from(image.as_slice()).unwrap();
+        run(store.put(Chunk::from_envelope(chunk.into()).unwrap())).unwrap();
+    }
+
+    let reader = Reader::new(&store);
+    let entry = run(reader.get(&root, filename.as_bytes()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        entry.reference(),
+        Some(&EntryRef::Plain(ChunkRef::new(file)))
+    );
+    assert_eq!(entry.metadata(), &meta);
+    let documents = run(reader.get(&root, b"/")).unwrap().unwrap();
+    assert_eq!(
+        documents.metadata().get(metadata::WEBSITE_INDEX_DOCUMENT),
+        Some(&filename.to_string())
+    );
+
+    let (reopened, _) = run(Editor::open(root, store).commit()).unwrap();
+    assert_eq!(reopened, root);
+
+    let mut editor = Editor::new(Store::new());
+    editor.set_index_document(filename);
+    editor.put_with_metadata(filename, file, meta);
+    let (written, _) = run(editor.commit()).unwrap();
+    assert_eq!(written, root);
+}
@@ -77,7 +77,7 @@ fn a_deep_issuer_stamps_through_a_batch_stamper() {
     // The fifth stamp exhausts the bucket at this geometry.
     assert!(matches!(
         stamper.stamp(&address),
-        Err(SigningError::Stamp(StampError::BucketFull {
+        Err(SigningError::Stamp(StampError::ImmutableBatchFull {
             bucket: 0xABCDE,
             capacity: 4
         }))
@@ -0,0 +1,412 @@
+//! A [`BatchFactory`] that transacts with the postage stamp contract.
+
+use core::marker::PhantomData;
+
+use alloy_network::{ReceiptResponse, TransactionBuilder};
+use alloy_primitives::{Address, B256, TxHash, U256};
+use alloy_provider::{PendingTransactionError, Provider};
+use alloy_rpc_types_eth::{Filter, Log, TransactionReceipt, TransactionRequest};
+use alloy_sol_types::{SolCall, SolEvent};
+use alloy_transport::TransportError;
+use nectar_contracts::IPostageStamp;
+use nectar_postage::{Batch, BatchId, BatchParams, BucketDepth, StampError};
+use nectar_primitives::{Mainnet, SwarmSpec};
+use thiserror::Error;
+
+use crate::factory::{BatchFactory, CreateResultFor};
+
+/// Errors from [`ContractBatchFactoryFor`].
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum ContractFactoryError {
+    /// The transaction could not be sent.
+    #[error(transparent)]
+    Transport(#[from] TransportError),
+
+    /// The receipt could not be obtained.
+    #[error(transparent)]
+    Pending(#[from] PendingTransactionError),
+
+    /// The transaction was mined but reverted.
+    #[error("transaction {0} reverted")]
+    Reverted(TxHash),
+
+    /// The receipt carries no event of the expected kind from the contract.
+    #[error("transaction {tx_hash} emitted no {event} event")]
+    MissingEvent {
+        /// The transaction.
+        tx_hash: TxHash,
+        /// The event that was expected.
+        event: &'static str,
+    },
+
+    /// The receipt has no block number.
+    #[error("receipt for {0} has no block number")]
+    Unmined(TxHash),
+
+    /// The batch's normalised balance does not fit in a `u128`.
+    #[error("normalised balance {0} exceeds u128")]
+    BalanceOverflow(U256),
+
+    /// The contract reported a geometry this network does not accept.
+    #[error(transparent)]
+    Stamp(#[from] StampError),
+}
+
+/// A [`BatchFactory`] that sends `createBatch`, `topUp` and `increaseDepth`
+/// to the postage stamp contract through an alloy [`Provider`].
+///
+/// Each call waits for the receipt and reads the batch state back from the
+/// event the contract emitted, so the returned batch carries the on-chain id
+/// and normalised balance. Amounts are per chunk, as the contract takes them.
+///
+/// The provider must be able to sign for the sender (a wallet filler), and
+/// the sender must already have approved the contract to spend its BZZ; see
+/// [`nectar_contracts::token::approve_call`].
+///
+/// The network is a type parameter; [`ContractBatchFactory`] is the mainnet
+/// factory.
+#[derive(Debug)]
+pub struct ContractBatchFactoryFor<P, S: SwarmSpec = Mainnet> {
+    provider: P,
+    postage_stamp: Address,
+    spec: PhantomData<fn() -> S>,
+}
+
+/// The [`ContractBatchFactoryFor`] of the mainnet spec.
+pub type ContractBatchFactory<P> = ContractBatchFactoryFor<P, Mainnet>;
+
+impl<P, S: SwarmSpec> ContractBatchFactoryFor<P, S> {
+    /// Creates a factory sending to the postage stamp contract at
+    /// `postage_stamp`, for example
+    /// [`nectar_contracts::mainnet::POSTAGE_STAMP`]'s address.
+    pub const fn new(provider: P, postage_stamp: Address) -> Self {
+        Self {
+            provider,
+            postage_stamp,
+            spec: PhantomData,
+        }
+    }
+
+    /// Returns the provider.
+    pub const fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    /// Returns the postage stamp contract address.
+    pub const fn postage_stamp(&self) -> Address {
+        self.postage_stamp
+    }
+}
+
+impl<P: Provider, S: SwarmSpec> ContractBatchFactoryFor<P, S> {
+    /// Sends `call` to the contract and waits for a successful receipt.
+    async fn transact(
+        &self,
+        call: impl SolCall,
+    ) -> Result<TransactionReceipt, ContractFactoryError> {
+        let tx = TransactionRequest::default()
+            .with_to(self.postage_stamp)
+            .with_input(call.abi_encode());
+        let receipt = self
+            .provider
+            .send_transaction(tx)
+            .await?
+            .get_receipt()
+            .await?;
+        if !receipt.status() {
+            return Err(ContractFactoryError::Reverted(receipt.transaction_hash()));
+        }
+        Ok(receipt)
+    }
+
+    /// The first `E` the contract emitted in `receipt`.
+    fn event<E: SolEvent>(&self, receipt: &TransactionReceipt) -> Result<E, ContractFactoryError> {
+        let missing = ContractFactoryError::MissingEvent {
+            tx_hash: receipt.transaction_hash(),
+            event: E::SIGNATURE,
+        };
+        find_event(receipt.logs(), self.postage_stamp).ok_or(missing)
+    }
+}
+
+/// Fetches the batch event logs of the postage stamp contract at
+/// `postage_stamp` mined in blocks `from_block..=to_block`, in chain order.
+///
+/// Feed the result to [`replay_postage_logs`](crate::replay_postage_logs) to
+/// rebuild batch state. Long ranges may exceed a provider's log limit; fetch
+/// them in windows.
+///
+/// # Errors
+///
+/// Returns the provider's error if the logs cannot be read.
+pub async fn fetch_postage_logs<P: Provider>(
+    provider: &P,
+    postage_stamp: Address,
+    from_block: u64,
+    to_block: u64,
+) -> Result<Vec<Log>, TransportError> {
+    let filter = Filter::new()
+        .address(postage_stamp)
+        .from_block(from_block)
+        .to_block(to_block)
+        .event_signature(vec![
+            IPostageStamp::BatchCreated::SIGNATURE_HASH,
+            IPostageStamp::BatchTopUp::SIGNATURE_HASH,
+            IPostageStamp::BatchDepthIncrease::SIGNATURE_HASH,
+        ]);
+    provider.get_logs(&filter).await
+}
+
+/// Decodes the first log of `E` emitted by `emitter`.
+fn find_event<E: SolEvent>(logs: &[Log], emitter: Address) -> Option<E> {
+    logs.iter()
+        .filter(|log| log.address() == emitter)
+        .find_map(|log| E::decode_log(&log.inner).ok())
+        .map(|decoded| decoded.data)
+}
+
+/// Narrows a contract balance to the batch value type.
+fn balance(value: U256) -> Result<u128, ContractFactoryError> {
+    u128::try_from(value).map_err(|_| ContractFactoryError::BalanceOverflow(value))
+}
+
+impl<P: Provider, S: SwarmSpec> BatchFactory for ContractBatchFactoryFor<P, S> {
+    type Error = ContractFactoryError;
+    type Spec = S;
+
+    async fn create(&self, params: BatchParams<S>) -> Result<CreateResultFor<S>, Self::Error> {
+        let receipt = self
+            .transact(IPostageStamp::createBatchCall {
+                _owner: params.owner,
+                _initialBalancePerChunk: U256::from(params.amount),
+                _depth: params.depth,
+                _bucketDepth: params.bucket_depth.get(),
+                _nonce: B256::random(),
+                _immutable: params.immutable,
+            })
+            .await?;
+        let tx_hash = receipt.transaction_hash();
+        let start = receipt
+            .block_number()
+            .ok_or(ContractFactoryError::Unmined(tx_hash))?;
+        let event: IPostageStamp::BatchCreated = self.event(&receipt)?;
+
+        let batch = Batch::new(
+            BatchId::from(event.batchId),
+            balance(event.normalisedBalance)?,
+            start,
+            event.owner,
+            event.depth,
+            BucketDepth::new(event.bucketDepth)?,
+            event.immutableFlag,
+        );
+        Ok(CreateResultFor {
+            batch,
+            tx_hash: Some(tx_hash),
+        })
+    }
+
+    /// Returns the normalised balance the contract reports after the top-up.
+    async fn top_up(&self, batch_id: BatchId, amount: u128) -> Result<u128, Self::Error> {
+        let receipt = self
+            .transact(IPostageStamp::topUpCall {
+                _batchId: batch_id.into(),
+                _topupAmountPerChunk: U256::from(amount),
+            })
+            .await?;
+        let event: IPostageStamp::BatchTopUp = self.event(&receipt)?;
+        balance(event.normalisedBalance)
+    }
+
+    async fn dilute(&self, batch_id: BatchId, new_depth: u8) -> Result<(), Self::Error> {
+        let receipt = self
+            .transact(IPostageStamp::increaseDepthCall {
+                _batchId: batch_id.into(),
+                _newDepth: new_depth,
+            })
+            .await?;
+        self.event::<IPostageStamp::BatchDepthIncrease>(&receipt)?;
+        Ok(())
+    }
+}
+
+// Sanctioned tokio adapter tests: the test macro expands to `Runtime::block_on`.
+#[cfg(test)]
+#[allow(clippy::disallowed_methods)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{LogData, address};
+    use alloy_provider::ProviderBuilder;
+    use alloy_rpc_types_eth::{Receipt, ReceiptEnvelope};
+    use alloy_transport::mock::Asserter;
+
+    const POSTAGE: Address = address!("0x45a1502382541Cd610CC9068e88727426b696293");
+    const TX: TxHash = TxHash::repeat_byte(0xaa);
+
+    fn log(emitter: Address, data: LogData) -> Log {
+        Log {
+            inner: alloy_primitives::Log {
+                address: emitter,
+                data,
+            },
+            ..Log::default()
+        }
+    }
+
+    fn created(batch_id: B256) -> IPostageStamp::BatchCreated {
+        IPostageStamp::BatchCreated {
+            batchId: batch_id,
+            totalAmount: U256::from(1u64 << 40),
+            normalisedBalance: U256::from(1u64 << 20),
+            owner: Address::repeat_byte(7),
+            depth: 20,
+            bucketDepth: 16,
+            immutableFlag: true,
+        }
+    }
+
+    /// A mined receipt for [`TX`] sent to [`POSTAGE`], carrying `logs`.
+    fn receipt(status: bool, block_number: Option<u64>, logs: Vec<Log>) -> TransactionReceipt {
+        let inner = Receipt {
+            status: status.into(),
+            cumulative_gas_used: 100_000,
+            logs,
+        };
+        TransactionReceipt {
+            inner: ReceiptEnvelope::Legacy(inner.with_bloom()),
+            transaction_hash: TX,
+            transaction_index: Some(0),
+            block_hash: Some(B256::repeat_byte(0xbb)),
+            block_number,
+            gas_used: 100_000,
+            effective_gas_price: 1,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            from: Address::repeat_byte(7),
+            to: Some(POSTAGE),
+            contract_address: None,
+        }
+    }
+
+    /// A factory over a mocked provider that answers `eth_sendTransaction`
+    /// with [`TX`] and `eth_getTransactionReceipt` with `receipt`.
+    ///
+    /// Waiting for the receipt starts the provider's block heartbeat, which
+    /// may take the next queued response first; the receipt is queued twice
+    /// so the lookup finds it either way.
+    fn mocked(receipt: TransactionReceipt) -> ContractBatchFactory<impl Provider> {
+        let asserter = Asserter::new();
+        asserter.push_success(&TX);
+        asserter.push_success(&receipt);
+        asserter.push_success(&receipt);
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .connect_mocked_client(asserter);
+        ContractBatchFactory::new(provider, POSTAGE)
+    }
+
+    fn params() -> BatchParams {
+        BatchParams::new(
+            Address::repeat_byte(7),
+            20,
+            BucketDepth::new(16).unwrap(),
+            1 << 20,
+        )
+    }
+
+    #[tokio::test]
+    async fn create_reads_the_batch_from_the_receipt() {
+        let event = created(B256::repeat_byte(1));
+        let logs = vec![log(POSTAGE, event.encode_log_data())];
+        let factory = mocked(receipt(true, Some(38_000_000), logs));
+
+        let result = factory.create(params()).await.unwrap();
+        assert_eq!(result.tx_hash, Some(TX));
+        let batch = result.batch;
+        assert_eq!(batch.id(), BatchId::from(B256::repeat_byte(1)));
+        assert_eq!(batch.owner(), Address::repeat_byte(7));
+        assert_eq!(batch.depth(), 20);
+        assert_eq!(batch.bucket_depth().get(), 16);
+        assert!(batch.immutable());
+        assert_eq!(batch.value(), 1 << 20);
+        assert_eq!(batch.start(), 38_000_000);
+    }
+
+    #[tokio::test]
+    async fn create_reports_a_reverted_transaction() {
+        let factory = mocked(receipt(false, Some(38_000_000), Vec::new()));
+        let result = factory.create(params()).await;
+        assert!(
+            matches!(result, Err(ContractFactoryError::Reverted(TX))),
+            "{result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn create_needs_the_event_from_the_contract() {
+        let spoofed = created(B256::repeat_byte(2));
+        let logs = vec![log(Address::repeat_byte(9), spoofed.encode_log_data())];
+        let factory = mocked(receipt(true, Some(38_000_000), logs));
+        assert!(matches!(
+            factory.create(params()).await,
+            Err(ContractFactoryError::MissingEvent { tx_hash: TX, .. })
+        ));
+    }
+
+    #[test]
+    fn events_are_read_from_the_contract_only() {
+        let ours = created(B256::repeat_byte(1));
+        let spoofed = created(B256::repeat_byte(2));
+        let logs = [
+            log(Address::repeat_byte(9), spoofed.encode_log_data()),
+            log(POSTAGE, ours.encode_log_data()),
+        ];
+
+        let found: IPostageStamp::BatchCreated = find_event(&logs, POSTAGE).unwrap();
+        assert_eq!(found, ours);
+        assert!(find_event::<IPostageStamp::BatchTopUp>(&logs, POSTAGE).is_none());
+    }
+
+    #[test]
+    fn batches_getter_maps_onto_from_chain() {
+        let decoded = IPostageStamp::batchesReturn {
+            owner: Address::repeat_byte(7),
+            depth: 20,
+            bucketDepth: 16,
+            immutableFlag: true,
+            normalisedBalance: U256::from(1u64 << 20),
+            lastUpdatedBlockNumber: U256::from(38_000_000u64),
+        };
+        let encoded = IPostageStamp::batchesCall::abi_encode_returns(&decoded);
+        let decoded = IPostageStamp::batchesCall::abi_decode_returns(&encoded).unwrap();
+
+        let id = BatchId::from(B256::repeat_byte(1));
+        let batch: Batch = Batch::from_chain(
+            id,
+            decoded.owner,
+            decoded.depth,
+            BucketDepth::new(decoded.bucketDepth).unwrap(),
+            decoded.immutableFlag,
+            balance(decoded.normalisedBalance).unwrap(),
+            u64::try_from(decoded.lastUpdatedBlockNumber).unwrap(),
+        );
+
+        assert_eq!(batch.id(), id);
+        assert_eq!(batch.owner(), Address::repeat_byte(7));
+        assert_eq!(batch.depth(), 20);
+        assert_eq!(batch.bucket_depth().get(), 16);
+        assert!(batch.immutable());
+        assert_eq!(batch.value(), 1 << 20);
+        assert_eq!(batch.start(), 38_000_000);
+    }
+
+    #[test]
+    fn balances_beyond_u128_are_rejected() {
+        assert_eq!(balance(U256::from(u128::MAX)).unwrap(), u128::MAX);
+        assert!(matches!(
+            balance(U256::from(u128::MAX) + U256::from(1)),
+            Err(ContractFactoryError::BalanceOverflow(_))
+        ));
+    }
+}
@@ -287,57 +287,33 @@ impl<S: SwarmSpec> CounterTableFor<S> {
         Ok(self.count(bucket)? < self.bucket_capacity())
     }
 
-    /// Advances the counter of `bucket`, skipping any slot for which
-    /// `is_protected` returns `true`, and returns the assigned slot.
+    /// Returns the slot the next [`record`](Self::record) into `bucket` would
+    /// assign, without advancing anything.
     ///
-    /// Fill mode returns the watermark and bumps it, failing with
-    /// [`CounterError::BucketFull`] at capacity; the predicate is unused because a
-    /// monotone watermark never lands on a reserved slot. Ring mode starts at the
-    /// cursor (wrapping a cursor that equals the capacity), skips protected slots,
-    /// returns the slot, and stores the cursor just past it in `[0, capacity]`. A
-    /// ring whose every slot is protected fails with
-    /// [`CounterError::RingExhausted`]; the geometry forbids this at real depths.
-    pub fn record(
-        &mut self,
+    /// Fails exactly when `record` would, with the same error.
+    pub fn peek(
+        &self,
         bucket: u32,
         is_protected: impl Fn(u32) -> bool,
     ) -> Result<u32, CounterError> {
         // `u32` always fits `usize` on the >=32-bit targets this crate supports.
         #[allow(clippy::as_conversions)]
         let bucket_idx = bucket as usize;
-        if bucket_idx >= self.counts.len() {
+        let Some(&cursor) = self.counts.get(bucket_idx) else {
             return Err(CounterError::InvalidBucket { bucket });
-        }
+        };
         let capacity = self.bucket_capacity();
 
         if matches!(self.mode, CounterMode::Fill) {
-            // Indexing guarded by the bucket range check at the top of `record`.
-            #[allow(clippy::indexing_slicing)]
-            let count = &mut self.counts[bucket_idx];
-            if *count >= capacity {
+            if cursor >= capacity {
                 return Err(CounterError::BucketFull { bucket, capacity });
             }
-            let index = *count;
-            // `*count < capacity <= u32::MAX` (checked above) and the u64 issued
-            // total cannot overflow before the u32 counters do.
-            #[allow(clippy::arithmetic_side_effects)]
-            {
-                *count += 1;
-                self.issued += 1;
-            }
-            return Ok(index);
+            return Ok(cursor);
         }
 
-        // Indexing guarded by the bucket range check at the top of `record`.
-        #[allow(clippy::indexing_slicing)]
-        let old_cursor = self.counts[bucket_idx];
         // Start at the cursor; a cursor equal to capacity means "wrap on the next
         // write", resetting to 0 when the bucket bound is reached.
-        let mut candidate = if old_cursor >= capacity {
-            0
-        } else {
-            old_cursor
-        };
+        let mut candidate = if cursor >= capacity { 0 } else { cursor };
         // Skip protected slots, wrapping. Bounded by `capacity` steps: if every
         // slot is protected we fail rather than loop.
         let mut steps = 0u32;
@@ -352,18 +328,38 @@ impl<S: SwarmSpec> CounterTableFor<S> {
                 return Err(CounterError::RingExhausted { bucket });
             }
         }
-        let index = candidate;
+        Ok(candidate)
+    }
+
+    /// Advances the counter of `bucket`, skipping any slot for which
+    /// `is_protected` returns `true`, and returns the assigned slot.
+    ///
+    /// Fill mode returns the watermark and bumps it, failing with
+    /// [`CounterError::BucketFull`] at capacity; the predicate is unused because a
+    /// monotone watermark never lands on a reserved slot. Ring mode starts at the
+    /// cursor (wrapping a cursor that equals the capacity), skips protected slots,
+    /// returns the slot, and stores the cursor just past it in `[0, capacity]`. A
+    /// ring whose every slot is protected fails with
+    /// [`CounterError::RingExhausted`]; the geometry forbids this at real depths.
+    pub fn record(
+        &mut self,
+        bucket: u32,
+        is_protected: impl Fn(u32) -> bool,
+    ) -> Result<u32, CounterError> {
+        let index = self.peek(bucket, is_protected)?;
+        // `u32` always fits `usize` on the >=32-bit targets this crate supports.
+        #[allow(clippy::as_conversions)]
+        let bucket_idx = bucket as usize;
         // The new cursor points just past the slot we returned. Storing
         // `capacity` (rather than wrapping to 0 here) defers the wrap to the next
-        // write, keeping the cursor in [0, capacity] as on the wire.
+        // write, keeping the cursor in [0, capacity] as on the wire. In fill mode
+        // the slot is the watermark itself, so this is the plain bump.
         // `index < capacity <= u32::MAX`, so the increment cannot overflow.
         #[allow(clippy::arithmetic_side_effects)]
         let new_cursor = index + 1;
-        // Indexing guarded by the bucket range check at the top of `record`.
+        // `peek` succeeded, so `bucket_idx` is in range.
         #[allow(clippy::indexing_slicing)]
-        {
-            self.counts[bucket_idx] = new_cursor;
-        }
+        let old_cursor = core::mem::replace(&mut self.counts[bucket_idx], new_cursor);
         // Keep issued == sum(counts): fold in the signed delta (it decreases on
         // wrap, when new_cursor < old_cursor). `issued == sum(counts) >=
         // old_cursor` (it is one of the summands), so the subtraction cannot
@@ -375,6 +371,34 @@ impl<S: SwarmSpec> CounterTableFor<S> {
         Ok(index)
     }
 
+    /// Takes back slot `index` of `bucket`, the last one [`record`](Self::record)
+    /// assigned there, so the next `record` assigns it again. Returns whether
+    /// the slot was released.
+    ///
+    /// Only a fill watermark can rewind, and only by its latest slot: a ring
+    /// cursor cannot recover where it stood before a wrap or a skipped
+    /// protected slot, so ring tables release nothing.
+    pub fn release(&mut self, bucket: u32, index: u32) -> bool {
+        if !matches!(self.mode, CounterMode::Fill) {
+            return false;
+        }
+        // `u32` always fits `usize` on the >=32-bit targets this crate supports.
+        #[allow(clippy::as_conversions)]
+        let Some(cursor) = self.counts.get_mut(bucket as usize) else {
+            return false;
+        };
+        if index.checked_add(1) != Some(*cursor) {
+            return false;
+        }
+        *cursor = index;
+        // The released slot was counted in `issued`, so it is at least one.
+        #[allow(clippy::arithmetic_side_effects)]
+        {
+            self.issued -= 1;
+        }
+        true
+    }
+
     /// Increases the batch depth after an on-chain dilution, growing the
     /// per-bucket capacity without moving any counter.
     ///
@@ -437,6 +461,26 @@ mod tests {
         assert_eq!(table.total_issued(), 2);
     }
 
+    #[test]
+    fn fill_releases_only_its_latest_slot() {
+        let mut table = CounterTable::new(18, bucket_depth(), CounterMode::Fill);
+        assert_eq!(table.record(5, never).unwrap(), 0);
+        assert_eq!(table.record(5, never).unwrap(), 1);
+        // An older slot, another bucket or an unassigned slot stays put.
+        assert!(!table.release(5, 0));
+        assert!(!table.release(6, 1));
+        assert!(!table.release(5, 2));
+
+        assert!(table.release(5, 1));
+        assert_eq!(table.count(5).unwrap(), 1);
+        assert_eq!(table.total_issued(), 1);
+        assert_eq!(table.record(5, never).unwrap(), 1);
+
+        let mut ring = CounterTable::new(18, bucket_depth(), CounterMode::Ring);
+        assert_eq!(ring.record(5, never).unwrap(), 0);
+        assert!(!ring.release(5, 0));
+    }
+
     #[test]
     fn ring_wraps_and_keeps_the_cursor_in_range() {
         let mut table = CounterTable::new(17, bucket_depth(), CounterMode::Ring);
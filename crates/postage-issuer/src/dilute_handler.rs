@@ -156,6 +156,7 @@ impl BatchEventHandler for IssuerRegistry {
             BatchEvent::DepthIncrease {
                 batch_id,
                 new_depth,
+                ..
             } => self
                 .issuers
                 .get_mut(&batch_id)
@@ -197,6 +198,7 @@ mod tests {
             .handle_event(BatchEvent::DepthIncrease {
                 batch_id: tracked,
                 new_depth: 18,
+                new_value: 1000,
             })
             .unwrap();
 
@@ -222,6 +224,7 @@ mod tests {
             .handle_event(BatchEvent::DepthIncrease {
                 batch_id: tracked,
                 new_depth: 20,
+                new_value: 1000,
             })
             .unwrap();
 
@@ -248,6 +251,7 @@ mod tests {
             .handle_event(BatchEvent::DepthIncrease {
                 batch_id: other,
                 new_depth: 24,
+                new_value: 1000,
             })
             .unwrap();
 
@@ -301,6 +305,7 @@ mod tests {
         let result = registry.handle_event(BatchEvent::DepthIncrease {
             batch_id: tracked,
             new_depth: 17,
+            new_value: 1000,
         });
         assert!(matches!(
             result,
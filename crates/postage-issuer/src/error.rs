@@ -1,5 +1,6 @@
 //! Error types for postage issuing operations.
 
+use nectar_postage::BatchId;
 use thiserror::Error;
 
 /// Errors that can occur when constructing a stamp issuer.
@@ -54,3 +55,24 @@ pub enum SigningError {
     #[error(transparent)]
     Signer(#[from] alloy_signer::Error),
 }
+
+/// Errors from the in-memory batch factory's batch operations.
+///
+/// Top-ups and dilutions act on batches the factory minted, and fail when
+/// they name any other batch or ask for a depth the chain would refuse.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum FactoryError {
+    /// The batch was never created by this factory.
+    #[error("batch not found: {0}")]
+    BatchNotFound(BatchId),
+
+    /// A dilution must strictly increase the batch depth, as on chain.
+    #[error("batch depth must increase ({current} -> {requested})")]
+    DepthNotIncreased {
+        /// The current depth.
+        current: u8,
+        /// The requested depth.
+        requested: u8,
+    },
+}
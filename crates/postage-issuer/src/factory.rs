@@ -1,10 +1,14 @@
 //! Batch factory traits for creating batches.
 
 use core::marker::PhantomData;
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard, PoisonError};
 
-use nectar_postage::{Batch, BatchId, BatchParams};
+use nectar_postage::{Batch, BatchEvent, BatchEventHandler, BatchId, BatchParams};
 use nectar_primitives::{Mainnet, SwarmSpec};
 
+use crate::error::FactoryError;
+
 /// The result of creating a batch on the network `S`.
 #[derive(Debug)]
 pub struct CreateResultFor<S: SwarmSpec = Mainnet> {
@@ -103,6 +107,13 @@ pub trait BatchFactory {
 /// This implementation creates batches in memory without any blockchain
 /// interaction. Useful for unit tests and local development.
 ///
+/// Created batches are kept, so [`top_up`](BatchFactory::top_up) and
+/// [`dilute`](BatchFactory::dilute) change them as the contract would. Each
+/// change queues the matching [`BatchEvent`]; hand the queue to a
+/// [`BatchEventHandler`] with [`flush_events`](Self::flush_events), for
+/// example an [`IssuerRegistry`](crate::IssuerRegistry) that dilutes the live
+/// issuer in step with the batch.
+///
 /// The network the batches are minted for is a type parameter;
 /// [`MemoryBatchFactory`] is the mainnet factory.
 #[derive(Debug)]
@@ -111,6 +122,10 @@ pub struct MemoryBatchFactoryFor<S: SwarmSpec = Mainnet> {
     next_id: std::sync::atomic::AtomicU64,
     /// The current block number (for start block).
     current_block: u64,
+    /// Batches created so far, by ID.
+    batches: Mutex<BTreeMap<BatchId, Batch<S>>>,
+    /// Events from top-ups and dilutions not yet flushed to a handler.
+    events: Mutex<Vec<BatchEvent>>,
     /// The network the minted batches belong to.
     spec: PhantomData<fn() -> S>,
 }
@@ -124,6 +139,8 @@ impl<S: SwarmSpec> MemoryBatchFactoryFor<S> {
         Self {
             next_id: std::sync::atomic::AtomicU64::new(0),
             current_block,
+            batches: Mutex::new(BTreeMap::new()),
+            events: Mutex::new(Vec::new()),
             spec: PhantomData,
         }
     }
@@ -133,6 +150,22 @@ impl<S: SwarmSpec> MemoryBatchFactoryFor<S> {
         self.current_block = block;
     }
 
+    /// Returns the current state of a batch this factory created.
+    pub fn batch(&self, batch_id: &BatchId) -> Option<Batch<S>> {
+        lock(&self.batches).get(batch_id).cloned()
+    }
+
+    /// Passes every queued event to `handler`, oldest first, and clears the
+    /// queue.
+    ///
+    /// # Errors
+    ///
+    /// Returns the handler's error; the events are consumed either way.
+    pub fn flush_events<H: BatchEventHandler>(&self, handler: &mut H) -> Result<(), H::Error> {
+        let events = std::mem::take(&mut *lock(&self.events));
+        handler.handle_events(events)
+    }
+
     fn generate_batch_id(&self) -> BatchId {
         let id = self
             .next_id
@@ -141,6 +174,29 @@ impl<S: SwarmSpec> MemoryBatchFactoryFor<S> {
         bytes[24..32].copy_from_slice(&id.to_be_bytes());
         BatchId::new(bytes)
     }
+
+    /// Applies `update` to a stored batch and queues the event it yields.
+    fn update<T>(
+        &self,
+        batch_id: BatchId,
+        update: impl FnOnce(&mut Batch<S>) -> Result<(T, BatchEvent), FactoryError>,
+    ) -> Result<T, FactoryError> {
+        let (out, event) = {
+            let mut batches = lock(&self.batches);
+            let batch = batches
+                .get_mut(&batch_id)
+                .ok_or(FactoryError::BatchNotFound(batch_id))?;
+            update(batch)?
+        };
+        lock(&self.events).push(event);
+        Ok(out)
+    }
+}
+
+/// Locks a factory mutex; a panic elsewhere leaves plain data that is still
+/// consistent, so poisoning is ignored.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
 }
 
 impl<S: SwarmSpec> Default for MemoryBatchFactoryFor<S> {
@@ -150,7 +206,7 @@ impl<S: SwarmSpec> Default for MemoryBatchFactoryFor<S> {
 }
 
 impl<S: SwarmSpec> BatchFactory for MemoryBatchFactoryFor<S> {
-    type Error = std::convert::Infallible;
+    type Error = FactoryError;
     type Spec = S;
 
     async fn create(&self, params: BatchParams<S>) -> Result<CreateResultFor<S>, Self::Error> {
@@ -165,6 +221,7 @@ impl<S: SwarmSpec> BatchFactory for MemoryBatchFactoryFor<S> {
             params.bucket_depth,
             params.immutable,
         );
+        lock(&self.batches).insert(batch_id, batch.clone());
 
         Ok(CreateResultFor {
             batch,
@@ -172,14 +229,54 @@ impl<S: SwarmSpec> BatchFactory for MemoryBatchFactoryFor<S> {
         })
     }
 
-    async fn top_up(&self, _batch_id: BatchId, _amount: u128) -> Result<u128, Self::Error> {
-        // Memory factory doesn't track batches after creation
-        Ok(0)
+    /// Adds `amount` to the batch's per-chunk balance, saturating, and queues
+    /// a [`BatchEvent::TopUp`].
+    async fn top_up(&self, batch_id: BatchId, amount: u128) -> Result<u128, Self::Error> {
+        self.update(batch_id, |batch| {
+            let new_value = batch.value().saturating_add(amount);
+            batch.set_value(new_value);
+            Ok((
+                new_value,
+                BatchEvent::TopUp {
+                    batch_id,
+                    new_value,
+                },
+            ))
+        })
     }
 
-    async fn dilute(&self, _batch_id: BatchId, _new_depth: u8) -> Result<(), Self::Error> {
-        // Memory factory doesn't track batches after creation
-        Ok(())
+    /// Raises the batch depth and queues a [`BatchEvent::DepthIncrease`].
+    ///
+    /// The per-chunk balance is split across the new chunks, halving once per
+    /// added depth level.
+    ///
+    /// The postage stamp contract does not keep the normalised balance: it
+    /// halves only the remaining part, the balance above the cumulative
+    /// payout, and adds the payout back. The memory chain accrues no payout,
+    /// so its whole balance is remaining and the two agree: the value is
+    /// shifted right by the added levels.
+    async fn dilute(&self, batch_id: BatchId, new_depth: u8) -> Result<(), Self::Error> {
+        self.update(batch_id, |batch| {
+            let current = batch.depth();
+            if new_depth <= current {
+                return Err(FactoryError::DepthNotIncreased {
+                    current,
+                    requested: new_depth,
+                });
+            }
+            let levels = u32::from(new_depth.saturating_sub(current));
+            let new_value = batch.value().checked_shr(levels).unwrap_or(0);
+            batch.set_value(new_value);
+            batch.set_depth(new_depth);
+            Ok((
+                (),
+                BatchEvent::DepthIncrease {
+                    batch_id,
+                    new_depth,
+                    new_value,
+                },
+            ))
+        })
     }
 }
 
@@ -230,4 +327,114 @@ mod tests {
 
         assert!(result.batch.immutable());
     }
+
+    #[tokio::test]
+    async fn test_memory_factory_top_up() {
+        let factory = MemoryBatchFactory::new(0);
+        let params = BatchParams::new(Address::ZERO, 20, BucketDepth::new(16).unwrap(), 1000);
+        let id = factory.create(params).await.unwrap().batch.id();
+
+        assert_eq!(factory.top_up(id, 500).await.unwrap(), 1500);
+        assert_eq!(factory.batch(&id).unwrap().value(), 1500);
+
+        let mut seen = Vec::new();
+        factory.flush_events(&mut Recorder(&mut seen)).unwrap();
+        assert_eq!(
+            seen,
+            [BatchEvent::TopUp {
+                batch_id: id,
+                new_value: 1500
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_memory_factory_dilute() {
+        let factory = MemoryBatchFactory::new(0);
+        let params = BatchParams::new(Address::ZERO, 20, BucketDepth::new(16).unwrap(), 1000);
+        let id = factory.create(params).await.unwrap().batch.id();
+
+        factory.dilute(id, 22).await.unwrap();
+        let batch = factory.batch(&id).unwrap();
+        assert_eq!(batch.depth(), 22);
+        // Two added levels split the per-chunk balance four ways.
+        assert_eq!(batch.value(), 250);
+
+        assert_eq!(
+            factory.dilute(id, 22).await,
+            Err(FactoryError::DepthNotIncreased {
+                current: 22,
+                requested: 22
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_memory_factory_unknown_batch() {
+        let factory = MemoryBatchFactory::new(0);
+        let id = BatchId::new([0xAA; 32]);
+
+        assert_eq!(
+            factory.top_up(id, 1).await,
+            Err(FactoryError::BatchNotFound(id))
+        );
+        assert_eq!(
+            factory.dilute(id, 24).await,
+            Err(FactoryError::BatchNotFound(id))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_diluted_batch_accepts_more_stamps() {
+        use crate::{MemoryIssuer, StampIssuer};
+        use nectar_primitives::ChunkAddress;
+
+        // depth=17 over bucket_depth=16 leaves 2 slots per bucket.
+        let factory = MemoryBatchFactory::new(0);
+        let params = BatchParams::new(Address::ZERO, 17, BucketDepth::new(16).unwrap(), 1000)
+            .immutable(true);
+        let batch = factory.create(params).await.unwrap().batch;
+        let mut live = Live(MemoryIssuer::from_batch(&batch).unwrap());
+
+        let address = ChunkAddress::new([0u8; 32]);
+        for _ in 0..2 {
+            live.0.prepare_stamp(&address, 0).unwrap();
+        }
+        assert!(live.0.prepare_stamp(&address, 0).is_err());
+
+        factory.dilute(batch.id(), 18).await.unwrap();
+        factory.flush_events(&mut live).unwrap();
+
+        // Depth 18 doubles the bucket: two more stamps fit, then it is full.
+        for _ in 0..2 {
+            live.0.prepare_stamp(&address, 0).unwrap();
+        }
+        assert!(live.0.prepare_stamp(&address, 0).is_err());
+    }
+
+    /// Collects every event it is handed.
+    struct Recorder<'a>(&'a mut Vec<BatchEvent>);
+
+    impl BatchEventHandler for Recorder<'_> {
+        type Error = std::convert::Infallible;
+
+        fn handle_event(&mut self, event: BatchEvent) -> Result<(), Self::Error> {
+            self.0.push(event);
+            Ok(())
+        }
+    }
+
+    /// Keeps a live issuer in step with the batch it stamps for.
+    struct Live(crate::MemoryIssuer);
+
+    impl BatchEventHandler for Live {
+        type Error = crate::IssuerError;
+
+        fn handle_event(&mut self, event: BatchEvent) -> Result<(), Self::Error> {
+            match event {
+                BatchEvent::DepthIncrease { new_depth, .. } => self.0.dilute(new_depth),
+                _ => Ok(()),
+            }
+        }
+    }
 }
@@ -1,5 +1,7 @@
 //! Stamp issuer trait for tracking bucket utilization.
 
+use alloy_primitives::Address;
+
 use crate::counter::{CounterMode, CounterTableFor};
 use crate::error::IssuerError;
 use nectar_postage::{
@@ -58,7 +60,9 @@ pub trait StampIssuer {
     ///
     /// # Errors
     ///
-    /// Returns `StampError::BucketFull` if the bucket has no remaining capacity.
+    /// Returns `StampError::BucketFull` if the bucket has no remaining capacity,
+    /// or `StampError::ImmutableBatchFull` if the issuer knows its batch is
+    /// immutable.
     fn prepare_stamp(
         &mut self,
         address: &ChunkAddress,
@@ -106,6 +110,28 @@ pub trait StampIssuer {
     /// honest in both modes.
     fn stamps_issued(&self) -> Option<u64>;
 
+    /// Returns the stamp index the next [`prepare_stamp`](Self::prepare_stamp)
+    /// for `address` would allocate, without allocating it.
+    ///
+    /// `None` means the next `prepare_stamp` for `address` would fail. The
+    /// default assumes fill issuance: the bucket's watermark, or `None` once
+    /// the bucket is full. Issuers that wrap or skip slots override it.
+    fn peek_index(&self, address: &ChunkAddress) -> Option<StampIndex> {
+        let bucket = calculate_bucket(address, self.bucket_depth());
+        self.bucket_has_capacity(bucket)
+            .then(|| StampIndex::new(bucket, self.bucket_utilization(bucket)))
+    }
+
+    /// Returns the index of a digest from [`prepare_stamp`](Self::prepare_stamp)
+    /// that will never be signed, so its slot is not lost. Returns whether the
+    /// index was released.
+    ///
+    /// Only the latest allocation in the digest's bucket can come back, and
+    /// only from an issuer able to rewind. The default releases nothing.
+    fn release_stamp(&mut self, _digest: &StampDigest) -> bool {
+        false
+    }
+
     /// Returns the total capacity of the batch (2^depth).
     fn total_capacity(&self) -> u64 {
         1u64 << self.batch_depth()
@@ -132,6 +158,21 @@ pub trait StampIssuer {
         let capacity = f64::from(self.bucket_capacity());
         max_util / capacity >= threshold
     }
+
+    /// Estimates the probability that stamping `expected_chunks` chunks fills
+    /// some bucket before the batch is logically full.
+    ///
+    /// The estimate is from an empty batch of this issuer's geometry and
+    /// ignores stamps already issued; see
+    /// [`overflow_probability`](nectar_postage::overflow_probability) for the
+    /// balls-into-bins model behind it.
+    fn overflow_probability(&self, expected_chunks: u64) -> f64 {
+        nectar_postage::overflow_probability(
+            expected_chunks,
+            self.batch_depth(),
+            self.bucket_depth(),
+        )
+    }
 }
 
 /// An in-memory stamp issuer that tracks bucket utilization.
@@ -141,7 +182,10 @@ pub trait StampIssuer {
 /// across restarts.
 ///
 /// Issuance is fill-only: every slot is written at most once and the bucket is
-/// refused with [`StampError::BucketFull`] once full. Mutable, overwrite-aware
+/// refused with [`StampError::BucketFull`] once full. An issuer built by
+/// [`from_batch`](Self::from_batch) holds an immutable batch and refuses with
+/// [`StampError::ImmutableBatchFull`] instead; it never reuses an index, and
+/// only [`dilute`](Self::dilute) reopens a full bucket. Mutable, overwrite-aware
 /// issuance is intentionally absent from this crate; it requires reserved-slot
 /// awareness that lives in `nectar-postage-usage`. See the crate-root
 /// documentation for the steer toward `Snapshot::issuer` / `SnapshotIssuer`.
@@ -155,6 +199,8 @@ pub struct MemoryIssuerFor<S: SwarmSpec = Mainnet> {
     /// The shared per-bucket fill watermarks. `counts[b]` is the next unused
     /// slot, monotone and never above the capacity.
     counters: CounterTableFor<S>,
+    /// Whether the batch is known to be immutable.
+    immutable: bool,
 }
 
 /// The [`MemoryIssuerFor`] of the mainnet spec.
@@ -168,6 +214,7 @@ impl<S: SwarmSpec> Clone for MemoryIssuerFor<S> {
         Self {
             batch_id: self.batch_id,
             counters: self.counters.clone(),
+            immutable: self.immutable,
         }
     }
 }
@@ -178,9 +225,15 @@ impl<S: SwarmSpec> MemoryIssuerFor<S> {
         Self {
             batch_id,
             counters: CounterTableFor::new(depth, bucket_depth, CounterMode::Fill),
+            immutable: false,
         }
     }
 
+    /// Whether the issuer was built from an immutable batch.
+    pub const fn is_immutable(&self) -> bool {
+        self.immutable
+    }
+
     /// Applies an on-chain dilution, growing the per-bucket capacity without
     /// moving any watermark.
     ///
@@ -205,8 +258,9 @@ impl<S: SwarmSpec> MemoryIssuerFor<S> {
 
     /// Creates a memory issuer from a batch.
     ///
-    /// Immutable batches yield a fill-only issuer identical to
-    /// [`MemoryIssuer::new`] for the same geometry. Mutable batches are refused
+    /// Immutable batches yield a fill-only issuer that allocates identically to
+    /// [`MemoryIssuer::new`] for the same geometry, but reports a full bucket
+    /// as [`StampError::ImmutableBatchFull`]. Mutable batches are refused
     /// with [`IssuerError::MutableNotSupported`] so a ring is never produced by
     /// accident: overwrite-aware issuance must be requested by name through
     /// [`RingIssuer::external`](crate::RingIssuer::external) for external
@@ -214,11 +268,41 @@ impl<S: SwarmSpec> MemoryIssuerFor<S> {
     /// self-hosting, where the protected slots come from `nectar-postage-usage`.
     pub fn from_batch(batch: &Batch<S>) -> Result<Self, IssuerError> {
         if batch.immutable() {
-            Ok(Self::new(batch.id(), batch.depth(), batch.bucket_depth()))
+            Ok(Self {
+                immutable: true,
+                ..Self::new(batch.id(), batch.depth(), batch.bucket_depth())
+            })
         } else {
             Err(IssuerError::MutableNotSupported)
         }
     }
+
+    /// Rebuilds the batch this issuer stamps for, the inverse of
+    /// [`from_batch`](Self::from_batch).
+    ///
+    /// The id and geometry come from the issuer (its current depth, after
+    /// any [`dilute`](Self::dilute)); the on-chain fields it does not track
+    /// are supplied. The issuer does not know the creation block either, so
+    /// the batch starts at block 0 and counts as long confirmed.
+    ///
+    /// An issuer that enforces immutability always yields an immutable batch;
+    /// `immutable` can only mark the batch of a mutable issuer immutable.
+    pub const fn to_batch(
+        &self,
+        owner: Address,
+        normalised_balance: u128,
+        immutable: bool,
+    ) -> Batch<S> {
+        Batch::new(
+            self.batch_id,
+            normalised_balance,
+            0,
+            owner,
+            self.counters.depth(),
+            self.counters.bucket_depth(),
+            self.immutable || immutable,
+        )
+    }
 }
 
 impl<S: SwarmSpec> StampIssuer for MemoryIssuerFor<S> {
@@ -230,22 +314,32 @@ impl<S: SwarmSpec> StampIssuer for MemoryIssuerFor<S> {
         let bucket = calculate_bucket(address, self.counters.bucket_depth().get());
         // Fill mode ignores the predicate; a monotone watermark never lands on a
         // reserved slot.
-        let position =
-            self.counters
-                .record(bucket, |_| false)
-                .map_err(|err| StampError::BucketFull {
-                    bucket,
-                    capacity: match err {
-                        crate::counter::CounterError::BucketFull { capacity, .. } => capacity,
-                        _ => self.counters.bucket_capacity(),
-                    },
-                })?;
+        let position = self.counters.record(bucket, |_| false).map_err(|err| {
+            let capacity = match err {
+                crate::counter::CounterError::BucketFull { capacity, .. } => capacity,
+                _ => self.counters.bucket_capacity(),
+            };
+            bucket_full(self.immutable, bucket, capacity)
+        })?;
 
         let index = StampIndex::new(bucket, position);
 
         Ok(StampDigest::new(*address, self.batch_id, index, timestamp))
     }
 
+    fn peek_index(&self, address: &ChunkAddress) -> Option<StampIndex> {
+        let bucket = calculate_bucket(address, self.counters.bucket_depth().get());
+        let position = self.counters.peek(bucket, |_| false).ok()?;
+        Some(StampIndex::new(bucket, position))
+    }
+
+    fn release_stamp(&mut self, digest: &StampDigest) -> bool {
+        digest.batch_id == self.batch_id
+            && self
+                .counters
+                .release(digest.index.bucket(), digest.index.index())
+    }
+
     fn batch_id(&self) -> BatchId {
         self.batch_id
     }
@@ -278,6 +372,16 @@ impl<S: SwarmSpec> StampIssuer for MemoryIssuerFor<S> {
     }
 }
 
+/// The error for a full fill-only bucket: [`StampError::ImmutableBatchFull`]
+/// when the batch is known to be immutable, else [`StampError::BucketFull`].
+pub(crate) const fn bucket_full(immutable: bool, bucket: u32, capacity: u32) -> StampError {
+    if immutable {
+        StampError::ImmutableBatchFull { bucket, capacity }
+    } else {
+        StampError::BucketFull { bucket, capacity }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -361,6 +465,22 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_memory_issuer_peek_index() {
+        // depth=17, bucket_depth=16 gives 2 slots per bucket
+        let mut issuer = MemoryIssuer::new(BatchId::ZERO, 17, BucketDepth::new(16).unwrap());
+        let address = test_address(0xABCD);
+
+        for _ in 0..2 {
+            let peeked = issuer.peek_index(&address).unwrap();
+            // Peeking is idempotent and allocates nothing.
+            assert_eq!(issuer.peek_index(&address), Some(peeked));
+            assert_eq!(issuer.prepare_stamp(&address, 0).unwrap().index, peeked);
+        }
+        assert_eq!(issuer.peek_index(&address), None);
+        assert_eq!(issuer.stamps_issued(), Some(2));
+    }
+
     #[test]
     fn test_memory_issuer_bucket_utilization() {
         let mut issuer = MemoryIssuer::new(BatchId::ZERO, 20, BucketDepth::new(16).unwrap());
@@ -415,6 +535,32 @@ mod tests {
         assert!(issuer.is_near_capacity(0.75));
     }
 
+    #[test]
+    fn test_overflow_probability() {
+        // 2^16 buckets of 4 slots.
+        let issuer = MemoryIssuer::new(BatchId::ZERO, 18, BucketDepth::new(16).unwrap());
+        assert_eq!(issuer.overflow_probability(0), 0.0);
+        assert_eq!(
+            issuer.overflow_probability(issuer.total_capacity() + 1),
+            1.0
+        );
+        assert_eq!(
+            issuer.overflow_probability(100_000),
+            nectar_postage::overflow_probability(100_000, 18, 16)
+        );
+
+        // The estimate rises with the load and stays a probability.
+        let mut previous = 0.0;
+        for chunks in [1_000, 10_000, 50_000, 100_000, 200_000, 1 << 18] {
+            let p = issuer.overflow_probability(chunks);
+            assert!((0.0..=1.0).contains(&p), "{chunks}: {p}");
+            assert!(p >= previous, "{chunks}: {p} < {previous}");
+            previous = p;
+        }
+        // A full batch almost surely overflowed a bucket on the way.
+        assert!(previous > 0.999);
+    }
+
     #[test]
     fn test_memory_issuer_from_batch_mutable_refused() {
         use nectar_postage::Batch;
@@ -476,6 +622,90 @@ mod tests {
         assert_eq!(from_batch.stamps_issued(), from_new.stamps_issued());
     }
 
+    #[test]
+    fn test_memory_issuer_to_batch_round_trips_from_batch() {
+        use nectar_postage::Batch;
+
+        let owner = alloy_primitives::Address::repeat_byte(7);
+        let batch = Batch::new(
+            BatchId::new([0x22u8; 32]),
+            1 << 20,
+            0,
+            owner,
+            20,
+            BucketDepth::new(16).unwrap(),
+            true,
+        );
+
+        let mut issuer = MemoryIssuer::from_batch(&batch).unwrap();
+        assert_eq!(issuer.to_batch(owner, 1 << 20, true), batch);
+
+        // The depth follows the issuer through a dilution.
+        issuer.dilute(21).unwrap();
+        assert_eq!(issuer.to_batch(owner, 1 << 20, true).depth(), 21);
+
+        // An issuer enforcing immutability never yields a mutable batch.
+        assert!(issuer.to_batch(owner, 1 << 20, false).immutable());
+    }
+
+    #[test]
+    fn test_immutable_batch_full_never_reuses_indices() {
+        use nectar_postage::Batch;
+
+        // depth=17, bucket_depth=16 gives 2 slots per bucket.
+        let batch = |immutable| {
+            Batch::new(
+                BatchId::new([0x22u8; 32]),
+                0,
+                0,
+                Default::default(),
+                17,
+                BucketDepth::new(16).unwrap(),
+                immutable,
+            )
+        };
+        let address = test_address(0xABCD);
+
+        // An immutable batch refuses the overflow, and keeps refusing it.
+        let mut immutable = MemoryIssuer::from_batch(&batch(true)).unwrap();
+        assert!(immutable.is_immutable());
+        assert_eq!(
+            immutable.prepare_stamp(&address, 1).unwrap().index.index(),
+            0
+        );
+        assert_eq!(
+            immutable.prepare_stamp(&address, 2).unwrap().index.index(),
+            1
+        );
+        for ts in 3..5 {
+            assert_eq!(
+                immutable.prepare_stamp(&address, ts),
+                Err(StampError::ImmutableBatchFull {
+                    bucket: 0xABCD,
+                    capacity: 2
+                })
+            );
+        }
+        assert_eq!(immutable.stamps_issued(), Some(2));
+
+        // A geometry-only issuer does not know the batch's mutability.
+        let mut plain = MemoryIssuer::new(BatchId::ZERO, 17, BucketDepth::new(16).unwrap());
+        assert!(!plain.is_immutable());
+        plain.prepare_stamp(&address, 1).unwrap();
+        plain.prepare_stamp(&address, 2).unwrap();
+        assert!(matches!(
+            plain.prepare_stamp(&address, 3),
+            Err(StampError::BucketFull { .. })
+        ));
+
+        // A mutable batch wraps and reuses the oldest index instead.
+        let mut mutable = crate::RingIssuer::external(&batch(false)).unwrap();
+        for (ts, expected) in [(1, 0), (2, 1), (3, 0)] {
+            let digest = mutable.prepare_stamp(&address, ts).unwrap();
+            assert_eq!(digest.index.index(), expected);
+        }
+    }
+
     #[test]
     fn test_memory_issuer_dilute_grows_capacity_only() {
         // depth=17, bucket_depth=16 gives 2 slots per bucket.
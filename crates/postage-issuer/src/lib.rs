@@ -68,8 +68,13 @@
 //! # Features
 //!
 //! - `std` (default) - Enables standard library support
+//! - `replay` - Enables [`replay_postage_logs`], rebuilding batch state from
+//!   the postage stamp contract's logs
 //! - `local-signer` - Enables local key signing with `alloy-signer-local`
 //! - `parallel` - Enables parallel signing with rayon
+//! - `contract` - Enables [`ContractBatchFactory`], creating batches on chain
+//!   through an alloy provider, and [`fetch_postage_logs`], reading the
+//!   contract's logs for replay
 //!
 //! # Example
 //!
@@ -106,12 +111,17 @@
     )
 )]
 
+#[cfg(feature = "contract")]
+mod contract;
 mod counter;
 #[cfg(feature = "std")]
 mod dilute_handler;
 mod error;
 mod factory;
 mod issuer;
+mod metrics;
+#[cfg(feature = "replay")]
+mod replay;
 mod ring;
 mod sharded;
 mod sharded_ring;
@@ -124,7 +134,7 @@ pub use nectar_postage::*;
 pub use nectar_primitives::{Mainnet, NetworkId, SwarmSpec, Testnet};
 
 // Errors (override nectar_postage::StampError with our own that includes signing)
-pub use error::{IssuerError, SigningError};
+pub use error::{FactoryError, IssuerError, SigningError};
 
 // The shared per-bucket counter table behind every issuer and the snapshot.
 pub use counter::{CounterError, CounterMode, CounterTable, CounterTableFor};
@@ -135,6 +145,7 @@ pub use dilute_handler::{Dilutable, IssuerRegistry};
 
 // Issuing
 pub use issuer::{MemoryIssuer, MemoryIssuerFor, StampIssuer};
+pub use metrics::IssuerMetrics;
 pub use sharded::{ShardedIssuer, ShardedIssuerFor};
 pub use stamper::{BatchStamper, Stamper};
 
@@ -148,6 +159,19 @@ pub use factory::{
     BatchFactory, CreateResult, CreateResultFor, MemoryBatchFactory, MemoryBatchFactoryFor,
 };
 
+// On-chain factory (requires contract feature)
+#[cfg(feature = "contract")]
+pub use contract::{
+    ContractBatchFactory, ContractBatchFactoryFor, ContractFactoryError, fetch_postage_logs,
+};
+
+// Rebuilding batch state from postage stamp contract logs (requires replay feature)
+#[cfg(feature = "replay")]
+pub use replay::{ReplayError, replay_postage_logs};
+
 // Parallel signing (requires parallel feature)
 #[cfg(feature = "parallel")]
-pub use sharded::{StampResult, sign_stamps_parallel, sign_stamps_parallel_with_clock};
+pub use sharded::{
+    StampResult, sign_stamps_parallel, sign_stamps_parallel_for_each,
+    sign_stamps_parallel_with_clock,
+};
@@ -0,0 +1,130 @@
+//! Utilization totals across the issuers of a stamping service.
+
+use crate::issuer::StampIssuer;
+
+/// Utilization totals over a set of issuers, as a metrics endpoint reports
+/// them for a service stamping from many batches.
+///
+/// Issuers meet behind `dyn StampIssuer`, so fill issuers of any network and
+/// either concurrency model aggregate together. An issuer without a lifetime
+/// count ([`StampIssuer::stamps_issued`] is `None`, as for a self-hosted
+/// mutable snapshot) is counted in [`untracked`](Self::untracked) and its
+/// capacity left out of the fill.
+///
+/// ```
+/// use nectar_postage_issuer::{
+///     BatchId, BucketDepth, IssuerMetrics, MemoryIssuer, ShardedIssuer, StampIssuer,
+/// };
+/// use nectar_primitives::ChunkAddress;
+///
+/// let bucket_depth = BucketDepth::new(16).unwrap();
+/// let mut memory = MemoryIssuer::new(BatchId::ZERO, 17, bucket_depth);
+/// let sharded = ShardedIssuer::new(BatchId::new([1; 32]), 17, bucket_depth);
+/// memory.prepare_stamp(&ChunkAddress::new([0; 32]), 0).unwrap();
+///
+/// let metrics = IssuerMetrics::aggregate(&[&memory, &sharded]);
+/// assert_eq!(metrics.stamps_issued, 1);
+/// assert_eq!(metrics.total_capacity, 2 << 17);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct IssuerMetrics {
+    /// Issuers aggregated.
+    pub issuers: usize,
+    /// Issuers without a lifetime stamp count.
+    pub untracked: usize,
+    /// Stamps issued by the issuers that count them.
+    pub stamps_issued: u64,
+    /// Chunk capacity of every issuer.
+    pub total_capacity: u64,
+    /// Chunk capacity of the issuers that count their stamps.
+    pub tracked_capacity: u64,
+}
+
+impl IssuerMetrics {
+    /// Sums the utilization of `issuers`.
+    pub fn aggregate(issuers: &[&dyn StampIssuer]) -> Self {
+        issuers.iter().fold(Self::default(), |mut metrics, issuer| {
+            let capacity = issuer.total_capacity();
+            metrics.issuers = metrics.issuers.saturating_add(1);
+            metrics.total_capacity = metrics.total_capacity.saturating_add(capacity);
+            match issuer.stamps_issued() {
+                Some(issued) => {
+                    metrics.stamps_issued = metrics.stamps_issued.saturating_add(issued);
+                    metrics.tracked_capacity = metrics.tracked_capacity.saturating_add(capacity);
+                }
+                None => metrics.untracked = metrics.untracked.saturating_add(1),
+            }
+            metrics
+        })
+    }
+
+    /// Stamps issued as a percentage of the tracked capacity.
+    ///
+    /// Zero when no issuer counts its stamps. A ring counts its overwrites as
+    /// stamps, so a busy mutable batch can push the figure past 100.
+    pub fn fill_percentage(&self) -> f64 {
+        if self.tracked_capacity == 0 {
+            return 0.0;
+        }
+        // A ratio for display: the float rounding of two u64 counts is the point.
+        #[allow(clippy::as_conversions)]
+        let fill = self.stamps_issued as f64 / self.tracked_capacity as f64;
+        fill * 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MemoryIssuer, RingIssuer, ShardedIssuer};
+    use nectar_postage::{Batch, BatchId, BucketDepth};
+    use nectar_primitives::ChunkAddress;
+
+    fn address(bucket: u16) -> ChunkAddress {
+        let mut bytes = [0u8; 32];
+        bytes[..2].copy_from_slice(&bucket.to_be_bytes());
+        ChunkAddress::new(bytes)
+    }
+
+    #[test]
+    fn aggregates_fill_across_issuers() {
+        let bucket_depth = BucketDepth::new(16).unwrap();
+        // Depth 17: 2 slots in each of 2^16 buckets.
+        let mut memory = MemoryIssuer::new(BatchId::ZERO, 17, bucket_depth);
+        let sharded = ShardedIssuer::new(BatchId::new([1; 32]), 17, bucket_depth);
+        for bucket in 0..1024 {
+            memory.prepare_stamp(&address(bucket), 0).unwrap();
+            sharded.prepare_stamp(&address(bucket), 0).unwrap();
+            sharded.prepare_stamp(&address(bucket), 0).unwrap();
+        }
+
+        let metrics = IssuerMetrics::aggregate(&[&memory, &sharded]);
+        assert_eq!(metrics.issuers, 2);
+        assert_eq!(metrics.untracked, 0);
+        assert_eq!(metrics.stamps_issued, 3 * 1024);
+        assert_eq!(metrics.total_capacity, 2 << 17);
+        assert_eq!(metrics.tracked_capacity, 2 << 17);
+        // 3072 of 262144 slots.
+        assert!((metrics.fill_percentage() - 1.171875).abs() < 1e-12);
+
+        // An idle ring adds capacity and dilutes the fill.
+        let mutable = Batch::new(
+            BatchId::new([2; 32]),
+            0,
+            0,
+            Default::default(),
+            17,
+            bucket_depth,
+            false,
+        );
+        let ring = RingIssuer::external(&mutable).unwrap();
+        let with_ring = IssuerMetrics::aggregate(&[&memory, &sharded, &ring]);
+        assert_eq!(with_ring.untracked, 0);
+        assert_eq!(with_ring.total_capacity, 3 << 17);
+        assert_eq!(with_ring.tracked_capacity, 3 << 17);
+        assert!(with_ring.fill_percentage() < metrics.fill_percentage());
+
+        assert_eq!(IssuerMetrics::aggregate(&[]).fill_percentage(), 0.0);
+    }
+}
@@ -0,0 +1,333 @@
+//! Replaying postage stamp contract logs into a [`BatchEventHandler`].
+//!
+//! A node rebuilds its batch store by fetching the postage stamp contract's
+//! historical logs and driving them through the same handler that consumes
+//! live events. [`replay_postage_logs`] decodes `BatchCreated`, `BatchTopUp`
+//! and `BatchDepthIncrease` in log order; other events the contract emits
+//! are skipped.
+//!
+//! The replay is pure over logs the caller already holds, from any source;
+//! with the `contract` feature, [`fetch_postage_logs`](crate::fetch_postage_logs)
+//! reads them from a provider.
+
+use alloy_primitives::{Address, U256};
+use alloy_rpc_types_eth::Log;
+use alloy_sol_types::SolEvent;
+use nectar_contracts::IPostageStamp;
+use nectar_postage::{Batch, BatchEvent, BatchEventHandler, BatchId, BucketDepth, StampError};
+use thiserror::Error;
+
+/// Errors from [`replay_postage_logs`].
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum ReplayError<E> {
+    /// A log carrying a postage event's topic did not decode as that event.
+    #[error(transparent)]
+    Decode(#[from] alloy_sol_types::Error),
+
+    /// A `BatchCreated` log carries no block number, so the batch has no
+    /// start.
+    #[error("BatchCreated log has no block number")]
+    Unmined,
+
+    /// A normalised balance does not fit in a `u128`.
+    #[error("normalised balance {0} exceeds u128")]
+    BalanceOverflow(U256),
+
+    /// The contract reported a geometry this network does not accept.
+    #[error(transparent)]
+    Stamp(#[from] StampError),
+
+    /// The handler refused an event.
+    #[error("handler failed")]
+    Handler(#[source] E),
+}
+
+/// Replays postage stamp contract logs through `handler`, in order.
+///
+/// Only logs emitted by `postage_stamp` are read, so a log spoofing the event
+/// signature from another contract cannot reach the handler. Returns the
+/// number of events handled.
+///
+/// # Errors
+///
+/// Stops at the first log that fails to decode or convert, or the first event
+/// the handler refuses; the events before it have been handled.
+pub fn replay_postage_logs<H: BatchEventHandler>(
+    postage_stamp: Address,
+    logs: impl IntoIterator<Item = Log>,
+    handler: &mut H,
+) -> Result<usize, ReplayError<H::Error>> {
+    let mut handled = 0usize;
+    for log in logs {
+        if log.address() != postage_stamp {
+            continue;
+        }
+        let Some(event) = decode(&log)? else {
+            continue;
+        };
+        handler.handle_event(event).map_err(ReplayError::Handler)?;
+        handled = handled.saturating_add(1);
+    }
+    Ok(handled)
+}
+
+/// Decodes one contract log into a batch event, or `None` for an event the
+/// replay does not track.
+fn decode<E>(log: &Log) -> Result<Option<BatchEvent>, ReplayError<E>> {
+    let event = match log.topic0() {
+        Some(&IPostageStamp::BatchCreated::SIGNATURE_HASH) => {
+            let created = IPostageStamp::BatchCreated::decode_log(&log.inner)?.data;
+            let start = log.block_number.ok_or(ReplayError::Unmined)?;
+            BatchEvent::Created {
+                batch: Batch::new(
+                    BatchId::from(created.batchId),
+                    balance(created.normalisedBalance)?,
+                    start,
+                    created.owner,
+                    created.depth,
+                    BucketDepth::new(created.bucketDepth)?,
+                    created.immutableFlag,
+                ),
+            }
+        }
+        Some(&IPostageStamp::BatchTopUp::SIGNATURE_HASH) => {
+            let top_up = IPostageStamp::BatchTopUp::decode_log(&log.inner)?.data;
+            BatchEvent::TopUp {
+                batch_id: BatchId::from(top_up.batchId),
+                new_value: balance(top_up.normalisedBalance)?,
+            }
+        }
+        Some(&IPostageStamp::BatchDepthIncrease::SIGNATURE_HASH) => {
+            let increase = IPostageStamp::BatchDepthIncrease::decode_log(&log.inner)?.data;
+            BatchEvent::DepthIncrease {
+                batch_id: BatchId::from(increase.batchId),
+                new_depth: increase.newDepth,
+                new_value: balance(increase.normalisedBalance)?,
+            }
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(event))
+}
+
+/// Narrows a contract balance to the batch value type.
+fn balance<E>(value: U256) -> Result<u128, ReplayError<E>> {
+    u128::try_from(value).map_err(|_| ReplayError::BalanceOverflow(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{B256, LogData, address};
+    use nectar_contracts::IERC20;
+
+    const POSTAGE: Address = address!("0x45a1502382541Cd610CC9068e88727426b696293");
+
+    fn log(emitter: Address, block: u64, data: LogData) -> Log {
+        Log {
+            inner: alloy_primitives::Log {
+                address: emitter,
+                data,
+            },
+            block_number: Some(block),
+            ..Log::default()
+        }
+    }
+
+    fn created(batch_id: B256) -> LogData {
+        IPostageStamp::BatchCreated {
+            batchId: batch_id,
+            totalAmount: U256::from(1u64 << 40),
+            normalisedBalance: U256::from(1u64 << 20),
+            owner: Address::repeat_byte(7),
+            depth: 20,
+            bucketDepth: 16,
+            immutableFlag: true,
+        }
+        .encode_log_data()
+    }
+
+    /// Records every event, refusing any for the batch it is told to.
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<BatchEvent>,
+        refuse: Option<BatchId>,
+    }
+
+    impl BatchEventHandler for Recorder {
+        type Error = &'static str;
+
+        fn handle_event(&mut self, event: BatchEvent) -> Result<(), Self::Error> {
+            if Some(event.batch_id()) == self.refuse {
+                return Err("refused");
+            }
+            self.events.push(event);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn replay_drives_the_handler_in_log_order() {
+        let id = B256::repeat_byte(1);
+        let logs = vec![
+            log(POSTAGE, 100, created(id)),
+            // The same event from another contract is ignored.
+            log(Address::repeat_byte(9), 101, created(B256::repeat_byte(2))),
+            // A contract event the replay does not track is skipped.
+            log(
+                POSTAGE,
+                102,
+                IERC20::Transfer {
+                    from: Address::ZERO,
+                    to: Address::repeat_byte(3),
+                    value: U256::from(5),
+                }
+                .encode_log_data(),
+            ),
+            log(
+                POSTAGE,
+                103,
+                IPostageStamp::BatchTopUp {
+                    batchId: id,
+                    topupAmount: U256::from(10),
+                    normalisedBalance: U256::from(1u64 << 21),
+                }
+                .encode_log_data(),
+            ),
+            log(
+                POSTAGE,
+                104,
+                IPostageStamp::BatchDepthIncrease {
+                    batchId: id,
+                    newDepth: 22,
+                    normalisedBalance: U256::from(1u64 << 19),
+                }
+                .encode_log_data(),
+            ),
+        ];
+
+        let mut recorder = Recorder::default();
+        assert_eq!(
+            replay_postage_logs(POSTAGE, logs, &mut recorder).unwrap(),
+            3
+        );
+
+        let batch_id = BatchId::from(id);
+        let expected = Batch::new(
+            batch_id,
+            1 << 20,
+            100,
+            Address::repeat_byte(7),
+            20,
+            BucketDepth::new(16).unwrap(),
+            true,
+        );
+        assert_eq!(
+            recorder.events,
+            vec![
+                BatchEvent::Created { batch: expected },
+                BatchEvent::TopUp {
+                    batch_id,
+                    new_value: 1 << 21
+                },
+                BatchEvent::DepthIncrease {
+                    batch_id,
+                    new_depth: 22,
+                    new_value: 1 << 19
+                },
+            ]
+        );
+    }
+
+    /// Applies events to batch state the way a node's batch store does.
+    #[derive(Default)]
+    struct Ledger(std::collections::HashMap<BatchId, Batch>);
+
+    impl BatchEventHandler for Ledger {
+        type Error = &'static str;
+
+        fn handle_event(&mut self, event: BatchEvent) -> Result<(), Self::Error> {
+            match event {
+                BatchEvent::Created { batch } => {
+                    self.0.insert(batch.id(), batch);
+                }
+                BatchEvent::TopUp {
+                    batch_id,
+                    new_value,
+                } => self
+                    .0
+                    .get_mut(&batch_id)
+                    .ok_or("unknown")?
+                    .set_value(new_value),
+                BatchEvent::DepthIncrease {
+                    batch_id,
+                    new_depth,
+                    new_value,
+                } => {
+                    let batch = self.0.get_mut(&batch_id).ok_or("unknown")?;
+                    batch.set_depth(new_depth);
+                    batch.set_value(new_value);
+                }
+                BatchEvent::Expired { batch_id } => {
+                    self.0.remove(&batch_id);
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn replay_carries_the_diluted_balance() {
+        let id = B256::repeat_byte(1);
+        let logs = [
+            log(POSTAGE, 100, created(id)),
+            log(
+                POSTAGE,
+                101,
+                IPostageStamp::BatchDepthIncrease {
+                    batchId: id,
+                    newDepth: 22,
+                    normalisedBalance: U256::from(1u64 << 18),
+                }
+                .encode_log_data(),
+            ),
+        ];
+
+        let mut ledger = Ledger::default();
+        replay_postage_logs(POSTAGE, logs, &mut ledger).unwrap();
+
+        // Two levels of dilution quarter the per-chunk balance; keeping the
+        // created value would overstate the batch's remaining time.
+        let batch = &ledger.0[&BatchId::from(id)];
+        assert_eq!(batch.depth(), 22);
+        assert_eq!(batch.value(), 1 << 18);
+    }
+
+    #[test]
+    fn replay_stops_at_the_first_failure() {
+        let mut unmined = log(POSTAGE, 0, created(B256::repeat_byte(1)));
+        unmined.block_number = None;
+        let mut recorder = Recorder::default();
+        assert!(matches!(
+            replay_postage_logs(POSTAGE, [unmined], &mut recorder),
+            Err(ReplayError::Unmined)
+        ));
+
+        let refused = B256::repeat_byte(2);
+        let logs = [
+            log(POSTAGE, 1, created(B256::repeat_byte(1))),
+            log(POSTAGE, 2, created(refused)),
+            log(POSTAGE, 3, created(B256::repeat_byte(3))),
+        ];
+        let mut recorder = Recorder {
+            refuse: Some(BatchId::from(refused)),
+            ..Recorder::default()
+        };
+        assert!(matches!(
+            replay_postage_logs(POSTAGE, logs, &mut recorder),
+            Err(ReplayError::Handler("refused"))
+        ));
+        assert_eq!(recorder.events.len(), 1);
+    }
+}
@@ -372,6 +372,18 @@ impl<S: SwarmSpec, R: Reservation> StampIssuer for RingIssuerFor<S, R> {
             })
     }
 
+    fn peek_index(&self, address: &ChunkAddress) -> Option<StampIndex> {
+        // A ring wraps rather than filling, so this is `None` only for a fully
+        // reserved bucket, exactly when `prepare_stamp` fails.
+        let bucket = calculate_bucket(address, self.counters.bucket_depth().get());
+        let reservation = &self.reservation;
+        let position = self
+            .counters
+            .peek(bucket, |slot| reservation.is_protected(bucket, slot))
+            .ok()?;
+        Some(StampIndex::new(bucket, position))
+    }
+
     fn batch_id(&self) -> BatchId {
         self.batch_id
     }
@@ -518,6 +530,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn ring_peek_index_previews_wraps_and_reserved_slots() {
+        // depth=18, bucket_depth=16 gives 4 slots per bucket; slot 0 is
+        // protected, so the ring cycles 1, 2, 3.
+        let batch = mutable_batch(18, 16);
+        let address = test_address(0x00AA);
+        let bucket = calculate_bucket(&address, 16);
+        let mut issuer = RingIssuer::reserved(&batch, [(bucket, 0)]).unwrap();
+
+        for _ in 0..7 {
+            let peeked = issuer.peek_index(&address).unwrap();
+            assert_eq!(issuer.prepare_stamp(&address, 0).unwrap().index, peeked);
+        }
+        // A wrapped ring has no fresh slot, yet still previews the overwrite.
+        assert!(!issuer.bucket_has_capacity(bucket));
+        assert_eq!(
+            issuer.peek_index(&address),
+            Some(StampIndex::new(bucket, 2))
+        );
+
+        let full = RingIssuer::reserved(&batch, (0..4).map(|slot| (bucket, slot))).unwrap();
+        assert_eq!(full.peek_index(&address), None);
+    }
+
     #[test]
     fn reserved_ring_exhausts_when_every_slot_is_protected() {
         // depth=17, bucket_depth=16 gives 2 slots per bucket. Protect both, so
@@ -16,6 +16,7 @@
 use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 
 use crate::error::IssuerError;
+use crate::issuer::{StampIssuer, bucket_full};
 use nectar_postage::{
     Batch, BatchId, BucketDepth, StampDigest, StampError, StampIndex, calculate_bucket,
 };
@@ -28,13 +29,28 @@ use {
     alloy_primitives::B256,
     alloy_signer::Signature,
     nectar_clock::{Clock, SystemClock},
-    nectar_postage::Stamp,
+    nectar_postage::{Stamp, calculate_buckets},
 };
 
 /// Number of shards for bucket partitioning.
 /// Must be a power of 2 for efficient bucket-to-shard mapping.
 const DEFAULT_SHARD_COUNT: usize = 16;
 
+/// Shards per thread chosen by [`ShardedIssuerFor::auto`].
+///
+/// Threads stamping random addresses pick shards uniformly, so a few shards
+/// per thread keep the odds of two threads meeting on one lock low.
+const SHARDS_PER_THREAD: usize = 4;
+
+/// The shard count [`ShardedIssuerFor::auto`] picks for `parallelism`
+/// threads: the power of two at or above `SHARDS_PER_THREAD` per thread.
+fn auto_shard_count(parallelism: usize) -> usize {
+    parallelism
+        .saturating_mul(SHARDS_PER_THREAD)
+        .checked_next_power_of_two()
+        .unwrap_or(DEFAULT_SHARD_COUNT)
+}
+
 /// A shard containing bucket indices for a subset of the bucket space.
 #[derive(Debug)]
 struct BucketShard {
@@ -83,6 +99,23 @@ impl BucketShard {
         }
     }
 
+    /// Yields `(bucket, count)` for every bucket of this shard holding at
+    /// least one stamp, ascending.
+    // `base_bucket + local` stays below `1 << bucket_depth`, so it fits `u32`
+    // and cannot overflow.
+    #[allow(clippy::arithmetic_side_effects, clippy::as_conversions)]
+    fn occupied(&self, bucket_capacity: u32) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.indices
+            .iter()
+            .enumerate()
+            .filter_map(move |(local, index)| {
+                // A racing `allocate` on a full bucket overshoots before rolling
+                // back; report the capacity instead.
+                let count = index.load(Ordering::Relaxed).min(bucket_capacity);
+                (count > 0).then(|| (self.base_bucket + local as u32, count))
+            })
+    }
+
     /// Gets the current utilization of a bucket.
     // Shard routing invariant: `local_index(bucket) < indices.len()` because this
     // shard owns buckets `[base_bucket, base_bucket + indices.len())`.
@@ -130,6 +163,8 @@ pub struct ShardedIssuerFor<S: SwarmSpec = Mainnet> {
     max_utilization: AtomicU32,
     /// Total stamps issued (atomic for thread-safety).
     stamps_issued: AtomicU64,
+    /// Whether the batch is known to be immutable.
+    immutable: bool,
 }
 
 /// The [`ShardedIssuerFor`] of the mainnet spec.
@@ -141,6 +176,21 @@ impl<S: SwarmSpec> ShardedIssuerFor<S> {
         Self::with_shard_count(batch_id, depth, bucket_depth, DEFAULT_SHARD_COUNT)
     }
 
+    /// Creates a new sharded issuer sized to the machine.
+    ///
+    /// Picks the power of two at or above four shards per thread of
+    /// [`available_parallelism`](std::thread::available_parallelism), clamped
+    /// to the bucket count: 4 threads get 16 shards, 64 threads get 256.
+    /// Falls back to the default of 16 when the parallelism is unknown. Use
+    /// [`with_shard_count`](Self::with_shard_count) to choose explicitly.
+    pub fn auto(batch_id: BatchId, depth: u8, bucket_depth: BucketDepth<S>) -> Self {
+        let shard_count = std::thread::available_parallelism()
+            .map_or(DEFAULT_SHARD_COUNT, |threads| {
+                auto_shard_count(threads.get())
+            });
+        Self::with_shard_count(batch_id, depth, bucket_depth, shard_count)
+    }
+
     /// Creates a new sharded issuer with a specific number of shards.
     ///
     /// # Panics
@@ -200,12 +250,14 @@ impl<S: SwarmSpec> ShardedIssuerFor<S> {
             shard_shift,
             max_utilization: AtomicU32::new(0),
             stamps_issued: AtomicU64::new(0),
+            immutable: false,
         }
     }
 
     /// Creates a sharded issuer from a batch.
     ///
-    /// Immutable batches yield a fill-only issuer. Mutable batches are refused
+    /// Immutable batches yield a fill-only issuer that reports a full bucket as
+    /// [`StampError::ImmutableBatchFull`]. Mutable batches are refused
     /// with [`IssuerError::MutableNotSupported`], matching
     /// [`MemoryIssuer::from_batch`](crate::MemoryIssuer::from_batch), so a ring
     /// is never produced by accident. Overwrite-aware parallel issuance must be
@@ -216,7 +268,10 @@ impl<S: SwarmSpec> ShardedIssuerFor<S> {
     /// self-hosting, where the protected slots come from `nectar-postage-usage`.
     pub fn from_batch(batch: &Batch<S>) -> Result<Self, IssuerError> {
         if batch.immutable() {
-            Ok(Self::new(batch.id(), batch.depth(), batch.bucket_depth()))
+            Ok(Self {
+                immutable: true,
+                ..Self::new(batch.id(), batch.depth(), batch.bucket_depth())
+            })
         } else {
             Err(IssuerError::MutableNotSupported)
         }
@@ -270,19 +325,26 @@ impl<S: SwarmSpec> ShardedIssuerFor<S> {
         timestamp: u64,
     ) -> Result<StampDigest, StampError> {
         let bucket = calculate_bucket(address, self.bucket_depth.get());
+        self.prepare_in_bucket(address, bucket, timestamp)
+    }
+
+    /// [`prepare_stamp`](Self::prepare_stamp) for an `address` whose bucket
+    /// was already computed.
+    fn prepare_in_bucket(
+        &self,
+        address: &ChunkAddress,
+        bucket: u32,
+        timestamp: u64,
+    ) -> Result<StampDigest, StampError> {
         let shard_idx = self.shard_index(bucket);
         // `shard_index` masks with `shard_mask = shards.len() - 1`, so the index
         // is always in range.
         #[allow(clippy::indexing_slicing)]
         let shard = &self.shards[shard_idx];
 
-        let position =
-            shard
-                .allocate(bucket, self.bucket_capacity)
-                .ok_or(StampError::BucketFull {
-                    bucket,
-                    capacity: self.bucket_capacity,
-                })?;
+        let position = shard
+            .allocate(bucket, self.bucket_capacity)
+            .ok_or(bucket_full(self.immutable, bucket, self.bucket_capacity))?;
 
         // Update stats (relaxed ordering is fine for stats)
         self.stamps_issued.fetch_add(1, Ordering::Relaxed);
@@ -309,6 +371,41 @@ impl<S: SwarmSpec> ShardedIssuerFor<S> {
         Ok(StampDigest::new(*address, self.batch_id, index, timestamp))
     }
 
+    /// The buckets of `addresses`, in order, computed in one pass.
+    #[cfg(feature = "parallel")]
+    fn buckets_of(&self, addresses: &[ChunkAddress]) -> Vec<u32> {
+        let depth = self.bucket_depth.get();
+        let mut buckets = vec![0; addresses.len()];
+        // The output is sized to `addresses` and the depth is validated, so
+        // the batched path cannot refuse; stay correct if it ever does.
+        if calculate_buckets(addresses, depth, &mut buckets).is_err() {
+            return addresses
+                .iter()
+                .map(|address| calculate_bucket(address, depth))
+                .collect();
+        }
+        buckets
+    }
+
+    /// Returns the stamp index the next [`prepare_stamp`](Self::prepare_stamp)
+    /// for `address` would allocate, or `None` if its bucket is full.
+    ///
+    /// Under concurrent stamping another thread may take that index first, so
+    /// treat the answer as a preview, not a reservation.
+    // `shard_index` masks with `shard_mask = shards.len() - 1`, so the index is
+    // always in range.
+    #[allow(clippy::indexing_slicing)]
+    pub fn peek_index(&self, address: &ChunkAddress) -> Option<StampIndex> {
+        let bucket = calculate_bucket(address, self.bucket_depth.get());
+        let position = self.shards[self.shard_index(bucket)].utilization(bucket);
+        (position < self.bucket_capacity).then(|| StampIndex::new(bucket, position))
+    }
+
+    /// Whether the issuer was built from an immutable batch.
+    pub const fn is_immutable(&self) -> bool {
+        self.immutable
+    }
+
     /// Batch ID.
     pub const fn batch_id(&self) -> BatchId {
         self.batch_id
@@ -338,6 +435,35 @@ impl<S: SwarmSpec> ShardedIssuerFor<S> {
         self.shards[shard_idx].utilization(bucket)
     }
 
+    /// `(bucket, count)` for every bucket holding at least one stamp,
+    /// ascending by bucket.
+    ///
+    /// Reads each bucket's counter without locking, so under concurrent
+    /// stamping the result is a near-instant view rather than a snapshot.
+    /// Buckets far above the rest predict a bucket-full error long before the
+    /// batch is used up.
+    pub fn bucket_utilizations(&self) -> Vec<(u32, u32)> {
+        // Shards hold consecutive bucket ranges in order, so the walk is
+        // already ascending.
+        self.shards
+            .iter()
+            .flat_map(|shard| shard.occupied(self.bucket_capacity))
+            .collect()
+    }
+
+    /// The `n` fullest buckets as `(bucket, count)`, fullest first; ties go to
+    /// the lower bucket.
+    ///
+    /// Empty buckets are never returned, so fewer than `n` come back when
+    /// fewer buckets are in use. See
+    /// [`bucket_utilizations`](Self::bucket_utilizations).
+    pub fn hottest_buckets(&self, n: usize) -> Vec<(u32, u32)> {
+        let mut utilizations = self.bucket_utilizations();
+        utilizations.sort_by_key(|&(bucket, count)| (core::cmp::Reverse(count), bucket));
+        utilizations.truncate(n);
+        utilizations
+    }
+
     /// Total stamps issued.
     pub fn stamps_issued(&self) -> u64 {
         self.stamps_issued.load(Ordering::Relaxed)
@@ -354,6 +480,57 @@ impl<S: SwarmSpec> ShardedIssuerFor<S> {
     }
 }
 
+/// The sharded issuer behind the single-threaded issuer surface, so it can sit
+/// beside a [`MemoryIssuer`](crate::MemoryIssuer) behind `dyn StampIssuer`.
+/// Each method forwards to its inherent counterpart.
+impl<S: SwarmSpec> StampIssuer for ShardedIssuerFor<S> {
+    fn prepare_stamp(
+        &mut self,
+        address: &ChunkAddress,
+        timestamp: u64,
+    ) -> Result<StampDigest, StampError> {
+        Self::prepare_stamp(self, address, timestamp)
+    }
+
+    fn peek_index(&self, address: &ChunkAddress) -> Option<StampIndex> {
+        Self::peek_index(self, address)
+    }
+
+    fn batch_id(&self) -> BatchId {
+        Self::batch_id(self)
+    }
+
+    fn batch_depth(&self) -> u8 {
+        Self::batch_depth(self)
+    }
+
+    fn bucket_depth(&self) -> u8 {
+        Self::bucket_depth(self)
+    }
+
+    fn max_bucket_utilization(&self) -> u32 {
+        Self::max_bucket_utilization(self)
+    }
+
+    fn bucket_utilization(&self, bucket: u32) -> u32 {
+        if bucket < StampIssuer::bucket_count(self) {
+            Self::bucket_utilization(self, bucket)
+        } else {
+            0
+        }
+    }
+
+    fn bucket_has_capacity(&self, bucket: u32) -> bool {
+        bucket < StampIssuer::bucket_count(self)
+            && Self::bucket_utilization(self, bucket) < self.bucket_capacity
+    }
+
+    fn stamps_issued(&self) -> Option<u64> {
+        // Fill issuance is monotone, so the running total is the lifetime count.
+        Some(Self::stamps_issued(self))
+    }
+}
+
 /// Result of a parallel stamp operation.
 #[cfg(feature = "parallel")]
 #[derive(Debug)]
@@ -431,8 +608,9 @@ where
 
     addresses
         .par_iter()
-        .map(|address| {
-            let result = sign_stamp_internal(issuer, signer, address, clock);
+        .zip(issuer.buckets_of(addresses))
+        .map(|(address, bucket)| {
+            let result = sign_stamp_internal(issuer, signer, address, bucket, clock);
             StampResult {
                 address: *address,
                 result,
@@ -441,11 +619,57 @@ where
         .collect()
 }
 
+/// [`sign_stamps_parallel`] that hands each result to `sink` instead of
+/// collecting them, so memory stays bounded however many addresses are
+/// signed.
+///
+/// `sink` is called from the rayon worker threads, in no particular order;
+/// each [`StampResult`] carries its address so results can be matched back
+/// to their chunks. Use this to write stamps straight to storage during a
+/// large upload.
+///
+/// # Example
+///
+/// ```ignore
+/// use std::sync::mpsc;
+///
+/// let (tx, rx) = mpsc::channel();
+/// let tx = std::sync::Mutex::new(tx);
+/// sign_stamps_parallel_for_each(&issuer, &signer_fn, &addresses, |result| {
+///     let _ = tx.lock().unwrap().send(result);
+/// });
+/// ```
+#[cfg(feature = "parallel")]
+pub fn sign_stamps_parallel_for_each<Sp, Sg, E, F>(
+    issuer: &ShardedIssuerFor<Sp>,
+    signer: &Sg,
+    addresses: &[ChunkAddress],
+    sink: F,
+) where
+    Sp: SwarmSpec + Sync,
+    Sg: Fn(&B256) -> Result<Signature, E> + Sync,
+    E: Into<SigningError>,
+    F: Fn(StampResult) + Sync,
+{
+    use rayon::prelude::*;
+
+    addresses
+        .par_iter()
+        .zip(issuer.buckets_of(addresses))
+        .for_each(|(address, bucket)| {
+            sink(StampResult {
+                address: *address,
+                result: sign_stamp_internal(issuer, signer, address, bucket, &SystemClock),
+            });
+        });
+}
+
 #[cfg(feature = "parallel")]
 fn sign_stamp_internal<Sp, Sg, E, C>(
     issuer: &ShardedIssuerFor<Sp>,
     signer: &Sg,
     address: &ChunkAddress,
+    bucket: u32,
     clock: &C,
 ) -> Result<Stamp, SigningError>
 where
@@ -455,7 +679,7 @@ where
     C: Clock,
 {
     let timestamp = stamp_timestamp(clock);
-    let digest = issuer.prepare_stamp(address, timestamp)?;
+    let digest = issuer.prepare_in_bucket(address, bucket, timestamp)?;
     let prehash = digest.to_prehash();
     let sig = signer(&prehash).map_err(|e| e.into())?;
     Ok(stamp_from_signature(&digest, sig))
@@ -505,11 +729,22 @@ mod tests {
             0,
             0,
             Default::default(),
-            20,
+            17,
             BucketDepth::new(16).unwrap(),
             true,
         );
-        assert!(ShardedIssuer::from_batch(&immutable).is_ok());
+        let issuer = ShardedIssuer::from_batch(&immutable).unwrap();
+        assert!(issuer.is_immutable());
+
+        // depth=17, bucket_depth=16 gives 2 slots per bucket; the overflow is
+        // refused as an immutable batch, never by reusing an index.
+        let address = ChunkAddress::new([0xAB; 32]);
+        issuer.prepare_stamp(&address, 1).unwrap();
+        issuer.prepare_stamp(&address, 2).unwrap();
+        assert!(matches!(
+            issuer.prepare_stamp(&address, 3),
+            Err(StampError::ImmutableBatchFull { capacity: 2, .. })
+        ));
     }
 
     #[test]
@@ -523,6 +758,18 @@ mod tests {
         assert_eq!(issuer.shard_count(), DEFAULT_SHARD_COUNT);
     }
 
+    #[test]
+    fn auto_scales_shards_with_parallelism() {
+        assert_eq!(auto_shard_count(1), 4);
+        assert_eq!(auto_shard_count(4), 16);
+        assert_eq!(auto_shard_count(6), 32);
+        assert_eq!(auto_shard_count(64), 256);
+        assert_eq!(auto_shard_count(usize::MAX), DEFAULT_SHARD_COUNT);
+
+        let issuer = ShardedIssuer::auto(BatchId::ZERO, 20, BucketDepth::new(16).unwrap());
+        assert!(issuer.shard_count().is_power_of_two());
+    }
+
     #[test]
     fn test_sharded_issuer_prepare_stamp() {
         let issuer = ShardedIssuer::new(BatchId::ZERO, 20, BucketDepth::new(16).unwrap());
@@ -535,6 +782,52 @@ mod tests {
         assert_eq!(issuer.stamps_issued(), 1);
     }
 
+    #[test]
+    fn test_sharded_issuer_peek_index() {
+        // depth=17, bucket_depth=16 gives 2 slots per bucket.
+        let issuer = ShardedIssuer::new(BatchId::ZERO, 17, BucketDepth::new(16).unwrap());
+        let address = ChunkAddress::from(B256::random());
+
+        for _ in 0..2 {
+            let peeked = issuer.peek_index(&address).unwrap();
+            assert_eq!(issuer.peek_index(&address), Some(peeked));
+            assert_eq!(issuer.prepare_stamp(&address, 0).unwrap().index, peeked);
+        }
+        assert_eq!(issuer.peek_index(&address), None);
+        assert_eq!(issuer.stamps_issued(), 2);
+    }
+
+    #[test]
+    fn test_sharded_issuer_bucket_utilizations() {
+        // depth 18 over bucket depth 16: 4 slots per bucket.
+        let issuer = ShardedIssuer::new(BatchId::ZERO, 18, BucketDepth::new(16).unwrap());
+        assert!(issuer.bucket_utilizations().is_empty());
+        assert!(issuer.hottest_buckets(3).is_empty());
+
+        let fill = |bucket: u16, n: usize| {
+            let mut bytes = [0u8; 32];
+            bytes[..2].copy_from_slice(&bucket.to_be_bytes());
+            for _ in 0..n {
+                let _ = issuer.prepare_stamp(&ChunkAddress::new(bytes), 0);
+            }
+        };
+        fill(0xF000, 2);
+        fill(0x0001, 1);
+        fill(0x8000, 2);
+        // Overfilling a bucket reports its capacity.
+        fill(0x1234, 6);
+
+        assert_eq!(
+            issuer.bucket_utilizations(),
+            vec![(0x0001, 1), (0x1234, 4), (0x8000, 2), (0xF000, 2)]
+        );
+        assert_eq!(
+            issuer.hottest_buckets(3),
+            vec![(0x1234, 4), (0x8000, 2), (0xF000, 2)]
+        );
+        assert_eq!(issuer.hottest_buckets(10).len(), 4);
+    }
+
     #[test]
     fn test_sharded_issuer_dilute_grows_capacity_only() {
         // depth=17, bucket_depth=16 gives 2 slots per bucket.
@@ -621,12 +914,49 @@ mod tests {
         let results = sign_stamps_parallel(&issuer, &sign_fn, &addresses);
 
         assert_eq!(results.len(), 100);
-        for result in &results {
-            assert!(result.result.is_ok());
+        for (result, address) in results.iter().zip(&addresses) {
+            // The batched buckets agree with the scalar path, in order.
+            assert_eq!(result.address, *address);
+            let stamp = result.result.as_ref().unwrap();
+            assert_eq!(stamp.bucket(), calculate_bucket(address, 16));
         }
         assert_eq!(issuer.stamps_issued(), 100);
     }
 
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_signing_for_each() {
+        use crate::error::SigningError;
+        use alloy_signer::SignerSync;
+        use alloy_signer_local::PrivateKeySigner;
+        use std::collections::HashSet;
+        use std::sync::Mutex;
+
+        let issuer = ShardedIssuer::new(BatchId::ZERO, 24, BucketDepth::new(16).unwrap());
+        let signer = PrivateKeySigner::random();
+
+        let addresses: Vec<_> = (0..64)
+            .map(|_| ChunkAddress::from(B256::random()))
+            .collect();
+
+        let sign_fn = |prehash: &B256| -> Result<Signature, SigningError> {
+            Ok(signer
+                .sign_message_sync(prehash.as_slice())
+                .map_err(alloy_signer::Error::other)?)
+        };
+
+        let seen = Mutex::new(HashSet::new());
+        sign_stamps_parallel_for_each(&issuer, &sign_fn, &addresses, |result| {
+            let stamp = result.result.unwrap();
+            assert_eq!(stamp.batch(), BatchId::ZERO);
+            assert!(seen.lock().unwrap().insert(result.address));
+        });
+
+        let seen = seen.into_inner().unwrap();
+        assert_eq!(seen, addresses.iter().copied().collect::<HashSet<_>>());
+        assert_eq!(issuer.stamps_issued(), 64);
+    }
+
     #[cfg(feature = "parallel")]
     #[test]
     fn test_parallel_signing_with_clock() {
@@ -7,10 +7,11 @@
 //! with `"\x19Ethereum Signed Message:\n32"`.
 //!
 //! Use alloy's [`SignerSync`] trait with `sign_message_sync(prehash.as_slice())`
-//! rather than `sign_hash_sync`.
+//! rather than `sign_hash_sync`. [`BatchStamper::stamp_async`] does the same
+//! through the async [`Signer`] trait's `sign_message`.
 
 use alloy_primitives::Signature;
-use alloy_signer::SignerSync;
+use alloy_signer::{Signer, SignerSync};
 
 use crate::StampIssuer;
 use crate::error::SigningError;
@@ -104,6 +105,15 @@ pub trait Stamper {
 /// system clock; [`with_clock`](Self::with_clock) injects a deterministic
 /// source.
 ///
+/// A signing failure hands the allocated index back to the issuer
+/// ([`StampIssuer::release_stamp`]), so a flaky remote signer does not burn
+/// bucket capacity on issuers that can rewind.
+///
+/// The signer is any [`SignerSync`], which alloy implements for `&S`,
+/// `Box<S>` and `Arc<S>` as well as `S`. One remote or hardware signer can
+/// therefore back many stampers: wrap it in an `Arc` (or
+/// `Arc<dyn SignerSync + Send + Sync>`) and clone the handle into each.
+///
 /// # Example
 ///
 /// ```ignore
@@ -213,6 +223,90 @@ where
     }
 }
 
+impl<I, S, C> BatchStamper<I, S, C>
+where
+    I: StampIssuer,
+    C: Clock,
+{
+    /// Stamps a chunk identified by its address, awaiting an async signer.
+    ///
+    /// The async counterpart of [`Stamper::stamp`] for signers that sign over
+    /// the network: cloud KMS keys, HSMs and other remote services implement
+    /// alloy's `async_trait` [`Signer`] rather than [`SignerSync`]. Index
+    /// allocation and release are the same as on the sync path.
+    ///
+    /// The stamper is borrowed mutably across the await, so one stamper signs
+    /// one stamp at a time. For concurrent remote signing, split the work
+    /// with [`prepare_stamp`](Self::prepare_stamp) and sign the digests
+    /// separately, or run one stamper per issuer shard.
+    ///
+    /// The index is taken before the signer is awaited. Dropping the future
+    /// before the signer answers hands it back to the issuer, as a signer
+    /// failure does.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SigningError::Stamp`] if the chunk's bucket is full and
+    /// [`SigningError::Signer`] if the signer fails; the allocated index is
+    /// released in the latter case.
+    pub async fn stamp_async(&mut self, address: &ChunkAddress) -> Result<Stamp, SigningError>
+    where
+        S: Signer + Sync,
+    {
+        let digest = self
+            .issuer
+            .prepare_stamp(address, stamp_timestamp(&self.clock))?;
+        let mut pending = PendingStamp {
+            issuer: &mut self.issuer,
+            digest,
+            armed: true,
+        };
+        let signed = self
+            .signer
+            .sign_message(digest.to_prehash().as_slice())
+            .await;
+        pending.armed = false;
+        drop(pending);
+        self.finish_stamp(&digest, signed)
+    }
+
+    /// Turns the signer's answer for `digest` into a stamp.
+    ///
+    /// On failure the digest was never signed, so its index is handed back to
+    /// the issuer to be given out again where it can rewind.
+    fn finish_stamp(
+        &mut self,
+        digest: &StampDigest,
+        signed: Result<Signature, alloy_signer::Error>,
+    ) -> Result<Stamp, SigningError> {
+        match signed {
+            Ok(sig) => Ok(Self::stamp_from_signature(digest, sig)),
+            Err(err) => {
+                self.issuer.release_stamp(digest);
+                Err(err.into())
+            }
+        }
+    }
+}
+
+/// A prepared stamp whose signer has not answered yet.
+///
+/// Dropped while still armed, the future awaiting the signer was cancelled,
+/// so the index goes back to the issuer.
+struct PendingStamp<'a, I: StampIssuer> {
+    issuer: &'a mut I,
+    digest: StampDigest,
+    armed: bool,
+}
+
+impl<I: StampIssuer> Drop for PendingStamp<'_, I> {
+    fn drop(&mut self) {
+        if self.armed {
+            self.issuer.release_stamp(&self.digest);
+        }
+    }
+}
+
 impl<I, S, C> Stamper for BatchStamper<I, S, C>
 where
     I: StampIssuer,
@@ -222,13 +316,13 @@ where
     type Error = SigningError;
 
     fn stamp(&mut self, address: &ChunkAddress) -> Result<Stamp, Self::Error> {
-        let timestamp = stamp_timestamp(&self.clock);
-        let digest = self.issuer.prepare_stamp(address, timestamp)?;
-        let prehash = digest.to_prehash();
-
-        let sig = self.signer.sign_message_sync(prehash.as_slice())?;
-
-        Ok(Self::stamp_from_signature(&digest, sig))
+        let digest = self
+            .issuer
+            .prepare_stamp(address, stamp_timestamp(&self.clock))?;
+        let signed = self
+            .signer
+            .sign_message_sync(digest.to_prehash().as_slice());
+        self.finish_stamp(&digest, signed)
     }
 
     fn batch_id(&self) -> BatchId {
@@ -303,6 +397,162 @@ mod tests {
         assert_eq!(stamp2.bucket(), stamp3.bucket());
     }
 
+    /// Fails its first `failures` signings, then signs like [`MockSigner`].
+    struct FlakySigner {
+        failures: std::cell::Cell<u32>,
+    }
+
+    impl SignerSync for FlakySigner {
+        fn sign_hash_sync(&self, hash: &B256) -> Result<Signature, alloy_signer::Error> {
+            self.sign_message_sync(hash.as_slice())
+        }
+
+        fn sign_message_sync(&self, message: &[u8]) -> Result<Signature, alloy_signer::Error> {
+            let failures = self.failures.get();
+            if failures > 0 {
+                self.failures.set(failures - 1);
+                return Err(alloy_signer::Error::other("remote signer unavailable"));
+            }
+            MockSigner.sign_message_sync(message)
+        }
+
+        fn chain_id_sync(&self) -> Option<u64> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_batch_stamper_signing_failure_releases_the_index() {
+        let issuer = MemoryIssuer::new(BatchId::ZERO, 20, BucketDepth::new(16).unwrap());
+        let signer = FlakySigner {
+            failures: std::cell::Cell::new(2),
+        };
+        let mut stamper = BatchStamper::new(issuer, signer);
+        let address = ChunkAddress::new([0xAB; 32]);
+
+        assert!(matches!(
+            stamper.stamp(&address),
+            Err(SigningError::Signer(_))
+        ));
+        assert!(stamper.stamp(&address).is_err());
+        assert_eq!(stamper.issuer().stamps_issued(), Some(0));
+
+        // No gap: the first signed stamps take indices 0 and 1.
+        assert_eq!(stamper.stamp(&address).unwrap().index(), 0);
+        assert_eq!(stamper.stamp(&address).unwrap().index(), 1);
+    }
+
+    /// An async signer standing in for a remote KMS: fails its first
+    /// `failures` requests, then signs like [`MockSigner`].
+    struct RemoteSigner {
+        failures: std::sync::atomic::AtomicU32,
+    }
+
+    #[async_trait::async_trait]
+    impl Signer for RemoteSigner {
+        async fn sign_hash(&self, hash: &B256) -> Result<Signature, alloy_signer::Error> {
+            let failed = self
+                .failures
+                .fetch_update(
+                    std::sync::atomic::Ordering::Relaxed,
+                    std::sync::atomic::Ordering::Relaxed,
+                    |failures| failures.checked_sub(1),
+                )
+                .is_ok();
+            if failed {
+                return Err(alloy_signer::Error::other("kms request timed out"));
+            }
+            MockSigner.sign_hash_sync(hash)
+        }
+
+        fn address(&self) -> alloy_primitives::Address {
+            alloy_primitives::Address::ZERO
+        }
+
+        fn chain_id(&self) -> Option<u64> {
+            None
+        }
+
+        fn set_chain_id(&mut self, _chain_id: Option<u64>) {}
+    }
+
+    /// A remote signer that never answers.
+    struct StalledSigner;
+
+    #[async_trait::async_trait]
+    impl Signer for StalledSigner {
+        async fn sign_hash(&self, _hash: &B256) -> Result<Signature, alloy_signer::Error> {
+            std::future::pending().await
+        }
+
+        fn address(&self) -> alloy_primitives::Address {
+            alloy_primitives::Address::ZERO
+        }
+
+        fn chain_id(&self) -> Option<u64> {
+            None
+        }
+
+        fn set_chain_id(&mut self, _chain_id: Option<u64>) {}
+    }
+
+    #[test]
+    fn test_stamp_async_dropped_mid_sign_releases_the_index() {
+        let issuer = MemoryIssuer::new(BatchId::ZERO, 20, BucketDepth::new(16).unwrap());
+        let mut stamper = BatchStamper::new(issuer, StalledSigner);
+        let address = ChunkAddress::new([0xAB; 32]);
+
+        {
+            let mut stamp = std::pin::pin!(stamper.stamp_async(&address));
+            let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+            assert!(stamp.as_mut().poll(&mut cx).is_pending());
+        }
+        assert_eq!(stamper.issuer().stamps_issued(), Some(0));
+    }
+
+    // Sanctioned tokio adapter test: the test macro expands to `Runtime::block_on`.
+    #[allow(clippy::disallowed_methods)]
+    #[tokio::test]
+    async fn test_stamp_async_signs_through_a_remote_signer() {
+        let issuer = MemoryIssuer::new(BatchId::ZERO, 20, BucketDepth::new(16).unwrap());
+        let signer = RemoteSigner {
+            failures: std::sync::atomic::AtomicU32::new(1),
+        };
+        let mut stamper = BatchStamper::new(issuer, signer);
+        let address = ChunkAddress::new([0xAB; 32]);
+
+        assert!(matches!(
+            stamper.stamp_async(&address).await,
+            Err(SigningError::Signer(_))
+        ));
+        assert_eq!(stamper.issuer().stamps_issued(), Some(0));
+
+        let first = stamper.stamp_async(&address).await.unwrap();
+        let second = stamper.stamp_async(&address).await.unwrap();
+        assert_eq!(first.batch(), BatchId::ZERO);
+        assert_eq!((first.index(), second.index()), (0, 1));
+        assert_eq!(first.bucket(), second.bucket());
+        assert_eq!(stamper.issuer().max_bucket_utilization(), 2);
+
+        // The async path signs exactly what the sync stamper signs.
+        let mut sync = BatchStamper::with_clock(
+            MemoryIssuer::new(BatchId::ZERO, 20, BucketDepth::new(16).unwrap()),
+            MockSigner,
+            nectar_clock::ManualClock::new(7),
+        );
+        let mut remote = BatchStamper::with_clock(
+            MemoryIssuer::new(BatchId::ZERO, 20, BucketDepth::new(16).unwrap()),
+            RemoteSigner {
+                failures: std::sync::atomic::AtomicU32::new(0),
+            },
+            nectar_clock::ManualClock::new(7),
+        );
+        assert_eq!(
+            sync.stamp(&address).unwrap(),
+            remote.stamp_async(&address).await.unwrap()
+        );
+    }
+
     #[test]
     fn test_batch_stamper_injected_clock() {
         use nectar_clock::ManualClock;
@@ -363,6 +613,38 @@ mod tests {
         assert_eq!(stamper.max_bucket_utilization(), 2);
     }
 
+    #[test]
+    fn test_batch_stamper_shares_an_arc_signer() {
+        use alloy_signer_local::PrivateKeySigner;
+        use std::sync::Arc;
+
+        let signer = Arc::new(PrivateKeySigner::random());
+        let clock = nectar_clock::ManualClock::new(7);
+        let issuer = || MemoryIssuer::new(BatchId::ZERO, 20, BucketDepth::new(16).unwrap());
+        let mut first = BatchStamper::with_clock(issuer(), Arc::clone(&signer), &clock);
+        let shared: Arc<dyn SignerSync + Send + Sync> = signer.clone();
+        let mut second = BatchStamper::with_clock(issuer(), shared, &clock);
+        let mut borrowed = BatchStamper::with_clock(issuer(), &*signer, &clock);
+
+        let address = ChunkAddress::new([0xAB; 32]);
+        let stamp = first.stamp(&address).unwrap();
+        assert_eq!(second.stamp(&address).unwrap(), stamp);
+        assert_eq!(borrowed.stamp(&address).unwrap(), stamp);
+
+        let digest = StampDigest::new(
+            address,
+            BatchId::ZERO,
+            StampIndex::new(stamp.bucket(), 0),
+            7,
+        );
+        let owner = stamp
+            .signature()
+            .recover_address_from_msg(digest.to_prehash())
+            .unwrap();
+        assert_eq!(owner, signer.address());
+        assert_eq!(Arc::strong_count(&signer), 3);
+    }
+
     #[test]
     fn test_stamp_digest_prehash() {
         let address = ChunkAddress::new([0xAB; 32]);
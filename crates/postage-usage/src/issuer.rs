@@ -2,7 +2,7 @@
 //! owner-aware issuance path so a snapshot can back a `BatchStamper` directly.
 
 use alloy_primitives::Address;
-use nectar_postage::{BatchId, StampDigest, StampError};
+use nectar_postage::{BatchId, StampDigest, StampError, StampIndex, calculate_bucket};
 use nectar_postage_issuer::StampIssuer;
 use nectar_primitives::{ChunkAddress, Mainnet, SwarmSpec};
 
@@ -10,9 +10,14 @@ use crate::SnapshotFor;
 use crate::error::UsageError;
 
 /// Maps a usage table error onto a stamp issuer error.
+///
+/// Only an immutable batch's fill watermark reports a full bucket; a mutable
+/// ring wraps instead.
 const fn map_usage_error(err: UsageError) -> StampError {
     match err {
-        UsageError::BucketFull { bucket, capacity } => StampError::BucketFull { bucket, capacity },
+        UsageError::BucketFull { bucket, capacity } => {
+            StampError::ImmutableBatchFull { bucket, capacity }
+        }
         _ => StampError::InvalidIndex,
     }
 }
@@ -92,6 +97,20 @@ impl<S: SwarmSpec> StampIssuer for SnapshotIssuerFor<S> {
         ))
     }
 
+    fn peek_index(&self, address: &ChunkAddress) -> Option<StampIndex> {
+        // Mirror the owner's `Issuer`: a mutable ring skips the snapshot's own
+        // reserved slots and wraps, an immutable bucket stops at its bound.
+        let bucket = calculate_bucket(address, self.bucket_depth());
+        let reserved = self.snapshot.reserved_slots(&self.owner);
+        let position = self
+            .snapshot
+            .table_ref()
+            .counters()
+            .peek(bucket, |slot| reserved.contains(&(bucket, slot)))
+            .ok()?;
+        Some(StampIndex::new(bucket, position))
+    }
+
     fn batch_id(&self) -> BatchId {
         self.snapshot.table_ref().batch_id()
     }
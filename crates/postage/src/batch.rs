@@ -1,15 +1,16 @@
 //! Postage batch types.
 
-use core::{fmt, marker::PhantomData};
+use core::{fmt, marker::PhantomData, str::FromStr};
 
-use alloy_primitives::{Address, B256};
+use alloy_primitives::{Address, B256, hex::FromHexError};
 use derive_more::{AsRef, Display, From, Into};
 use nectar_primitives::{
-    ChunkAddress, Mainnet, SwarmSpec,
+    ChunkAddress, DEFAULT_BODY_SIZE, Mainnet, SwarmSpec,
     wire::{Cursor, FromCursor, ToWriter, Underrun, Writer},
 };
 
-use crate::{StampError, StampIndex, calculate_bucket};
+use crate::builder::{BuilderState, Initial, ReadyToBuild, WithBucketDepth, WithDepth};
+use crate::{BUCKET_DEPTH_MAX, Stamp, StampError, StampIndex, calculate_bucket};
 
 /// A 32-byte batch identifier.
 ///
@@ -55,6 +56,36 @@ impl BatchId {
     pub fn from_slice(slice: &[u8]) -> Self {
         Self(B256::from_slice(slice))
     }
+
+    /// Parse an id from 64 hex digits, with or without a `0x` prefix.
+    ///
+    /// ```
+    /// use nectar_postage::BatchId;
+    ///
+    /// let id = BatchId::from_hex("0x0101010101010101010101010101010101010101010101010101010101010101")?;
+    /// assert_eq!(id, BatchId::new([1; 32]));
+    /// assert!(BatchId::from_hex("0x0101").is_err());
+    /// # Ok::<(), alloy_primitives::hex::FromHexError>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// [`FromHexError::InvalidStringLength`] unless there are exactly 64
+    /// digits, [`FromHexError::InvalidHexCharacter`] on a non-hex digit.
+    #[inline]
+    pub fn from_hex(s: &str) -> Result<Self, FromHexError> {
+        B256::from_str(s).map(Self)
+    }
+}
+
+/// Parses the hex form accepted by [`BatchId::from_hex`], so an id can be
+/// taken straight from a CLI argument or config string.
+impl FromStr for BatchId {
+    type Err = FromHexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_hex(s)
+    }
 }
 
 /// Reads the id as its raw 32 bytes.
@@ -116,7 +147,7 @@ pub struct BucketDepth<S: SwarmSpec = Mainnet> {
 
 impl<S: SwarmSpec> BucketDepth<S> {
     /// Largest representable depth, the bit width of the bucket key.
-    pub const MAX: u8 = 32;
+    pub const MAX: u8 = BUCKET_DEPTH_MAX;
 
     /// Validates a raw depth against the spec floor and [`MAX`](Self::MAX).
     ///
@@ -331,6 +362,42 @@ impl<S: SwarmSpec> BatchParams<S> {
         }
     }
 
+    /// Picks a batch geometry for an upload of `expected` chunks whose
+    /// chance of overflowing a bucket stays at or below
+    /// `max_overflow_prob`.
+    ///
+    /// The bucket depth is the network floor,
+    /// [`SwarmSpec::MIN_BUCKET_DEPTH`], which is what bee issues with. The
+    /// depth is the shallowest one above it, as the contract requires, whose
+    /// [`overflow_probability`](crate::overflow_probability) is within the
+    /// bound, searched up to the deepest depth
+    /// [`validate_depth`](Self::validate_depth) accepts. The owner and amount
+    /// are left zero for the caller to fill in.
+    ///
+    /// # Errors
+    ///
+    /// [`StampError::InvalidBucketDepth`] when the network floor is above
+    /// [`BucketDepth::MAX`], [`StampError::OverflowBoundUnreachable`] when no
+    /// valid depth meets the bound (zero for a non-trivial upload, or NaN).
+    #[cfg(feature = "std")]
+    pub fn for_chunk_count(expected: u64, max_overflow_prob: f64) -> Result<Self, StampError> {
+        let bucket_depth = BucketDepth::<S>::new(S::MIN_BUCKET_DEPTH.get())?;
+        // `validate_depth` refuses a depth `BucketDepth::MAX` or more above
+        // the bucket depth.
+        let deepest = bucket_depth
+            .get()
+            .saturating_add(BucketDepth::<S>::MAX.saturating_sub(1));
+        let depth = (bucket_depth.get().saturating_add(1)..=deepest)
+            .find(|&depth| {
+                crate::overflow_probability(expected, depth, bucket_depth.get())
+                    <= max_overflow_prob
+            })
+            .ok_or(StampError::OverflowBoundUnreachable {
+                expected_chunks: expected,
+            })?;
+        Ok(Self::new(Address::ZERO, depth, bucket_depth, 0))
+    }
+
     /// Sets the immutable flag.
     #[must_use]
     pub const fn immutable(mut self, immutable: bool) -> Self {
@@ -347,23 +414,32 @@ impl<S: SwarmSpec> BatchParams<S> {
     /// # Errors
     ///
     /// [`StampError::DepthBelowBucketDepth`] when `depth` is under the bucket
-    /// depth.
+    /// depth, [`StampError::InvalidDepth`] when it is 32 or more above it.
     #[inline]
     pub const fn validate_depth(&self) -> Result<(), StampError> {
         validate_depth(self.depth, self.bucket_depth)
     }
 }
 
-/// Validates that a batch depth leaves room above its bucket depth.
+/// Validates that a batch depth leaves room above its bucket depth, and not
+/// so much that a bucket's slot count overflows a `u32`.
 const fn validate_depth<S: SwarmSpec>(
     depth: u8,
     bucket_depth: BucketDepth<S>,
 ) -> Result<(), StampError> {
-    if depth < bucket_depth.get() {
+    let Some(slots) = depth.checked_sub(bucket_depth.get()) else {
         return Err(StampError::DepthBelowBucketDepth {
             depth,
             bucket_depth: bucket_depth.get(),
         });
+    };
+    // `BucketDepth::MAX` is the bit width of a slot index, as in
+    // `Batch::bucket_upper_bound`.
+    if slots >= BucketDepth::<S>::MAX {
+        return Err(StampError::InvalidDepth {
+            depth,
+            bucket_depth: bucket_depth.get(),
+        });
     }
     Ok(())
 }
@@ -428,8 +504,34 @@ impl<S: SwarmSpec> PartialEq for Batch<S> {
 
 impl<S: SwarmSpec> Eq for Batch<S> {}
 
+/// A one-line summary for logs, with the capacity spelled out; [`Debug`]
+/// keeps the raw fields.
+impl<S: SwarmSpec> fmt::Display for Batch<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Batch[{}] owner {}, depth {} ({} chunks), bucket depth {} ({} per bucket), {}",
+            self.id,
+            self.owner,
+            self.depth,
+            self.total_chunks(),
+            self.bucket_depth,
+            self.bucket_upper_bound(),
+            if self.immutable {
+                "immutable"
+            } else {
+                "mutable"
+            },
+        )
+    }
+}
+
 impl<S: SwarmSpec> Batch<S> {
-    /// Creates a new batch with the given parameters.
+    /// Creates a new batch with the given parameters, without checking the
+    /// depth against the bucket depth.
+    ///
+    /// Prefer [`try_new`](Self::try_new) for parameters that did not come
+    /// from an already validated source.
     #[inline]
     pub const fn new(
         id: BatchId,
@@ -451,6 +553,95 @@ impl<S: SwarmSpec> Batch<S> {
         }
     }
 
+    /// Creates a new batch, rejecting a depth the bucket depth cannot carry.
+    ///
+    /// The bucket depth is range-checked by [`BucketDepth::new`]; this checks
+    /// the batch depth against it, as [`validate_depth`](Self::validate_depth)
+    /// does.
+    ///
+    /// ```
+    /// use alloy_primitives::Address;
+    /// use nectar_postage::{Batch, BatchId, BucketDepth, StampError};
+    ///
+    /// let bucket_depth = BucketDepth::new(16).unwrap();
+    /// let batch: Result<Batch, _> =
+    ///     Batch::try_new(BatchId::ZERO, 0, 0, Address::ZERO, 8, bucket_depth, false);
+    /// assert!(matches!(batch, Err(StampError::DepthBelowBucketDepth { .. })));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// [`StampError::DepthBelowBucketDepth`] when `depth` is under the bucket
+    /// depth, [`StampError::InvalidDepth`] when it is 32 or more above it.
+    #[inline]
+    pub const fn try_new(
+        id: BatchId,
+        value: u128,
+        start: u64,
+        owner: Address,
+        depth: u8,
+        bucket_depth: BucketDepth<S>,
+        immutable: bool,
+    ) -> Result<Self, StampError> {
+        match validate_depth(depth, bucket_depth) {
+            Ok(()) => Ok(Self::new(
+                id,
+                value,
+                start,
+                owner,
+                depth,
+                bucket_depth,
+                immutable,
+            )),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Creates a batch from the postage stamp contract's `batches(id)`
+    /// getter, arguments in the order of its return tuple.
+    ///
+    /// The getter does not expose the creation block, so the batch starts at
+    /// `last_updated_block`: confirmation checks such as
+    /// [`is_usable`](Self::is_usable) then count from the latest top-up or
+    /// dilution, never earlier than the true creation.
+    ///
+    /// ```
+    /// use alloy_primitives::Address;
+    /// use nectar_postage::{Batch, BatchId, BucketDepth};
+    ///
+    /// // (owner, depth, bucketDepth, immutableFlag, normalisedBalance, lastUpdatedBlockNumber)
+    /// let batch: Batch = Batch::from_chain(
+    ///     BatchId::ZERO,
+    ///     Address::repeat_byte(7),
+    ///     20,
+    ///     BucketDepth::new(16).unwrap(),
+    ///     true,
+    ///     1 << 20,
+    ///     38_000_000,
+    /// );
+    /// assert_eq!(batch.start(), 38_000_000);
+    /// ```
+    #[inline]
+    pub const fn from_chain(
+        id: BatchId,
+        owner: Address,
+        depth: u8,
+        bucket_depth: BucketDepth<S>,
+        immutable: bool,
+        normalised_balance: u128,
+        last_updated_block: u64,
+    ) -> Self {
+        Self::new(
+            id,
+            normalised_balance,
+            last_updated_block,
+            owner,
+            depth,
+            bucket_depth,
+            immutable,
+        )
+    }
+
     /// Returns the batch ID.
     #[inline]
     pub const fn id(&self) -> BatchId {
@@ -522,6 +713,63 @@ impl<S: SwarmSpec> Batch<S> {
         self.bucket_depth.bucket_count()
     }
 
+    /// Returns the number of chunks the batch can stamp, `2^depth`.
+    ///
+    /// Saturates at [`u64::MAX`] for a depth of 64 or more.
+    #[inline]
+    pub const fn total_chunks(&self) -> u64 {
+        // A shift of the full bit width or more has no `u64` to land in.
+        if self.depth >= 64 {
+            return u64::MAX;
+        }
+        1u64 << self.depth
+    }
+
+    /// Returns how many chunks the batch can be expected to stamp before a
+    /// bucket fills: the largest count whose
+    /// [`overflow_probability`](crate::overflow_probability) stays at or below
+    /// `max_overflow_prob`.
+    ///
+    /// Chunks land in buckets by address, so the fullest bucket refuses a
+    /// stamp well before [`total_chunks`](Self::total_chunks) are used; this
+    /// is that collision-limited figure. A bound of 1.0 admits the whole
+    /// batch, and a negative or NaN bound admits nothing.
+    #[cfg(feature = "std")]
+    pub fn effective_capacity(&self, max_overflow_prob: f64) -> u64 {
+        let within = |chunks| {
+            crate::overflow_probability(chunks, self.depth, self.bucket_depth.get())
+                <= max_overflow_prob
+        };
+        if !within(0) {
+            return 0;
+        }
+        // The estimate rises with the load: `within` holds at `low` and fails
+        // past `high`.
+        let (mut low, mut high) = (0, self.total_chunks());
+        while low < high {
+            let mid = high.saturating_sub(high.saturating_sub(low) >> 1);
+            if within(mid) {
+                low = mid;
+            } else {
+                high = mid.saturating_sub(1);
+            }
+        }
+        low
+    }
+
+    /// Returns the payload the batch can stamp in bytes,
+    /// `total_chunks * 4096`, before any bucket-collision overhead.
+    ///
+    /// Saturates at [`u64::MAX`].
+    #[inline]
+    pub const fn theoretical_size_bytes(&self) -> u64 {
+        // The 4096-byte body widens losslessly (`u64::from` is not
+        // const-callable for usize).
+        #[allow(clippy::as_conversions)]
+        const CHUNK_BYTES: u64 = DEFAULT_BODY_SIZE as u64;
+        self.total_chunks().saturating_mul(CHUNK_BYTES)
+    }
+
     /// Updates the batch value (for top-up operations).
     #[inline]
     pub const fn set_value(&mut self, value: u128) {
@@ -561,7 +809,7 @@ impl<S: SwarmSpec> Batch<S> {
     /// # Errors
     ///
     /// [`StampError::DepthBelowBucketDepth`] when `depth` is under the bucket
-    /// depth.
+    /// depth, [`StampError::InvalidDepth`] when it is 32 or more above it.
     #[inline]
     pub const fn validate_depth(&self) -> Result<(), StampError> {
         validate_depth(self.depth, self.bucket_depth)
@@ -595,7 +843,7 @@ impl<S: SwarmSpec> Batch<S> {
     /// The bucket is determined by taking the first `bucket_depth` bits of the
     /// chunk address, interpreted as a big-endian unsigned integer.
     #[inline]
-    pub fn bucket_for_address(&self, address: &ChunkAddress) -> u32 {
+    pub const fn bucket_for_address(&self, address: &ChunkAddress) -> u32 {
         calculate_bucket(address, self.bucket_depth.get())
     }
 
@@ -604,7 +852,7 @@ impl<S: SwarmSpec> Batch<S> {
     /// # Returns
     ///
     /// `Ok(())` if the bucket matches, or `Err(StampError::BucketMismatch)` otherwise.
-    pub fn validate_bucket(
+    pub const fn validate_bucket(
         &self,
         index: &StampIndex,
         address: &ChunkAddress,
@@ -615,18 +863,217 @@ impl<S: SwarmSpec> Batch<S> {
         }
         Ok(())
     }
+
+    /// Fully checks `stamp` for the chunk at `address` against this batch.
+    ///
+    /// In order, cheapest first: the stamp names this batch, its index is
+    /// within the batch ([`validate_index`](Self::validate_index)), its
+    /// bucket is the one `address` falls in
+    /// ([`validate_bucket`](Self::validate_bucket)), and the batch owner
+    /// signed it ([`Stamp::verify`]). Expiry and timestamp policy are left to
+    /// the caller, who knows the chain state.
+    ///
+    /// # Errors
+    ///
+    /// [`StampError::BatchMismatch`], [`StampError::InvalidIndex`],
+    /// [`StampError::BucketMismatch`], or the signature errors of
+    /// [`Stamp::verify`].
+    pub fn verify_stamp(&self, stamp: &Stamp, address: &ChunkAddress) -> Result<(), StampError> {
+        if stamp.batch() != self.id {
+            return Err(StampError::BatchMismatch {
+                expected: self.id,
+                actual: stamp.batch(),
+            });
+        }
+        self.validate_index(&stamp.stamp_index())?;
+        self.validate_bucket(&stamp.stamp_index(), address)?;
+        stamp.verify(address, self.owner)
+    }
+}
+
+// Batch Builder
+
+impl<S: SwarmSpec> Batch<S> {
+    /// Starts a [`BatchBuilder`] for the batch `id`.
+    ///
+    /// The balance, start block and owner default to zero and the batch to
+    /// mutable; the depth and bucket depth have no default and must both be
+    /// set before [`build`](BatchBuilder::build) is callable.
+    pub const fn builder(id: BatchId) -> BatchBuilder<S> {
+        BatchBuilder {
+            id,
+            value: 0,
+            start: 0,
+            owner: Address::ZERO,
+            immutable: false,
+            depth: None,
+            bucket_depth: None,
+            _spec: PhantomData,
+            _state: PhantomData,
+        }
+    }
+}
+
+/// Named-field construction of a [`Batch`], an alternative to the positional
+/// [`Batch::new`] where a depth and bucket depth are easily swapped.
+///
+/// The [`BuilderState`] tracks the geometry: the depth and bucket depth are
+/// set in either order, and only once both are does [`build`](Self::build)
+/// exist.
+///
+/// ```
+/// use nectar_postage::{Batch, BatchId, BucketDepth};
+///
+/// let batch: Batch = Batch::builder(BatchId::ZERO)
+///     .depth(20)
+///     .bucket_depth(BucketDepth::new(16).unwrap())
+///     .normalised_balance(1_000)
+///     .build()
+///     .unwrap();
+/// assert_eq!((batch.depth(), batch.bucket_depth().get()), (20, 16));
+/// ```
+///
+/// ```compile_fail
+/// use nectar_postage::{Batch, BatchId};
+///
+/// // No bucket depth: there is no `build` to call.
+/// let batch: Batch = Batch::builder(BatchId::ZERO).depth(20).build().unwrap();
+/// ```
+#[derive(Debug)]
+#[must_use = "a builder does nothing until built"]
+pub struct BatchBuilder<S: SwarmSpec = Mainnet, St: BuilderState = Initial> {
+    id: BatchId,
+    value: u128,
+    start: u64,
+    owner: Address,
+    immutable: bool,
+    depth: Option<u8>,
+    bucket_depth: Option<BucketDepth<S>>,
+    _spec: PhantomData<fn() -> S>,
+    _state: PhantomData<St>,
+}
+
+impl<S: SwarmSpec, St: BuilderState> BatchBuilder<S, St> {
+    /// Sets the owner's Ethereum address.
+    pub const fn owner(mut self, owner: Address) -> Self {
+        self.owner = owner;
+        self
+    }
+
+    /// Sets the normalised balance (value per chunk).
+    pub const fn normalised_balance(mut self, value: u128) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Sets the block the batch was created at.
+    pub const fn start(mut self, start: u64) -> Self {
+        self.start = start;
+        self
+    }
+
+    /// Sets the immutable flag.
+    pub const fn immutable(mut self, immutable: bool) -> Self {
+        self.immutable = immutable;
+        self
+    }
+
+    /// Moves the builder to the state `T`.
+    fn into_state<T: BuilderState>(self) -> BatchBuilder<S, T> {
+        BatchBuilder {
+            id: self.id,
+            value: self.value,
+            start: self.start,
+            owner: self.owner,
+            immutable: self.immutable,
+            depth: self.depth,
+            bucket_depth: self.bucket_depth,
+            _spec: PhantomData,
+            _state: PhantomData,
+        }
+    }
+}
+
+impl<S: SwarmSpec> BatchBuilder<S, Initial> {
+    /// Sets the batch depth (capacity of `2^depth` chunks).
+    pub fn depth(mut self, depth: u8) -> BatchBuilder<S, WithDepth> {
+        self.depth = Some(depth);
+        self.into_state()
+    }
+
+    /// Sets the collision bucket depth.
+    pub fn bucket_depth(
+        mut self,
+        bucket_depth: BucketDepth<S>,
+    ) -> BatchBuilder<S, WithBucketDepth> {
+        self.bucket_depth = Some(bucket_depth);
+        self.into_state()
+    }
+}
+
+impl<S: SwarmSpec> BatchBuilder<S, WithDepth> {
+    /// Sets the collision bucket depth.
+    pub fn bucket_depth(mut self, bucket_depth: BucketDepth<S>) -> BatchBuilder<S, ReadyToBuild> {
+        self.bucket_depth = Some(bucket_depth);
+        self.into_state()
+    }
+}
+
+impl<S: SwarmSpec> BatchBuilder<S, WithBucketDepth> {
+    /// Sets the batch depth (capacity of `2^depth` chunks).
+    pub fn depth(mut self, depth: u8) -> BatchBuilder<S, ReadyToBuild> {
+        self.depth = Some(depth);
+        self.into_state()
+    }
+}
+
+impl<S: SwarmSpec> BatchBuilder<S, ReadyToBuild> {
+    /// Builds the batch.
+    ///
+    /// # Errors
+    ///
+    /// [`StampError::DepthBelowBucketDepth`] when the depth is under the
+    /// bucket depth, [`StampError::InvalidDepth`] when it is 32 or more above
+    /// it.
+    pub fn build(self) -> Result<Batch<S>, StampError> {
+        // The ReadyToBuild typestate guarantees both are set.
+        let (Some(depth), Some(bucket_depth)) = (self.depth, self.bucket_depth) else {
+            return Err(StampError::InvalidData("batch geometry unset"));
+        };
+        validate_depth(depth, bucket_depth)?;
+        Ok(Batch::new(
+            self.id,
+            self.value,
+            self.start,
+            self.owner,
+            depth,
+            bucket_depth,
+            self.immutable,
+        ))
+    }
 }
 
 // Arbitrary implementations for property-based testing
 
+/// Batch depths bee buys and serves; generated batches stay inside them.
+#[cfg(any(test, feature = "arbitrary"))]
+const ARBITRARY_DEPTHS: core::ops::RangeInclusive<u8> = 16..=40;
+
 /// Draws a bucket depth the network accepts, then a batch depth at or above
-/// it, so the generated geometry satisfies both bounds.
+/// it within [`ARBITRARY_DEPTHS`], so the generated geometry satisfies both
+/// bounds.
 #[cfg(any(test, feature = "arbitrary"))]
 fn arbitrary_geometry<S: SwarmSpec>(
     u: &mut arbitrary::Unstructured<'_>,
 ) -> arbitrary::Result<(u8, BucketDepth<S>)> {
     let bucket_depth = <BucketDepth<S> as arbitrary::Arbitrary>::arbitrary(u)?;
-    let depth = u.int_in_range(bucket_depth.get()..=BucketDepth::<S>::MAX)?;
+    let floor = bucket_depth.get().max(*ARBITRARY_DEPTHS.start());
+    // Stay within the `u32` slot index of `validate_depth`.
+    let ceiling = bucket_depth
+        .get()
+        .saturating_add(BucketDepth::<S>::MAX - 1)
+        .min(*ARBITRARY_DEPTHS.end());
+    let depth = u.int_in_range(floor..=ceiling)?;
     Ok((depth, bucket_depth))
 }
 
@@ -665,9 +1112,236 @@ impl<'a, S: SwarmSpec> arbitrary::Arbitrary<'a> for Batch<S> {
 #[cfg(test)]
 mod tests {
     use nectar_testing::{HighFloor, LowFloor};
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
 
     use super::*;
 
+    #[test]
+    fn batch_id_parses_hex_with_or_without_prefix() {
+        let digits = "ab".repeat(32);
+        let id = BatchId::new([0xab; 32]);
+        assert_eq!(BatchId::from_hex(&digits), Ok(id));
+        assert_eq!(format!("0x{digits}").parse::<BatchId>(), Ok(id));
+        assert_eq!(id.to_string().parse::<BatchId>(), Ok(id));
+
+        assert_eq!(
+            BatchId::from_hex(&digits[2..]),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(
+            BatchId::from_hex(&format!("{digits}ab")),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert!(matches!(
+            BatchId::from_hex(&format!("zz{}", &digits[2..])),
+            Err(FromHexError::InvalidHexCharacter { .. })
+        ));
+    }
+
+    #[test]
+    fn for_chunk_count_picks_the_shallowest_safe_depth() {
+        for (expected, bound) in [(1u64, 0.0), (10_000, 0.01), (1 << 20, 1e-6), (1 << 30, 0.5)] {
+            let params: BatchParams = BatchParams::for_chunk_count(expected, bound).unwrap();
+            assert_eq!(params.bucket_depth.get(), 16);
+            assert!(params.depth > 16);
+            assert!(crate::overflow_probability(expected, params.depth, 16) <= bound);
+            if params.depth > 17 {
+                assert!(crate::overflow_probability(expected, params.depth - 1, 16) > bound);
+            }
+        }
+        // A single chunk fits the shallowest batch the contract accepts.
+        assert_eq!(
+            BatchParams::<Mainnet>::for_chunk_count(1, 0.0)
+                .unwrap()
+                .depth,
+            17
+        );
+        assert_eq!(
+            BatchParams::<Mainnet>::for_chunk_count(1 << 20, f64::NAN),
+            Err(StampError::OverflowBoundUnreachable {
+                expected_chunks: 1 << 20
+            })
+        );
+    }
+
+    #[test]
+    fn for_chunk_count_stays_within_validate_depth() {
+        // The deepest batch the crate accepts holds 2^47 chunks at bucket
+        // depth 16; half of that already fills every shallower batch.
+        let ceiling = BatchParams::<Mainnet>::for_chunk_count(1 << 46, 1e-6).unwrap();
+        assert_eq!(ceiling.depth, 16 + BucketDepth::<Mainnet>::MAX - 1);
+        assert_eq!(ceiling.validate_depth(), Ok(()));
+
+        // One chunk more overflows every valid depth, so there is no answer
+        // rather than a depth `validate_depth` refuses.
+        assert_eq!(
+            BatchParams::<Mainnet>::for_chunk_count((1 << 47) + 1, 0.5),
+            Err(StampError::OverflowBoundUnreachable {
+                expected_chunks: (1 << 47) + 1
+            })
+        );
+    }
+
+    #[test]
+    fn verify_stamp_checks_batch_index_bucket_and_owner() {
+        use crate::test_utils::sign_stamp;
+        use alloy_signer_local::PrivateKeySigner;
+
+        let signer = PrivateKeySigner::random();
+        let batch: Batch = Batch::new(
+            BatchId::new([1; 32]),
+            0,
+            0,
+            signer.address(),
+            18,
+            BucketDepth::new(16).unwrap(),
+            true,
+        );
+        let address = ChunkAddress::new([0xAB; 32]);
+        let bucket = batch.bucket_for_address(&address);
+        let valid = sign_stamp(&signer, batch.id(), address, StampIndex::new(bucket, 3), 42);
+        let other_batch = sign_stamp(
+            &signer,
+            BatchId::ZERO,
+            address,
+            StampIndex::new(bucket, 3),
+            42,
+        );
+        let past_depth = sign_stamp(&signer, batch.id(), address, StampIndex::new(bucket, 4), 42);
+        let other_bucket = sign_stamp(
+            &signer,
+            batch.id(),
+            address,
+            StampIndex::new(bucket ^ 1, 0),
+            42,
+        );
+
+        assert_eq!(batch.verify_stamp(&valid, &address), Ok(()));
+        assert_eq!(
+            batch.verify_stamp(&other_batch, &address),
+            Err(StampError::BatchMismatch {
+                expected: batch.id(),
+                actual: BatchId::ZERO,
+            })
+        );
+        assert_eq!(
+            batch.verify_stamp(&past_depth, &address),
+            Err(StampError::InvalidIndex)
+        );
+        assert_eq!(
+            batch.verify_stamp(&other_bucket, &address),
+            Err(StampError::BucketMismatch)
+        );
+
+        let foreign: Batch = Batch::new(
+            batch.id(),
+            0,
+            0,
+            Address::repeat_byte(9),
+            18,
+            BucketDepth::new(16).unwrap(),
+            true,
+        );
+        assert!(matches!(
+            foreign.verify_stamp(&valid, &address),
+            Err(StampError::OwnerMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn builder_matches_new_in_either_setter_order() {
+        let owner = Address::repeat_byte(3);
+        let bucket_depth = BucketDepth::new(16).unwrap();
+        let expected: Batch = Batch::new(BatchId::ZERO, 7, 9, owner, 20, bucket_depth, true);
+
+        let depth_first = Batch::builder(BatchId::ZERO)
+            .depth(20)
+            .bucket_depth(bucket_depth)
+            .owner(owner)
+            .normalised_balance(7)
+            .start(9)
+            .immutable(true)
+            .build()
+            .unwrap();
+        let bucket_depth_first = Batch::builder(BatchId::ZERO)
+            .immutable(true)
+            .start(9)
+            .normalised_balance(7)
+            .owner(owner)
+            .bucket_depth(bucket_depth)
+            .depth(20)
+            .build()
+            .unwrap();
+        assert_eq!(depth_first, expected);
+        assert_eq!(bucket_depth_first, expected);
+    }
+
+    #[test]
+    fn builder_rejects_a_bucket_depth_above_the_depth() {
+        let result: Result<Batch, _> = Batch::builder(BatchId::ZERO)
+            .depth(16)
+            .bucket_depth(BucketDepth::new(20).unwrap())
+            .build();
+        assert_eq!(
+            result,
+            Err(StampError::DepthBelowBucketDepth {
+                depth: 16,
+                bucket_depth: 20
+            })
+        );
+    }
+
+    #[test]
+    fn try_new_rejects_an_invalid_depth() {
+        let bucket_depth = BucketDepth::new(16).unwrap();
+        let try_new = |depth| -> Result<Batch, StampError> {
+            Batch::try_new(
+                BatchId::ZERO,
+                0,
+                0,
+                Address::ZERO,
+                depth,
+                bucket_depth,
+                false,
+            )
+        };
+
+        assert_eq!(
+            try_new(20),
+            Ok(Batch::new(
+                BatchId::ZERO,
+                0,
+                0,
+                Address::ZERO,
+                20,
+                bucket_depth,
+                false
+            ))
+        );
+        // Equal depths hold one slot per bucket; 31 above is the widest a `u32`
+        // slot index covers.
+        assert!(try_new(16).is_ok());
+        assert_eq!(try_new(47).unwrap().bucket_upper_bound(), 1 << 31);
+
+        assert_eq!(
+            try_new(15),
+            Err(StampError::DepthBelowBucketDepth {
+                depth: 15,
+                bucket_depth: 16
+            })
+        );
+        for depth in [48, u8::MAX] {
+            assert_eq!(
+                try_new(depth),
+                Err(StampError::InvalidDepth {
+                    depth,
+                    bucket_depth: 16
+                })
+            );
+        }
+    }
+
     #[test]
     fn batch_id_roundtrips_via_from_impls() {
         let bytes = [7u8; 32];
@@ -810,6 +1484,93 @@ mod tests {
         assert_eq!(max.bucket_upper_bound(), u32::MAX);
     }
 
+    #[test]
+    fn display_spells_out_the_capacity() {
+        let batch: Batch = Batch::new(
+            BatchId::new([0x11; 32]),
+            0,
+            0,
+            Address::repeat_byte(0x22),
+            20,
+            BucketDepth::new(16).unwrap(),
+            true,
+        );
+        assert_eq!(
+            batch.to_string(),
+            format!(
+                "Batch[0x{}] owner {}, depth 20 (1048576 chunks), bucket depth 16 (16 per bucket), immutable",
+                "11".repeat(32),
+                Address::repeat_byte(0x22),
+            )
+        );
+
+        let mutable: Batch = Batch::new(
+            BatchId::ZERO,
+            0,
+            0,
+            Address::ZERO,
+            17,
+            BucketDepth::new(16).unwrap(),
+            false,
+        );
+        assert!(mutable.to_string().ends_with("(2 per bucket), mutable"));
+    }
+
+    #[test]
+    fn capacity_helpers_follow_the_depth() {
+        let batch = |depth| -> Batch {
+            Batch::new(
+                BatchId::ZERO,
+                0,
+                0,
+                Address::ZERO,
+                depth,
+                BucketDepth::new(16).unwrap(),
+                false,
+            )
+        };
+
+        // Depth 20 is bee's smallest purchasable batch: 2^20 chunks, 4 GiB.
+        let small = batch(20);
+        assert_eq!(small.total_chunks(), 1 << 20);
+        assert_eq!(small.theoretical_size_bytes(), 4 << 30);
+
+        // Depths past the u64 width saturate instead of overflowing.
+        let huge = batch(u8::MAX);
+        assert_eq!(huge.total_chunks(), u64::MAX);
+        assert_eq!(huge.theoretical_size_bytes(), u64::MAX);
+        assert_eq!(batch(63).total_chunks(), 1 << 63);
+        assert_eq!(batch(64).total_chunks(), u64::MAX);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn effective_capacity_is_the_collision_limited_count() {
+        let batch: Batch = Batch::new(
+            BatchId::ZERO,
+            0,
+            0,
+            Address::ZERO,
+            20,
+            BucketDepth::new(16).unwrap(),
+            false,
+        );
+        let overflow = |chunks| crate::overflow_probability(chunks, 20, 16);
+
+        // The largest count within the bound: one more chunk exceeds it.
+        let capacity = batch.effective_capacity(0.5);
+        assert!(0 < capacity && capacity < batch.total_chunks());
+        assert!(overflow(capacity) <= 0.5);
+        assert!(overflow(capacity + 1) > 0.5);
+        assert!(batch.effective_capacity(1e-6) < capacity);
+
+        // No overflow is certain only while one bucket could hold everything.
+        assert_eq!(batch.effective_capacity(0.0), 16);
+        assert_eq!(batch.effective_capacity(1.0), batch.total_chunks());
+        assert_eq!(batch.effective_capacity(-1.0), 0);
+        assert_eq!(batch.effective_capacity(f64::NAN), 0);
+    }
+
     #[test]
     fn bucket_upper_bound_holds_for_a_batch_shallower_than_its_buckets() {
         let batch: Batch = Batch::new(
@@ -934,4 +1695,27 @@ mod tests {
         assert_eq!(params.amount, 1000);
         assert!(params.immutable);
     }
+
+    proptest! {
+        /// Generated batches and params are structurally sound at bee's
+        /// depths, whatever the spec floor.
+        #[test]
+        fn arbitrary_geometry_is_sound(
+            mainnet in arb::<Batch>(),
+            low in arb::<Batch<LowFloor>>(),
+            high in arb::<BatchParams<HighFloor>>(),
+        ) {
+            for (depth, bucket_depth) in [
+                (mainnet.depth(), mainnet.bucket_depth().get()),
+                (low.depth(), low.bucket_depth().get()),
+                (high.depth, high.bucket_depth.get()),
+            ] {
+                prop_assert!(ARBITRARY_DEPTHS.contains(&depth));
+                prop_assert!(bucket_depth <= depth);
+            }
+            prop_assert!(mainnet.validate_depth().is_ok());
+            prop_assert!(low.validate_depth().is_ok());
+            prop_assert!(high.validate_depth().is_ok());
+        }
+    }
 }
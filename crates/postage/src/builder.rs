@@ -0,0 +1,27 @@
+//! Type-state markers for [`BatchBuilder`](crate::BatchBuilder).
+//!
+//! The state records which of the depth and bucket depth have been set;
+//! [`build`](crate::BatchBuilder::build) exists only in [`ReadyToBuild`].
+
+/// Builder state marker trait.
+pub trait BuilderState {}
+
+/// Neither the depth nor the bucket depth is set.
+#[derive(Debug, Default)]
+pub struct Initial;
+impl BuilderState for Initial {}
+
+/// The depth is set, the bucket depth is not.
+#[derive(Debug)]
+pub struct WithDepth;
+impl BuilderState for WithDepth {}
+
+/// The bucket depth is set, the depth is not.
+#[derive(Debug)]
+pub struct WithBucketDepth;
+impl BuilderState for WithBucketDepth {}
+
+/// Both the depth and the bucket depth are set.
+#[derive(Debug)]
+pub struct ReadyToBuild;
+impl BuilderState for ReadyToBuild {}
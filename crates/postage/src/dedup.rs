@@ -0,0 +1,185 @@
+//! Double-stamp detection across the stamps a node has accepted.
+//!
+//! A stamp's `(batch, bucket, index)` slot names one chunk at a time. An
+//! uploader reusing a slot for a second chunk without advancing the stamp
+//! timestamp is claiming two chunks for the price of one; a storing node
+//! must refuse the second. A mutable batch may legitimately re-issue a slot
+//! with a later timestamp, which overwrites the earlier chunk, so only a
+//! reuse whose timestamp does not advance is a double stamp.
+
+use nectar_primitives::ChunkAddress;
+use nectar_primitives::cache::FifoCache;
+
+use crate::{BatchId, Stamp, StampError, StampIndex};
+
+/// The chunk currently holding a slot and the timestamp it was stamped at.
+#[derive(Debug, Clone, Copy)]
+struct Holder {
+    chunk: ChunkAddress,
+    timestamp: u64,
+}
+
+/// Tracks which chunk holds each `(batch, stamp index)` slot.
+///
+/// [`new`](Self::new) remembers every slot it records. A
+/// [`bounded`](Self::bounded) tracker keeps its slots in a [`FifoCache`],
+/// trading detection of old reuses for a fixed memory ceiling.
+///
+/// ```
+/// use nectar_postage::{BatchId, Stamp, StampError, StampIndex};
+/// use nectar_postage::dedup::StampDedup;
+/// # use alloy_primitives::Signature;
+/// use nectar_primitives::ChunkAddress;
+///
+/// let index = StampIndex::new(3, 7);
+/// # let sig = Signature::test_signature();
+/// let stamp = Stamp::with_index(BatchId::ZERO, index, 100, sig);
+///
+/// let mut dedup = StampDedup::new();
+/// dedup.record(&stamp, ChunkAddress::new([1; 32])).unwrap();
+/// // Re-recording the same chunk is idempotent.
+/// dedup.record(&stamp, ChunkAddress::new([1; 32])).unwrap();
+/// assert!(matches!(
+///     dedup.record(&stamp, ChunkAddress::new([2; 32])),
+///     Err(StampError::DoubleStamp { .. })
+/// ));
+/// ```
+#[derive(Debug, Clone)]
+pub struct StampDedup {
+    slots: FifoCache<(BatchId, StampIndex), Holder>,
+}
+
+impl Default for StampDedup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StampDedup {
+    /// Creates a tracker that remembers every slot.
+    pub fn new() -> Self {
+        Self {
+            slots: FifoCache::unbounded(),
+        }
+    }
+
+    /// Creates a tracker remembering at most `capacity` slots.
+    pub fn bounded(capacity: usize) -> Self {
+        Self {
+            slots: FifoCache::new(capacity),
+        }
+    }
+
+    /// Number of slots currently remembered.
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Whether no slot is remembered.
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Records that `stamp` was accepted for `chunk`.
+    ///
+    /// Recording the slot's current chunk again is a no-op, and a later
+    /// timestamp hands the slot to the new chunk.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StampError::DoubleStamp`] when the slot is held by another
+    /// chunk at the same or a later timestamp; the slot is left unchanged.
+    pub fn record(&mut self, stamp: &Stamp, chunk: ChunkAddress) -> Result<(), StampError> {
+        let key = (stamp.batch(), stamp.stamp_index());
+        let timestamp = stamp.timestamp();
+        if let Some(holder) = self.slots.get_mut(&key) {
+            if holder.chunk == chunk {
+                holder.timestamp = holder.timestamp.max(timestamp);
+                return Ok(());
+            }
+            if timestamp <= holder.timestamp {
+                return Err(StampError::DoubleStamp {
+                    batch: key.0,
+                    bucket: key.1.bucket(),
+                    index: key.1.index(),
+                    held_by: holder.chunk,
+                    chunk,
+                });
+            }
+            *holder = Holder { chunk, timestamp };
+            return Ok(());
+        }
+        self.slots.insert(key, Holder { chunk, timestamp });
+        Ok(())
+    }
+
+    /// Forgets every slot of `batch`, as when the batch expires.
+    pub fn forget_batch(&mut self, batch: BatchId) {
+        self.slots.retain(|(id, _), _| *id != batch);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Signature;
+
+    fn stamp(batch: u8, bucket: u32, index: u32, timestamp: u64) -> Stamp {
+        Stamp::with_index(
+            BatchId::new([batch; 32]),
+            StampIndex::new(bucket, index),
+            timestamp,
+            Signature::test_signature(),
+        )
+    }
+
+    fn chunk(byte: u8) -> ChunkAddress {
+        ChunkAddress::new([byte; 32])
+    }
+
+    #[test]
+    fn reusing_a_slot_for_another_chunk_is_a_double_stamp() {
+        let mut dedup = StampDedup::new();
+        dedup.record(&stamp(1, 4, 0, 10), chunk(0xaa)).unwrap();
+
+        for timestamp in [10, 9] {
+            assert_eq!(
+                dedup.record(&stamp(1, 4, 0, timestamp), chunk(0xbb)),
+                Err(StampError::DoubleStamp {
+                    batch: BatchId::new([1; 32]),
+                    bucket: 4,
+                    index: 0,
+                    held_by: chunk(0xaa),
+                    chunk: chunk(0xbb),
+                })
+            );
+        }
+
+        // Other slots, and the same slot of another batch, are independent.
+        dedup.record(&stamp(1, 4, 1, 10), chunk(0xbb)).unwrap();
+        dedup.record(&stamp(2, 4, 0, 10), chunk(0xbb)).unwrap();
+        assert_eq!(dedup.len(), 3);
+    }
+
+    #[test]
+    fn a_later_timestamp_overwrites_the_slot() {
+        let mut dedup = StampDedup::new();
+        dedup.record(&stamp(1, 0, 0, 10), chunk(0xaa)).unwrap();
+        dedup.record(&stamp(1, 0, 0, 11), chunk(0xbb)).unwrap();
+        // The overwritten chunk cannot reclaim the slot with its old stamp.
+        assert!(matches!(
+            dedup.record(&stamp(1, 0, 0, 10), chunk(0xaa)),
+            Err(StampError::DoubleStamp { held_by, .. }) if held_by == chunk(0xbb)
+        ));
+    }
+
+    #[test]
+    fn forgetting_a_batch_frees_its_slots() {
+        let mut dedup = StampDedup::bounded(4);
+        dedup.record(&stamp(1, 0, 0, 1), chunk(1)).unwrap();
+        dedup.record(&stamp(2, 0, 0, 1), chunk(1)).unwrap();
+        dedup.forget_batch(BatchId::new([1; 32]));
+        assert_eq!(dedup.len(), 1);
+        dedup.record(&stamp(1, 0, 0, 1), chunk(2)).unwrap();
+    }
+}
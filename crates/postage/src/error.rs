@@ -2,6 +2,7 @@
 
 use crate::BatchId;
 use alloy_primitives::Address;
+use nectar_primitives::ChunkAddress;
 use nectar_primitives::wire::Underrun;
 use thiserror::Error;
 
@@ -51,10 +52,38 @@ pub enum StampError {
         bucket_depth: u8,
     },
 
+    /// The batch depth is so far above the bucket depth that a bucket's slot
+    /// count no longer fits a `u32`.
+    #[error("batch depth {depth} too deep for bucket depth {bucket_depth}: at most 31 above it")]
+    InvalidDepth {
+        /// The rejected batch depth.
+        depth: u8,
+        /// The bucket depth it is measured against.
+        bucket_depth: u8,
+    },
+
+    /// No batch depth the crate accepts keeps the chance of an upload
+    /// overflowing a bucket within the requested bound.
+    #[error("no valid batch depth keeps {expected_chunks} chunks within the overflow bound")]
+    OverflowBoundUnreachable {
+        /// The chunk count the batch was sized for.
+        expected_chunks: u64,
+    },
+
     /// The batch was not found.
     #[error("batch not found: {0}")]
     BatchNotFound(BatchId),
 
+    /// The stamp was issued for a different batch than the one it is
+    /// checked against.
+    #[error("batch mismatch: expected {expected}, stamp is for {actual}")]
+    BatchMismatch {
+        /// The batch the stamp was checked against.
+        expected: BatchId,
+        /// The batch the stamp names.
+        actual: BatchId,
+    },
+
     /// The batch is not yet usable (needs more confirmations).
     #[error(
         "batch not usable: created at block {created}, current block {current}, need {threshold} confirmations"
@@ -77,6 +106,17 @@ pub enum StampError {
         total_amount: u128,
     },
 
+    /// The stamp timestamp falls outside the window the validator accepts.
+    #[error("timestamp {timestamp} outside the accepted window {min}..={max}")]
+    TimestampOutOfRange {
+        /// The stamp's timestamp, in nanoseconds since the Unix epoch.
+        timestamp: u64,
+        /// Earliest accepted timestamp.
+        min: u64,
+        /// Latest accepted timestamp.
+        max: u64,
+    },
+
     /// Invalid stamp data format.
     #[error("invalid stamp data: {0}")]
     InvalidData(&'static str),
@@ -90,6 +130,36 @@ pub enum StampError {
         capacity: u32,
     },
 
+    /// A bucket of an immutable batch is full.
+    ///
+    /// Immutable batches never reuse an index, so the bucket stays full until
+    /// the batch is diluted.
+    #[error("immutable batch full: bucket {bucket} has reached capacity {capacity}")]
+    ImmutableBatchFull {
+        /// The bucket that is full.
+        bucket: u32,
+        /// Maximum capacity of the bucket.
+        capacity: u32,
+    },
+
+    /// A stamp slot was reused for a second chunk without advancing the
+    /// stamp timestamp.
+    #[error(
+        "double stamp: batch {batch} bucket {bucket} index {index} holds {held_by}, not {chunk}"
+    )]
+    DoubleStamp {
+        /// The batch the slot belongs to.
+        batch: BatchId,
+        /// The slot's collision bucket.
+        bucket: u32,
+        /// The slot's position within the bucket.
+        index: u32,
+        /// The chunk already holding the slot.
+        held_by: ChunkAddress,
+        /// The chunk the reused stamp was presented with.
+        chunk: ChunkAddress,
+    },
+
     /// Signature verification failed.
     #[error("invalid signature")]
     InvalidSignature,
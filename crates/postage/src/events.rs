@@ -31,6 +31,9 @@ pub enum BatchEvent {
         batch_id: BatchId,
         /// The new depth.
         new_depth: u8,
+        /// The new normalized balance, lowered to spread the batch's funds
+        /// over the extra chunks.
+        new_value: u128,
     },
 
     /// A batch expired.
@@ -110,6 +113,7 @@ mod tests {
         let depth = BatchEvent::DepthIncrease {
             batch_id,
             new_depth: 21,
+            new_value: 500,
         };
         assert_eq!(depth.batch_id(), batch_id);
 
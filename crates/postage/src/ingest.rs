@@ -0,0 +1,421 @@
+//! The storing node's chunk ingestion path in one call.
+//!
+//! Every node accepting a chunk for storage runs the same sequence: parse the
+//! typed bytes, certify the claimed address, then check the postage stamp
+//! against its batch and the chain state. [`IngestPipeline::ingest`] chains
+//! the three and reports the first failure as an [`IngestError`], so the
+//! caller only ever stores a [`StoredChunk`] that passed all of them.
+//!
+//! The cheap structural and address checks run before signature recovery,
+//! so junk is rejected without touching the stamp.
+//!
+//! [`validate_stamped_chunk`] is the same contract for a chunk already
+//! parsed, of any registry: custom chunk types reach stamp validation
+//! through their [`ChunkRegistry`] with nothing else to implement.
+
+use core::marker::PhantomData;
+
+use nectar_primitives::{
+    Chunk, ChunkAddress, ChunkRegistry, PrimitivesError, StandardChunkSet, SwarmSpec, Unverified,
+    Verified,
+};
+use thiserror::Error;
+
+use crate::validation::check_live_stamp;
+use crate::{Batch, PostageContext, Stamp, StampError};
+
+/// Why [`IngestPipeline::ingest`] refused a chunk.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum IngestError {
+    /// The bytes are not a chunk the registry can parse.
+    #[error("malformed chunk: {0}")]
+    Malformed(#[source] PrimitivesError),
+
+    /// The chunk parsed but does not verify at the address it was sent for.
+    #[error("chunk does not verify at {address}: {source}")]
+    AddressMismatch {
+        /// The address the chunk was offered under.
+        address: ChunkAddress,
+        /// The verification failure.
+        #[source]
+        source: PrimitivesError,
+    },
+
+    /// The chunk is genuine but its stamp is not valid for the batch.
+    #[error(transparent)]
+    Stamp(#[from] StampError),
+}
+
+/// A chunk that passed [`IngestPipeline::ingest`], with the stamp that paid
+/// for it.
+#[derive(Debug, Clone)]
+pub struct StoredChunk<R: ChunkRegistry = StandardChunkSet> {
+    chunk: Chunk<Verified, R>,
+    stamp: Stamp,
+}
+
+impl<R: ChunkRegistry> StoredChunk<R> {
+    /// The verified chunk.
+    pub const fn chunk(&self) -> &Chunk<Verified, R> {
+        &self.chunk
+    }
+
+    /// The stamp that was validated for it.
+    pub const fn stamp(&self) -> &Stamp {
+        &self.stamp
+    }
+
+    /// The chunk's verified address.
+    pub const fn address(&self) -> &ChunkAddress {
+        self.chunk.address()
+    }
+
+    /// Consume into the verified chunk and its stamp.
+    pub fn into_parts(self) -> (Chunk<Verified, R>, Stamp) {
+        (self.chunk, self.stamp)
+    }
+}
+
+/// Parses, verifies and stamp-checks incoming chunks against a fixed chain
+/// state.
+///
+/// The pipeline holds only the [`PostageContext`] it judges batch expiry
+/// by; update it with [`set_state`](Self::set_state) as blocks arrive.
+///
+/// ```
+/// use nectar_postage::PostageContext;
+/// use nectar_postage::ingest::{IngestError, IngestPipeline};
+/// # use alloy_primitives::{Address, Signature};
+/// # use nectar_postage::{Batch, BatchId, BucketDepth, Stamp};
+/// # use nectar_primitives::ChunkAddress;
+/// # let batch: Batch = Batch::new(BatchId::ZERO, 1_000, 0, Address::ZERO, 18, BucketDepth::new(16).unwrap(), false);
+/// # let stamp = Stamp::new(BatchId::ZERO, 0, 0, 0, Signature::test_signature());
+///
+/// let pipeline: IngestPipeline = IngestPipeline::new(PostageContext::new(100, 0));
+/// let result = pipeline.ingest(&[0xff; 3], stamp, ChunkAddress::ZERO, &batch);
+/// assert!(matches!(result, Err(IngestError::Malformed(_))));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct IngestPipeline<R: ChunkRegistry = StandardChunkSet> {
+    state: PostageContext,
+    _registry: PhantomData<fn() -> R>,
+}
+
+impl<R: ChunkRegistry> IngestPipeline<R> {
+    /// Creates a pipeline judging batch expiry by `state`.
+    pub const fn new(state: PostageContext) -> Self {
+        Self {
+            state,
+            _registry: PhantomData,
+        }
+    }
+
+    /// The chain state batches are judged by.
+    pub const fn state(&self) -> &PostageContext {
+        &self.state
+    }
+
+    /// Replaces the chain state, typically on a new block.
+    pub const fn set_state(&mut self, state: PostageContext) {
+        self.state = state;
+    }
+
+    /// Accepts the chunk in the registry's typed encoding `bytes`, offered
+    /// under `expected_address` and stamped by `stamp` against `batch`.
+    ///
+    /// # Errors
+    ///
+    /// - [`IngestError::Malformed`] if `bytes` do not parse;
+    /// - [`IngestError::AddressMismatch`] if the chunk does not verify at
+    ///   `expected_address`;
+    /// - [`IngestError::Stamp`] if `batch` has expired or rejects the stamp.
+    pub fn ingest<S: SwarmSpec>(
+        &self,
+        bytes: &[u8],
+        stamp: Stamp,
+        expected_address: ChunkAddress,
+        batch: &Batch<S>,
+    ) -> Result<StoredChunk<R>, IngestError> {
+        let chunk = Chunk::<Unverified, R>::parse(expected_address, bytes)
+            .map_err(IngestError::Malformed)?
+            .verify()
+            .map_err(|source| IngestError::AddressMismatch {
+                address: expected_address,
+                source,
+            })?;
+        check_live_stamp(&stamp, chunk.address(), batch, &self.state)?;
+        Ok(StoredChunk { chunk, stamp })
+    }
+}
+
+/// Verifies an already-parsed chunk at its claimed address, then its stamp
+/// against that address and `batch`.
+///
+/// The chunk is certified by its registry member's own acceptance rule, so a
+/// custom chunk type is checked exactly as the built-in ones are. Batch
+/// expiry needs the chain state and is left to [`IngestPipeline`] or
+/// [`StampValidator::validate_with_state`](crate::StampValidator::validate_with_state).
+///
+/// # Errors
+///
+/// - [`IngestError::AddressMismatch`] if the chunk does not verify at its
+///   claimed address;
+/// - [`IngestError::Stamp`] if `batch` rejects the stamp.
+pub fn validate_stamped_chunk<R: ChunkRegistry, S: SwarmSpec>(
+    chunk: Chunk<Unverified, R>,
+    stamp: &Stamp,
+    batch: &Batch<S>,
+) -> Result<Chunk<Verified, R>, IngestError> {
+    let address = *chunk.claimed_address();
+    let chunk = chunk
+        .verify()
+        .map_err(|source| IngestError::AddressMismatch { address, source })?;
+    batch.verify_stamp(stamp, chunk.address())?;
+    Ok(chunk)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::stamp;
+    use crate::{BatchId, BucketDepth};
+    use alloy_primitives::{Address, B256, Keccak256};
+    use alloy_signer_local::PrivateKeySigner;
+    use nectar_primitives::bytes::{Bytes, BytesMut};
+    use nectar_primitives::chunk::{ChunkError, ChunkHeader};
+    use nectar_primitives::{
+        AnyChunk, ChunkInner, ChunkOps, ChunkTypeId, ChunkTypeInfo, ChunkTypeTag, ChunkVersion,
+        DefaultContentChunk, wire,
+    };
+
+    const BATCH: BatchId = BatchId::new([1; 32]);
+
+    fn batch(owner: Address, value: u128) -> Batch {
+        Batch::new(
+            BATCH,
+            value,
+            0,
+            owner,
+            18,
+            BucketDepth::new(16).unwrap(),
+            false,
+        )
+    }
+
+    fn chunk() -> (ChunkAddress, Vec<u8>) {
+        let chunk = DefaultContentChunk::new(b"ingest me".as_slice()).unwrap();
+        (*chunk.address(), AnyChunk::from(chunk).to_typed_bytes())
+    }
+
+    fn pipeline() -> IngestPipeline {
+        IngestPipeline::new(PostageContext::new(100, 500))
+    }
+
+    #[test]
+    fn accepts_a_genuine_stamped_chunk() {
+        let owner = PrivateKeySigner::random();
+        let (address, bytes) = chunk();
+        let expected = stamp(&owner, BATCH, address);
+
+        let stored = pipeline()
+            .ingest(
+                &bytes,
+                expected.clone(),
+                address,
+                &batch(owner.address(), 1_000),
+            )
+            .unwrap();
+        assert_eq!(*stored.address(), address);
+        assert_eq!(*stored.stamp(), expected);
+        assert_eq!(stored.chunk().typed_bytes(), bytes);
+    }
+
+    #[test]
+    fn rejects_malformed_bytes() {
+        let owner = PrivateKeySigner::random();
+        let (address, _) = chunk();
+        let stamp = stamp(&owner, BATCH, address);
+
+        let err = pipeline()
+            .ingest(&[0xff; 3], stamp, address, &batch(owner.address(), 1_000))
+            .unwrap_err();
+        assert!(matches!(err, IngestError::Malformed(_)), "{err:?}");
+    }
+
+    #[test]
+    fn rejects_a_chunk_offered_at_the_wrong_address() {
+        let owner = PrivateKeySigner::random();
+        let (_, bytes) = chunk();
+        let wrong = ChunkAddress::new([0xab; 32]);
+        let stamp = stamp(&owner, BATCH, wrong);
+
+        let err = pipeline()
+            .ingest(&bytes, stamp, wrong, &batch(owner.address(), 1_000))
+            .unwrap_err();
+        assert!(
+            matches!(err, IngestError::AddressMismatch { address, .. } if address == wrong),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn rejects_an_expired_batch() {
+        let owner = PrivateKeySigner::random();
+        let (address, bytes) = chunk();
+        let stamp = stamp(&owner, BATCH, address);
+
+        let err = pipeline()
+            .ingest(&bytes, stamp, address, &batch(owner.address(), 500))
+            .unwrap_err();
+        assert!(
+            matches!(err, IngestError::Stamp(StampError::BatchExpired { .. })),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn rejects_a_stamp_not_signed_by_the_batch_owner() {
+        let owner = PrivateKeySigner::random();
+        let (address, bytes) = chunk();
+        let stamp = stamp(&PrivateKeySigner::random(), BATCH, address);
+
+        let err = pipeline()
+            .ingest(&bytes, stamp, address, &batch(owner.address(), 1_000))
+            .unwrap_err();
+        assert!(
+            matches!(err, IngestError::Stamp(StampError::OwnerMismatch { .. })),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn rejects_a_stamp_for_another_batch() {
+        let owner = PrivateKeySigner::random();
+        let (address, bytes) = chunk();
+        let stamp = stamp(&owner, BatchId::new([2; 32]), address);
+
+        let err = pipeline()
+            .ingest(&bytes, stamp, address, &batch(owner.address(), 1_000))
+            .unwrap_err();
+        assert!(
+            matches!(err, IngestError::Stamp(StampError::BatchMismatch { .. })),
+            "{err:?}"
+        );
+    }
+
+    /// A custom member: address = keccak256(type id || body hash).
+    #[derive(Clone)]
+    struct TaggedHeader;
+
+    impl ChunkHeader for TaggedHeader {
+        const TYPE_ID: ChunkTypeId = ChunkTypeId::custom(200);
+        const VERSION: ChunkVersion = ChunkVersion::new(0);
+        const NAME: &'static str = "tagged";
+        const SIZE: usize = 0;
+
+        fn commit(&self, body_hash: B256) -> ChunkAddress {
+            let mut hasher = Keccak256::new();
+            hasher.update([Self::TYPE_ID.as_u8()]);
+            hasher.update(body_hash);
+            ChunkAddress::from(hasher.finalize())
+        }
+
+        fn validate(&self, body_hash: B256, expected: &ChunkAddress) -> Result<(), ChunkError> {
+            let actual = self.commit(body_hash);
+            if actual == *expected {
+                Ok(())
+            } else {
+                Err(ChunkError::verification_failed(*expected, actual))
+            }
+        }
+
+        fn seal_transformed(&self, _address: &ChunkAddress, root: B256) -> ChunkAddress {
+            ChunkAddress::from(root)
+        }
+
+        fn encode(&self, _out: &mut BytesMut) {}
+
+        fn decode(_cursor: &mut wire::Cursor<'_>) -> Result<Self, ChunkError> {
+            Ok(Self)
+        }
+    }
+
+    type TaggedChunk = ChunkInner<TaggedHeader>;
+
+    const TAGGED_TAG: ChunkTypeTag =
+        ChunkTypeTag::new(TaggedHeader::TYPE_ID, TaggedHeader::VERSION);
+
+    /// A network accepting only the custom member.
+    struct TaggedChunkSet;
+
+    impl ChunkRegistry for TaggedChunkSet {
+        type Envelope = TaggedChunk;
+
+        const MEMBERS: &'static [ChunkTypeInfo] = &[ChunkTypeInfo::of::<TaggedHeader>()];
+
+        fn parse_typed(bytes: &[u8]) -> nectar_primitives::Result<TaggedChunk> {
+            let (tag, payload) = bytes
+                .split_first_chunk::<2>()
+                .ok_or_else(|| ChunkError::invalid_format("missing tag"))?;
+            if ChunkTypeTag::from(*tag) != TAGGED_TAG {
+                return Err(ChunkError::unsupported_tag(ChunkTypeTag::from(*tag)).into());
+            }
+            TaggedChunk::try_from(Bytes::copy_from_slice(payload))
+        }
+
+        fn decode_wire(
+            address: &ChunkAddress,
+            data: Bytes,
+        ) -> nectar_primitives::Result<TaggedChunk> {
+            let chunk = TaggedChunk::try_from(data)?;
+            chunk.verify(address)?;
+            Ok(chunk)
+        }
+
+        fn encode_typed(chunk: &TaggedChunk) -> Vec<u8> {
+            let mut out = TAGGED_TAG.to_bytes().to_vec();
+            out.extend_from_slice(&chunk.clone().into_bytes());
+            out
+        }
+    }
+
+    const _: () = TaggedChunkSet::DISTINCT_TAGS;
+    const _: () = TaggedChunkSet::RESERVED_IDS;
+
+    fn tagged_chunk() -> (ChunkAddress, Vec<u8>) {
+        let payload = b"custom swarm payload";
+        let mut wire_bytes = u64::try_from(payload.len()).unwrap().to_le_bytes().to_vec();
+        wire_bytes.extend_from_slice(payload);
+        let chunk = TaggedChunk::try_from(Bytes::from(wire_bytes)).unwrap();
+        (*chunk.address(), TaggedChunkSet::encode_typed(&chunk))
+    }
+
+    #[test]
+    fn validates_a_stamped_custom_chunk() {
+        let owner = PrivateKeySigner::random();
+        let (address, bytes) = tagged_chunk();
+        let batch = batch(owner.address(), 1_000);
+        let parse = |claimed| Chunk::<Unverified, TaggedChunkSet>::parse(claimed, &bytes).unwrap();
+
+        let genuine = stamp(&owner, BATCH, address);
+        let verified = validate_stamped_chunk(parse(address), &genuine, &batch).unwrap();
+        assert_eq!(*verified.address(), address);
+
+        // The custom member's rule certifies the address before the stamp.
+        let wrong = ChunkAddress::new([0xab; 32]);
+        let err =
+            validate_stamped_chunk(parse(wrong), &stamp(&owner, BATCH, wrong), &batch).unwrap_err();
+        assert!(
+            matches!(err, IngestError::AddressMismatch { address, .. } if address == wrong),
+            "{err:?}"
+        );
+
+        let forged = stamp(&PrivateKeySigner::random(), BATCH, address);
+        let err = validate_stamped_chunk(parse(address), &forged, &batch).unwrap_err();
+        assert!(
+            matches!(err, IngestError::Stamp(StampError::OwnerMismatch { .. })),
+            "{err:?}"
+        );
+    }
+}
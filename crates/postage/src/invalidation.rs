@@ -0,0 +1,223 @@
+//! Derived batch caches kept coherent with chain events.
+//!
+//! A node verifying stamps at volume caches what it derives from a batch:
+//! the owner key ([`BatchOwnerCache`]) and how long the batch has left
+//! ([`BatchTtlCache`]). Both go stale as the chain moves. A top-up or a
+//! dilution changes the per-chunk balance, and so the TTL. An expiry ends the
+//! batch. [`CacheInvalidator`] is the [`BatchEventHandler`] that drops the
+//! affected entries as each [`BatchEvent`] arrives, so the next lookup
+//! re-derives from current state.
+
+use core::convert::Infallible;
+use std::collections::HashMap;
+
+use crate::owner_cache::BatchOwnerCache;
+use crate::{BatchEvent, BatchEventHandler, BatchId};
+
+/// Remaining blocks per batch, as last computed by the caller.
+///
+/// Entries are whatever the caller derived, typically
+/// [`ttl_for_balance`](crate::ttl_for_balance) at the price of the time; the
+/// cache only remembers them until an event invalidates them.
+#[derive(Debug, Clone, Default)]
+pub struct BatchTtlCache {
+    ttls: HashMap<BatchId, u64>,
+}
+
+impl BatchTtlCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The cached TTL of `batch` in blocks, if any.
+    pub fn get(&self, batch: &BatchId) -> Option<u64> {
+        self.ttls.get(batch).copied()
+    }
+
+    /// Caches `ttl_blocks` for `batch`, returning the entry it replaced.
+    pub fn insert(&mut self, batch: BatchId, ttl_blocks: u64) -> Option<u64> {
+        self.ttls.insert(batch, ttl_blocks)
+    }
+
+    /// Forgets `batch`, returning whether it was cached.
+    pub fn forget(&mut self, batch: &BatchId) -> bool {
+        self.ttls.remove(batch).is_some()
+    }
+
+    /// Number of batches cached.
+    pub fn len(&self) -> usize {
+        self.ttls.len()
+    }
+
+    /// Whether no batch is cached.
+    pub fn is_empty(&self) -> bool {
+        self.ttls.is_empty()
+    }
+
+    /// Forgets every cached batch.
+    pub fn clear(&mut self) {
+        self.ttls.clear();
+    }
+}
+
+/// Owner and TTL caches invalidated by batch events.
+///
+/// Feed it the same events as the batch store. Each event drops what it
+/// makes stale:
+///
+/// | Event             | Owner key | TTL     |
+/// |-------------------|-----------|---------|
+/// | `Created`         | dropped   | dropped |
+/// | `TopUp`           | kept      | dropped |
+/// | `DepthIncrease`   | kept      | dropped |
+/// | `Expired`         | dropped   | dropped |
+///
+/// A batch's owner never changes on chain, so only creation and expiry
+/// touch the owner cache; creation clears any entry left over under a
+/// reused id.
+///
+/// ```
+/// use nectar_postage::invalidation::CacheInvalidator;
+/// use nectar_postage::{BatchEvent, BatchEventHandler, BatchId};
+///
+/// let batch_id = BatchId::new([1; 32]);
+/// let mut caches = CacheInvalidator::new(1024);
+/// caches.ttls_mut().insert(batch_id, 17_280);
+///
+/// caches.handle_event(BatchEvent::TopUp { batch_id, new_value: 2_000 }).unwrap();
+/// assert_eq!(caches.ttls().get(&batch_id), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CacheInvalidator {
+    owners: BatchOwnerCache,
+    ttls: BatchTtlCache,
+}
+
+impl CacheInvalidator {
+    /// Creates empty caches, remembering at most `owner_capacity` owner keys.
+    pub fn new(owner_capacity: usize) -> Self {
+        Self::from_parts(BatchOwnerCache::new(owner_capacity), BatchTtlCache::new())
+    }
+
+    /// Wraps existing caches.
+    pub const fn from_parts(owners: BatchOwnerCache, ttls: BatchTtlCache) -> Self {
+        Self { owners, ttls }
+    }
+
+    /// The owner key cache.
+    pub const fn owners(&self) -> &BatchOwnerCache {
+        &self.owners
+    }
+
+    /// The owner key cache, for verification.
+    pub const fn owners_mut(&mut self) -> &mut BatchOwnerCache {
+        &mut self.owners
+    }
+
+    /// The TTL cache.
+    pub const fn ttls(&self) -> &BatchTtlCache {
+        &self.ttls
+    }
+
+    /// The TTL cache, for recording fresh TTLs.
+    pub const fn ttls_mut(&mut self) -> &mut BatchTtlCache {
+        &mut self.ttls
+    }
+
+    /// Consumes into the owner and TTL caches.
+    pub fn into_parts(self) -> (BatchOwnerCache, BatchTtlCache) {
+        (self.owners, self.ttls)
+    }
+}
+
+impl BatchEventHandler for CacheInvalidator {
+    type Error = Infallible;
+
+    fn handle_event(&mut self, event: BatchEvent) -> Result<(), Self::Error> {
+        let batch_id = event.batch_id();
+        self.ttls.forget(&batch_id);
+        match event {
+            BatchEvent::Created { .. } | BatchEvent::Expired { .. } => {
+                self.owners.forget(&batch_id);
+            }
+            BatchEvent::TopUp { .. } | BatchEvent::DepthIncrease { .. } => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::stamp;
+    use crate::{Batch, BucketDepth};
+    use alloy_primitives::Address;
+    use alloy_signer_local::PrivateKeySigner;
+    use nectar_primitives::ChunkAddress;
+
+    #[test]
+    fn events_drop_what_they_make_stale() {
+        let owner = PrivateKeySigner::random();
+        let batch_id = BatchId::new([1; 32]);
+        let other = BatchId::new([2; 32]);
+        let address = ChunkAddress::new([7; 32]);
+        let mut caches = CacheInvalidator::new(8);
+        let warm = |caches: &mut CacheInvalidator| {
+            caches
+                .owners_mut()
+                .verify_stamp_cached(&stamp(&owner, batch_id, address), &address, owner.address())
+                .unwrap();
+            caches.ttls_mut().insert(batch_id, 100);
+            caches.ttls_mut().insert(other, 200);
+        };
+
+        // A top-up clears the cached TTL but keeps the owner key.
+        warm(&mut caches);
+        caches
+            .handle_event(BatchEvent::TopUp {
+                batch_id,
+                new_value: 5_000,
+            })
+            .unwrap();
+        assert_eq!(caches.ttls().get(&batch_id), None);
+        assert_eq!(caches.ttls().get(&other), Some(200));
+        assert_eq!(caches.owners().owner(&batch_id), Some(owner.address()));
+
+        warm(&mut caches);
+        caches
+            .handle_event(BatchEvent::DepthIncrease {
+                batch_id,
+                new_depth: 21,
+                new_value: 1000,
+            })
+            .unwrap();
+        assert_eq!(caches.ttls().get(&batch_id), None);
+        assert_eq!(caches.owners().owner(&batch_id), Some(owner.address()));
+
+        // Expiry and creation drop both.
+        warm(&mut caches);
+        caches
+            .handle_event(BatchEvent::Expired { batch_id })
+            .unwrap();
+        assert_eq!(caches.ttls().get(&batch_id), None);
+        assert_eq!(caches.owners().owner(&batch_id), None);
+
+        warm(&mut caches);
+        let created = Batch::new(
+            batch_id,
+            1_000,
+            0,
+            Address::ZERO,
+            20,
+            BucketDepth::new(16).unwrap(),
+            false,
+        );
+        caches
+            .handle_event(BatchEvent::Created { batch: created })
+            .unwrap();
+        let (owners, ttls) = caches.into_parts();
+        assert!(owners.is_empty());
+        assert_eq!((ttls.len(), ttls.get(&other)), (1, Some(200)));
+    }
+}
@@ -8,14 +8,29 @@
 //!
 //! # Core Types
 //!
-//! - [`Batch`]: A postage batch representing prepaid storage
+//! - [`Batch`]: A postage batch representing prepaid storage, built
+//!   positionally or through a [`BatchBuilder`]
 //! - [`BucketDepth`]: A collision-bucket depth a network accepts, checked
 //!   against the [`SwarmSpec`](nectar_primitives::SwarmSpec) it is built for
 //! - [`Stamp`]: A postage stamp proving payment for chunk storage
 //! - [`StampIndex`]: The bucket and position index within a stamp
 //! - [`StampDigest`]: The data to be signed when creating a stamp
 //! - [`PostageContext`]: Context for batch expiry calculations
+//! - [`balance_for_ttl`] / [`ttl_for_balance`]: Convert between a per-chunk
+//!   balance and the blocks it lasts at an oracle price
+//! - [`NormalisedBalance`] / [`PerChunkBalance`]: The contract's stored
+//!   balance and the per-chunk balance it leaves after the total payout
 //! - [`BatchEvent`]: Events emitted by the postage stamp contract (requires `std`)
+//! - [`dedup::StampDedup`]: Detect a stamp slot reused for a second chunk
+//!   (requires `std`)
+//! - [`ingest::IngestPipeline`]: Parse, address-verify and stamp-check an
+//!   incoming chunk in one call (requires `std`);
+//!   [`ingest::validate_stamped_chunk`] does the same for an already-parsed
+//!   chunk of any registry
+//! - [`owner_cache::BatchOwnerCache`]: Verify stamps against batch owner keys
+//!   recovered once per batch (requires `std`)
+//! - [`invalidation::CacheInvalidator`]: Keep owner and TTL caches coherent
+//!   with batch events (requires `std`)
 //!
 //! # Traits
 //!
@@ -31,7 +46,9 @@
 //!
 //! - `std` (default): Enable standard library support, BatchStore, events
 //! - `serde`: Enable serde serialization/deserialization
+//! - `cbor`: Versioned CBOR stamp blobs for storage
 //! - `parallel`: Enable parallel verification with rayon
+//! - `streaming`: Ordered stream verification over any executor
 //! - `arbitrary`: Raw `Arbitrary` impls plus the valid-by-construction
 //!   `generators` module for property-based testing and fuzzing
 
@@ -64,11 +81,13 @@ use k256 as _;
 extern crate alloc;
 
 mod batch;
+pub mod builder;
 mod error;
 #[cfg(any(test, feature = "arbitrary"))]
 pub mod generators;
 #[cfg(any(test, feature = "arbitrary"))]
 pub mod oracles;
+mod pricing;
 mod stamp;
 mod stamped;
 mod util;
@@ -76,8 +95,18 @@ mod validation;
 
 // Storage and events (std only)
 #[cfg(feature = "std")]
+pub mod dedup;
+#[cfg(feature = "std")]
 mod events;
 #[cfg(feature = "std")]
+pub mod ingest;
+#[cfg(feature = "std")]
+pub mod invalidation;
+#[cfg(feature = "std")]
+mod overflow;
+#[cfg(feature = "std")]
+pub mod owner_cache;
+#[cfg(feature = "std")]
 mod snapshot_store;
 #[cfg(feature = "std")]
 mod store;
@@ -86,12 +115,29 @@ mod store;
 #[cfg(feature = "parallel")]
 pub mod parallel;
 
+// Stream verification (requires futures-core)
+#[cfg(feature = "streaming")]
+pub mod streaming;
+
+#[cfg(test)]
+mod test_utils;
+
 // Core types
-pub use batch::{Batch, BatchId, BatchParams, BucketDepth};
+pub use batch::{Batch, BatchBuilder, BatchId, BatchParams, BucketDepth};
 pub use error::StampError;
-pub use stamp::{STAMP_SIZE, Stamp, StampBytes, StampDigest, StampIndex};
+pub use pricing::{
+    BLOCK_TIME_SECS, NormalisedBalance, PerChunkBalance, balance_for_ttl, ttl_for_balance,
+};
+#[cfg(feature = "cbor")]
+pub use stamp::STAMP_CBOR_VERSION;
+pub use stamp::{
+    COMPACT_STAMP_SIZE, CompactStampBytes, STAMP_SIZE, Stamp, StampBytes, StampDigest, StampIndex,
+    StampRef, read_stamps, write_stamps,
+};
 pub use stamped::StampedChunk;
-pub use util::{PostageContext, calculate_bucket, current_timestamp};
+pub use util::{
+    BUCKET_DEPTH_MAX, PostageContext, calculate_bucket, calculate_buckets, current_timestamp,
+};
 pub use validation::StampValidator;
 #[cfg(feature = "std")]
 pub use validation::StoreValidator;
@@ -100,6 +146,8 @@ pub use validation::StoreValidator;
 #[cfg(feature = "std")]
 pub use events::{BatchEvent, BatchEventHandler};
 #[cfg(feature = "std")]
+pub use overflow::overflow_probability;
+#[cfg(feature = "std")]
 pub use snapshot_store::SnapshotStore;
 #[cfg(feature = "std")]
 pub use store::{BatchStore, BatchStoreError, BatchStoreExt};
@@ -0,0 +1,115 @@
+//! Bucket-overflow estimates for choosing a batch geometry.
+
+/// Estimates the probability that stamping `expected_chunks` chunks into an
+/// empty batch of `depth` and `bucket_depth` fills some bucket before the
+/// batch is logically full.
+///
+/// The model is balls into bins: each chunk lands in one of the
+/// `2^bucket_depth` buckets uniformly and independently of the others (chunk
+/// addresses are hashes). A bucket's load is approximated as Poisson with
+/// mean `expected_chunks / 2^bucket_depth`, and the buckets as independent,
+/// giving
+///
+/// `P = 1 - (1 - P(load > 2^(depth - bucket_depth)))^(2^bucket_depth)`.
+///
+/// Poisson tails are heavier than the exact binomial ones and the true bucket
+/// loads are negatively correlated, so the estimate errs high, which is the
+/// safe side when sizing a batch. Two cases are exact: no overflow while a
+/// single bucket could hold every chunk, and certain overflow past the
+/// batch's `2^depth` chunks.
+// The u64 to f64 casts round only past 2^53 chunks, far beyond any batch
+// geometry; the float arithmetic is the estimate itself.
+#[allow(clippy::as_conversions, clippy::arithmetic_side_effects)]
+pub fn overflow_probability(expected_chunks: u64, depth: u8, bucket_depth: u8) -> f64 {
+    let buckets = 1u64
+        .checked_shl(u32::from(bucket_depth))
+        .unwrap_or(u64::MAX);
+    // A bucket wider than any `u64` count never overflows.
+    let Some(capacity) = 1u64.checked_shl(u32::from(depth.saturating_sub(bucket_depth))) else {
+        return 0.0;
+    };
+    if expected_chunks > buckets.saturating_mul(capacity) {
+        return 1.0;
+    }
+    if expected_chunks <= capacity {
+        return 0.0;
+    }
+
+    // Upper tail P(X > capacity) of X ~ Poisson(lambda). lambda <= capacity
+    // here, so from k = capacity + 1 each term shrinks by lambda / (k + 1).
+    let buckets = buckets as f64;
+    let lambda = expected_chunks as f64 / buckets;
+    let first = capacity + 1;
+    let mut term = (-lambda + first as f64 * lambda.ln() - ln_factorial(first)).exp();
+    let mut tail = 0.0;
+    let mut k = first;
+    while term > tail * f64::EPSILON && term > 0.0 {
+        tail += term;
+        k += 1;
+        term *= lambda / k as f64;
+    }
+
+    // 1 - (1 - tail)^buckets, kept accurate for tiny tails.
+    -(buckets * (-tail.min(1.0)).ln_1p()).exp_m1()
+}
+
+/// `ln(k!)`: exact for small `k`, Stirling's series beyond.
+#[allow(clippy::as_conversions, clippy::arithmetic_side_effects)]
+fn ln_factorial(k: u64) -> f64 {
+    if k < 16 {
+        return (2..=k).map(|i| (i as f64).ln()).sum();
+    }
+    let k = k as f64;
+    k * k.ln() - k + 0.5 * (core::f64::consts::TAU * k).ln() + 1.0 / (12.0 * k)
+        - 1.0 / (360.0 * k * k * k)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_cases() {
+        // 2^16 buckets of 4 slots.
+        assert_eq!(overflow_probability(0, 18, 16), 0.0);
+        assert_eq!(overflow_probability(4, 18, 16), 0.0);
+        assert_eq!(overflow_probability((1 << 18) + 1, 18, 16), 1.0);
+        assert_eq!(overflow_probability(u64::MAX, 200, 16), 0.0);
+    }
+
+    #[test]
+    fn rises_with_the_load() {
+        let mut previous = 0.0;
+        for chunks in [1_000, 10_000, 50_000, 100_000, 200_000, 1 << 18] {
+            let p = overflow_probability(chunks, 18, 16);
+            assert!((0.0..=1.0).contains(&p), "{chunks}: {p}");
+            assert!(p >= previous, "{chunks}: {p} < {previous}");
+            previous = p;
+        }
+        // A full batch almost surely overflowed a bucket on the way.
+        assert!(previous > 0.999);
+        // A roomy batch barely registers.
+        assert!(overflow_probability(1_000, 24, 16) < 1e-12);
+    }
+
+    #[test]
+    fn matches_the_poisson_tail() {
+        // Two buckets of one slot, mean load one: each overflows with
+        // 1 - 2/e, and the pair with 1 - (2/e)^2.
+        let per_bucket = 1.0 - 2.0 / core::f64::consts::E;
+        let exact = 1.0 - (1.0 - per_bucket).powi(2);
+        assert!((overflow_probability(2, 1, 1) - exact).abs() < 1e-12);
+    }
+
+    #[test]
+    fn ln_factorial_matches_product() {
+        let mut exact = 0.0f64;
+        for k in 1..=40u64 {
+            exact += (k as f64).ln();
+            assert!(
+                (ln_factorial(k) - exact).abs() < 1e-9 * exact.max(1.0),
+                "{k}"
+            );
+        }
+    }
+}
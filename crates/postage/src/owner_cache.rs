@@ -0,0 +1,200 @@
+//! Batch owner keys recovered once and reused across calls.
+//!
+//! Recovering a stamp's signer is the expensive half of verification;
+//! checking a signature against a known public key is about ten times
+//! cheaper. [`BatchOwnerCache`] keeps the owner key of each batch it has
+//! verified a stamp for, so later stamps of the batch take the
+//! [`verify_with_pubkey`](Stamp::verify_with_pubkey) fast path.
+//!
+//! A key is cached only after a full recovery matched the owner the caller
+//! vouches for, so a forged stamp can never plant a key for a batch.
+
+use alloy_primitives::Address;
+use alloy_signer::utils::public_key_to_address;
+use nectar_primitives::ChunkAddress;
+use nectar_primitives::cache::FifoCache;
+
+use crate::{BatchId, Stamp, StampError, VerifyingKey};
+
+/// Remembers the owner and public key of up to `capacity` batches in a
+/// [`FifoCache`].
+///
+/// ```
+/// use alloy_signer::SignerSync;
+/// use alloy_signer_local::PrivateKeySigner;
+/// use nectar_postage::owner_cache::BatchOwnerCache;
+/// use nectar_postage::{BatchId, Stamp, StampDigest, StampIndex};
+/// use nectar_primitives::ChunkAddress;
+///
+/// let owner = PrivateKeySigner::random();
+/// let address = ChunkAddress::new([1; 32]);
+/// let index = StampIndex::new(nectar_postage::calculate_bucket(&address, 16), 0);
+/// let prehash = StampDigest::new(address, BatchId::ZERO, index, 1).to_prehash();
+/// let sig = owner.sign_message_sync(prehash.as_slice()).unwrap();
+/// let stamp = Stamp::with_index(BatchId::ZERO, index, 1, sig);
+///
+/// let mut cache = BatchOwnerCache::new(1024);
+/// // The first stamp of a batch recovers the key, later ones reuse it.
+/// assert_eq!(cache.verify_stamp_cached(&stamp, &address, owner.address()), Ok(owner.address()));
+/// assert_eq!(cache.verify_stamp_cached(&stamp, &address, owner.address()), Ok(owner.address()));
+/// assert_eq!((cache.misses(), cache.hits()), (1, 1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct BatchOwnerCache {
+    owners: FifoCache<BatchId, (Address, VerifyingKey)>,
+    hits: u64,
+    misses: u64,
+}
+
+impl BatchOwnerCache {
+    /// Creates a cache remembering at most `capacity` batches.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            owners: FifoCache::new(capacity),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Verifies that `owner` signed `stamp` for `chunk_address`, returning
+    /// the owner.
+    ///
+    /// `owner` is the owner of the stamp's batch, as the batch store or chain
+    /// knows it. A batch cached under that owner is checked against its
+    /// cached key; otherwise the key is recovered from the stamp and cached
+    /// once it matches `owner`. A stamp failing the fast path is re-checked
+    /// by full recovery, so its error is the one [`Stamp::verify`] would give.
+    ///
+    /// # Errors
+    ///
+    /// - [`StampError::InvalidSignature`] if no key can be recovered;
+    /// - [`StampError::OwnerMismatch`] if the stamp was signed by someone
+    ///   other than `owner`.
+    pub fn verify_stamp_cached(
+        &mut self,
+        stamp: &Stamp,
+        chunk_address: &ChunkAddress,
+        owner: Address,
+    ) -> Result<Address, StampError> {
+        if let Some((cached, key)) = self.owners.get(&stamp.batch())
+            && *cached == owner
+            && stamp.verify_with_pubkey(chunk_address, key).is_ok()
+        {
+            self.hits = self.hits.saturating_add(1);
+            return Ok(owner);
+        }
+
+        self.misses = self.misses.saturating_add(1);
+        let key = stamp.recover_pubkey(chunk_address)?;
+        let signer = public_key_to_address(&key);
+        if signer != owner {
+            return Err(StampError::OwnerMismatch {
+                expected: owner,
+                actual: signer,
+            });
+        }
+        self.owners.insert(stamp.batch(), (owner, key));
+        Ok(owner)
+    }
+
+    /// The cached owner of `batch`, if any.
+    pub fn owner(&self, batch: &BatchId) -> Option<Address> {
+        self.owners.get(batch).map(|(owner, _)| *owner)
+    }
+
+    /// Number of batches currently remembered.
+    pub fn len(&self) -> usize {
+        self.owners.len()
+    }
+
+    /// Whether no batch is remembered.
+    pub fn is_empty(&self) -> bool {
+        self.owners.is_empty()
+    }
+
+    /// Maximum number of batches remembered.
+    pub const fn capacity(&self) -> usize {
+        match self.owners.capacity() {
+            Some(capacity) => capacity,
+            None => usize::MAX,
+        }
+    }
+
+    /// Stamps verified against a cached key.
+    pub const fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Stamps that needed a full recovery.
+    pub const fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Forgets `batch`, returning whether it was remembered.
+    pub fn forget(&mut self, batch: &BatchId) -> bool {
+        self.owners.remove(batch).is_some()
+    }
+
+    /// Forgets every remembered batch, keeping the counters.
+    pub fn clear(&mut self) {
+        self.owners.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::stamp;
+    use alloy_signer_local::PrivateKeySigner;
+
+    #[test]
+    fn later_stamps_of_a_batch_hit_the_cached_key() {
+        let owner = PrivateKeySigner::random();
+        let batch = BatchId::new([1; 32]);
+        let mut cache = BatchOwnerCache::new(8);
+
+        for byte in 1..=3u8 {
+            let address = ChunkAddress::new([byte; 32]);
+            let stamp = stamp(&owner, batch, address);
+            assert_eq!(
+                cache.verify_stamp_cached(&stamp, &address, owner.address()),
+                Ok(owner.address())
+            );
+        }
+        assert_eq!((cache.misses(), cache.hits()), (1, 2));
+        assert_eq!(cache.owner(&batch), Some(owner.address()));
+
+        // A stamp the owner did not sign fails the fast path and then the
+        // full recovery, with the error `Stamp::verify` gives.
+        let address = ChunkAddress::new([9; 32]);
+        let forged = stamp(&PrivateKeySigner::random(), batch, address);
+        assert_eq!(
+            cache.verify_stamp_cached(&forged, &address, owner.address()),
+            forged
+                .verify(&address, owner.address())
+                .map(|()| owner.address())
+        );
+        assert_eq!(cache.owner(&batch), Some(owner.address()));
+    }
+
+    #[test]
+    fn a_forged_first_stamp_is_not_cached() {
+        let owner = PrivateKeySigner::random();
+        let batch = BatchId::new([1; 32]);
+        let address = ChunkAddress::new([2; 32]);
+        let mut cache = BatchOwnerCache::new(8);
+
+        let forged = stamp(&PrivateKeySigner::random(), batch, address);
+        assert!(matches!(
+            cache.verify_stamp_cached(&forged, &address, owner.address()),
+            Err(StampError::OwnerMismatch { .. })
+        ));
+        assert!(cache.is_empty());
+
+        let genuine = stamp(&owner, batch, address);
+        assert_eq!(
+            cache.verify_stamp_cached(&genuine, &address, owner.address()),
+            Ok(owner.address())
+        );
+    }
+}
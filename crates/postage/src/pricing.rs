@@ -0,0 +1,178 @@
+//! Conversions between a batch's per-chunk balance and its time to live.
+//!
+//! The price oracle quotes storage in PLUR per chunk per block, and the
+//! postage contract charges every batch that price on each block: a batch
+//! whose per-chunk balance is `b` survives `b / price` blocks at a constant
+//! price. These helpers apply that rule in both directions. The price moves
+//! with network utilisation, so a TTL computed here is an estimate at the
+//! current price, not a guarantee.
+//!
+//! The contract stores a batch's balance *normalised*: the cumulative payout
+//! per chunk at creation plus the per-chunk balance paid in, so one running
+//! total drains every batch at once. [`NormalisedBalance`] and
+//! [`PerChunkBalance`] keep the two apart, converting through that total
+//! payout; only a per-chunk balance converts to a TTL.
+//!
+//! Durations are counted in blocks. Converting from wall-clock time assumes
+//! the Gnosis Chain block interval, [`BLOCK_TIME_SECS`].
+//!
+//! ```
+//! use alloy_primitives::U256;
+//! use nectar_postage::{BLOCK_TIME_SECS, PerChunkBalance, balance_for_ttl, ttl_for_balance};
+//!
+//! let price = 24_000; // PLUR per chunk per block
+//! let thirty_days = 30 * 24 * 60 * 60 / BLOCK_TIME_SECS;
+//! let balance = balance_for_ttl(price, thirty_days);
+//! assert_eq!(balance, PerChunkBalance::new(U256::from(12_441_600_000u64)));
+//! assert_eq!(ttl_for_balance(price, balance), thirty_days);
+//!
+//! // Ten days later the total payout has caught up a third of the way.
+//! let paid_out = U256::from(4_147_200_000u64);
+//! let normalised = balance.normalise(U256::ZERO);
+//! assert_eq!(ttl_for_balance(price, normalised.per_chunk(paid_out)), 2 * thirty_days / 3);
+//! ```
+
+use alloy_primitives::U256;
+use derive_more::{Display, From, Into};
+
+/// A batch balance as the postage contract stores it: the cumulative payout
+/// per chunk when the batch was funded plus the per-chunk balance paid in.
+///
+/// This is [`Batch::value`](crate::Batch::value). It says nothing about time
+/// left until the current total payout is subtracted
+/// ([`per_chunk`](Self::per_chunk)).
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Display, From, Into,
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct NormalisedBalance(U256);
+
+impl NormalisedBalance {
+    /// Wraps a raw normalised balance.
+    pub const fn new(value: U256) -> Self {
+        Self(value)
+    }
+
+    /// Returns the raw value.
+    pub const fn get(self) -> U256 {
+        self.0
+    }
+
+    /// The per-chunk balance left once the contract has paid out
+    /// `total_amount` per chunk, zero for an expired batch.
+    pub const fn per_chunk(self, total_amount: U256) -> PerChunkBalance {
+        PerChunkBalance(self.0.saturating_sub(total_amount))
+    }
+}
+
+/// A balance per chunk: PLUR each chunk of a batch can still pay for.
+///
+/// This is the unit batch creation and top-ups take, and the one
+/// [`ttl_for_balance`] divides by the price.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Display, From, Into,
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct PerChunkBalance(U256);
+
+impl PerChunkBalance {
+    /// Wraps a raw per-chunk balance.
+    pub const fn new(value: U256) -> Self {
+        Self(value)
+    }
+
+    /// Returns the raw value.
+    pub const fn get(self) -> U256 {
+        self.0
+    }
+
+    /// The normalised balance a batch funded with this per-chunk balance
+    /// holds when the contract has paid out `total_amount` per chunk.
+    pub const fn normalise(self, total_amount: U256) -> NormalisedBalance {
+        NormalisedBalance(self.0.saturating_add(total_amount))
+    }
+}
+
+/// Average Gnosis Chain block interval in seconds, the chain the postage
+/// contract lives on.
+pub const BLOCK_TIME_SECS: u64 = 5;
+
+/// Per-chunk balance that keeps a batch alive for `ttl_blocks` blocks at
+/// `price_per_chunk` PLUR per chunk per block.
+///
+/// This is the initial balance per chunk passed to batch creation; the
+/// batch's total cost is this times its chunk capacity.
+pub fn balance_for_ttl(price_per_chunk: u64, ttl_blocks: u64) -> PerChunkBalance {
+    // Two 64-bit factors cannot overflow 256 bits.
+    PerChunkBalance(U256::from(price_per_chunk).saturating_mul(U256::from(ttl_blocks)))
+}
+
+/// Blocks a per-chunk `balance` lasts at `price_per_chunk` PLUR per chunk
+/// per block, rounded down.
+///
+/// Saturates at `u64::MAX` when the balance outlasts any block height, and
+/// for a zero price, which never drains a balance.
+pub fn ttl_for_balance(price_per_chunk: u64, balance: PerChunkBalance) -> u64 {
+    balance
+        .0
+        .checked_div(U256::from(price_per_chunk))
+        .map_or(u64::MAX, |blocks| u64::try_from(blocks).unwrap_or(u64::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Blocks in one day at the assumed block time.
+    const DAY: u64 = 24 * 60 * 60 / BLOCK_TIME_SECS;
+
+    #[test]
+    fn known_prices_round_trip() {
+        for (price, days, balance) in [
+            (24_000u64, 1u64, 414_720_000u64),
+            (24_000, 30, 12_441_600_000),
+            (44_445, 7, 5_376_067_200),
+        ] {
+            let blocks = days * DAY;
+            let balance = PerChunkBalance::new(U256::from(balance));
+            assert_eq!(balance_for_ttl(price, blocks), balance);
+            assert_eq!(ttl_for_balance(price, balance), blocks);
+        }
+    }
+
+    #[test]
+    fn ttl_rounds_down_and_saturates() {
+        let balance = |value: u64| PerChunkBalance::new(U256::from(value));
+        assert_eq!(ttl_for_balance(24_000, balance(47_999)), 1);
+        assert_eq!(ttl_for_balance(24_000, PerChunkBalance::default()), 0);
+        assert_eq!(ttl_for_balance(0, balance(1)), u64::MAX);
+        assert_eq!(
+            ttl_for_balance(1, PerChunkBalance::new(U256::MAX)),
+            u64::MAX
+        );
+        assert_eq!(
+            balance_for_ttl(u64::MAX, u64::MAX).get(),
+            U256::from(u64::MAX) * U256::from(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn normalising_round_trips_through_the_total_payout() {
+        let paid_in = PerChunkBalance::new(U256::from(1_000u64));
+        let normalised = paid_in.normalise(U256::from(250u64));
+        assert_eq!(normalised.get(), U256::from(1_250u64));
+        assert_eq!(normalised.per_chunk(U256::from(250u64)), paid_in);
+
+        // The payout drains the balance, and an expired batch has none left.
+        assert_eq!(
+            normalised.per_chunk(U256::from(1_000u64)).get(),
+            U256::from(250u64)
+        );
+        assert_eq!(
+            normalised.per_chunk(U256::from(2_000u64)),
+            PerChunkBalance::default()
+        );
+    }
+}
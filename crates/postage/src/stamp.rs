@@ -5,11 +5,12 @@ use alloc::vec::Vec;
 use alloy_primitives::{Address, B256, Signature, eip191_hash_message};
 use alloy_signer::k256::ecdsa::VerifyingKey;
 use nectar_primitives::{
-    ChunkAddress,
+    ChunkAddress, SwarmSpec,
     wire::{Cursor, FromCursor, ToWriter, Underrun, Writer},
 };
+use subtle::ConstantTimeEq;
 
-use crate::{BatchId, StampError};
+use crate::{BatchId, BucketDepth, StampError};
 
 /// The size of a serialized stamp in bytes.
 ///
@@ -33,6 +34,19 @@ const PREHASH_SIZE: usize = ChunkAddress::SIZE + BatchId::SIZE + INDEX_SIZE + TI
 /// A serialized postage stamp as a fixed-size byte array.
 pub type StampBytes = [u8; STAMP_SIZE];
 
+/// The size of a stamp serialized with an EIP-2098 compact signature.
+///
+/// Layout: batch_id (32) + bucket (4) + index (4) + timestamp (8) + `r || yParity:s` (64) = 112 bytes
+pub const COMPACT_STAMP_SIZE: usize = 112;
+
+/// Wire width of an EIP-2098 compact signature.
+const COMPACT_SIG_SIZE: usize = 64;
+
+const _: () = assert!(COMPACT_STAMP_SIZE + 1 == STAMP_SIZE);
+
+/// A postage stamp in the compact form, as a fixed-size byte array.
+pub type CompactStampBytes = [u8; COMPACT_STAMP_SIZE];
+
 /// A stamp index representing the position of a chunk within a batch.
 ///
 /// The stamp index consists of two components:
@@ -61,11 +75,37 @@ pub struct StampIndex {
 
 impl StampIndex {
     /// Creates a new stamp index.
+    ///
+    /// The bucket is not checked against any bucket depth; use
+    /// [`checked_new`](Self::checked_new) for coordinates from untrusted input.
     #[inline]
     pub const fn new(bucket: u32, index: u32) -> Self {
         Self { bucket, index }
     }
 
+    /// Creates a stamp index whose bucket is one of the `2^bucket_depth`
+    /// buckets of a batch, or `None` if it lies outside them.
+    ///
+    /// ```
+    /// use nectar_postage::{BucketDepth, StampIndex};
+    ///
+    /// let depth = BucketDepth::<nectar_primitives::Mainnet>::new(16).unwrap();
+    /// assert!(StampIndex::checked_new(0xffff, 3, depth).is_some());
+    /// assert!(StampIndex::checked_new(0x1_0000, 3, depth).is_none());
+    /// ```
+    #[inline]
+    pub const fn checked_new<S: SwarmSpec>(
+        bucket: u32,
+        index: u32,
+        bucket_depth: BucketDepth<S>,
+    ) -> Option<Self> {
+        if bucket_depth.contains_bucket(bucket) {
+            Some(Self::new(bucket, index))
+        } else {
+            None
+        }
+    }
+
     /// Returns the collision bucket (x).
     #[inline]
     pub const fn bucket(&self) -> u32 {
@@ -94,6 +134,9 @@ impl StampIndex {
     /// specifications. The Swarm protocol only specifies that the stamp contains
     /// bucket and index values; the exact wire format for the combined index
     /// used in signature computation is left to implementations.
+    ///
+    /// Each half is a full `u32`, so the encoding is lossless for every
+    /// bucket and index, [`u32::MAX`] included; it cannot wrap.
     #[inline]
     #[allow(clippy::as_conversions)] // widening u32 -> u64, infallible; `u64::from` is not const-callable
     pub const fn encode(&self) -> u64 {
@@ -166,6 +209,10 @@ impl ToWriter for StampIndex {
 /// - Index (y): 4 bytes, big-endian
 /// - Timestamp: 8 bytes, big-endian
 /// - Signature: 65 bytes (r || s || v)
+///
+/// With `serde`, human-readable formats carry the batch id and the signature
+/// as `0x`-prefixed hex strings, as Ethereum tooling and bee's API expect;
+/// binary formats carry raw bytes.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Stamp {
@@ -176,9 +223,31 @@ pub struct Stamp {
     /// Timestamp when the stamp was created (nanoseconds since epoch).
     timestamp: u64,
     /// The signature proving ownership.
+    #[cfg_attr(feature = "serde", serde(with = "sig_serde"))]
     sig: Signature,
 }
 
+/// Serde for a stamp signature as its 65 wire bytes (`r || s || v`).
+#[cfg(feature = "serde")]
+mod sig_serde {
+    use alloy_primitives::{FixedBytes, Signature};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
+    pub(super) fn serialize<S: Serializer>(
+        sig: &Signature,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        FixedBytes(sig.as_bytes()).serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Signature, D::Error> {
+        let bytes = FixedBytes::<65>::deserialize(deserializer)?;
+        Signature::from_raw_array(&bytes.0).map_err(D::Error::custom)
+    }
+}
+
 impl Stamp {
     /// Creates a new stamp with the given parameters.
     #[inline]
@@ -249,6 +318,28 @@ impl Stamp {
         &self.sig
     }
 
+    /// The signature's recovery id in the raw `{0, 1}` convention, as
+    /// secp256k1 recovery takes it.
+    ///
+    /// Only the parity is stored, so a stamp parsed from either convention
+    /// answers the same.
+    #[inline]
+    pub fn normalized_v(&self) -> u8 {
+        u8::from(self.sig.v())
+    }
+
+    /// The signature's recovery id in the Ethereum `{27, 28}` convention:
+    /// the byte [`to_bytes`](Self::to_bytes) writes last, as bee does.
+    ///
+    /// [`from_bytes`](Self::from_bytes) accepts either convention, so stamps
+    /// from bee and from signers emitting `{0, 1}` both parse, and every
+    /// stamp serializes in bee's form.
+    #[inline]
+    #[allow(clippy::arithmetic_side_effects)] // `normalized_v` is 0 or 1, so the sum is 27 or 28
+    pub fn eth_v(&self) -> u8 {
+        27 + self.normalized_v()
+    }
+
     /// Serializes the stamp to a 113-byte array.
     #[inline]
     pub fn to_bytes(&self) -> StampBytes {
@@ -264,12 +355,58 @@ impl Stamp {
 
     /// Deserializes a stamp from a 113-byte array.
     ///
-    /// Returns an error if the signature bytes are invalid.
+    /// Returns an error if the signature bytes are invalid. Parsing reads only
+    /// the stamp's own bytes, so its early returns reveal nothing to a prober
+    /// beyond what it sent.
     #[inline]
     pub fn from_bytes(bytes: &StampBytes) -> Result<Self, StampError> {
         Cursor::new(bytes).take::<Self>()
     }
 
+    /// Serializes the stamp to 112 bytes, packing the signature's recovery
+    /// bit into the top bit of `s` as EIP-2098 specifies.
+    ///
+    /// The canonical 113-byte [`to_bytes`](Self::to_bytes) form is what the
+    /// network exchanges and what other implementations parse; prefer the
+    /// compact form only for local storage or transports both ends agree on,
+    /// where the saved byte per stamp adds up. A high-`s` signature is
+    /// normalised to its low-`s` twin, which recovers the same signer.
+    #[inline]
+    pub fn to_bytes_compact(&self) -> CompactStampBytes {
+        let mut buf = Vec::with_capacity(COMPACT_STAMP_SIZE);
+        let mut w = Writer::new(&mut buf);
+        w.put(&self.batch);
+        w.put(&self.index);
+        w.put(&self.timestamp.to_be_bytes());
+        w.put(&self.sig.as_erc2098());
+
+        // The compact field widths sum to COMPACT_STAMP_SIZE (asserted at
+        // compile time above), so the writer filled the array exactly.
+        let mut bytes = [0u8; COMPACT_STAMP_SIZE];
+        bytes.copy_from_slice(&buf);
+        bytes
+    }
+
+    /// Deserializes a stamp from the 112-byte form written by
+    /// [`to_bytes_compact`](Self::to_bytes_compact).
+    ///
+    /// Any 64 bytes decode as a compact signature; a malformed one surfaces
+    /// when the signer is recovered.
+    #[inline]
+    pub fn from_bytes_compact(bytes: &CompactStampBytes) -> Result<Self, StampError> {
+        let mut cur = Cursor::new(bytes);
+        let batch = cur.take::<BatchId>()?;
+        let index = cur.take::<StampIndex>()?;
+        let timestamp = u64::from_be_bytes(cur.take::<[u8; TIMESTAMP_SIZE]>()?);
+        let sig = Signature::from_erc2098(&cur.take::<[u8; COMPACT_SIG_SIZE]>()?);
+        Ok(Self {
+            batch,
+            index,
+            timestamp,
+            sig,
+        })
+    }
+
     /// Attempts to deserialize a stamp from a byte slice.
     ///
     /// Returns an error if the slice is not exactly 113 bytes or if the signature is invalid.
@@ -335,6 +472,24 @@ impl Stamp {
     /// stamp.verify(&chunk_address, batch.owner())?;
     /// ```
     pub fn verify(&self, chunk_address: &ChunkAddress, owner: Address) -> Result<(), StampError> {
+        self.verify_returning_owner(chunk_address, owner).map(drop)
+    }
+
+    /// Verifies this stamp was signed by `owner`, returning the recovered
+    /// signer for logging or auditing.
+    ///
+    /// One recovery serves both the check and the audit record: on success
+    /// the signer is returned, on a mismatch it is the `actual` field of
+    /// [`StampError::OwnerMismatch`].
+    ///
+    /// # Errors
+    ///
+    /// As [`verify`](Self::verify).
+    pub fn verify_returning_owner(
+        &self,
+        chunk_address: &ChunkAddress,
+        owner: Address,
+    ) -> Result<Address, StampError> {
         let recovered = self.recover_signer(chunk_address)?;
         if recovered != owner {
             return Err(StampError::OwnerMismatch {
@@ -342,7 +497,32 @@ impl Stamp {
                 actual: recovered,
             });
         }
-        Ok(())
+        Ok(recovered)
+    }
+
+    /// Verifies this stamp was signed by `owner`, comparing the recovered
+    /// signer with the owner in constant time.
+    ///
+    /// Behaves as [`verify`](Self::verify), for deployments where the owner
+    /// is secret (stamps used as access tokens) and probed by untrusted
+    /// peers: recovery depends only on the stamp, and the comparison takes
+    /// the same time however many bytes of the owner a guess matches. The
+    /// error on a mismatch still carries both addresses, for the caller to
+    /// withhold from the peer.
+    pub fn verify_ct(
+        &self,
+        chunk_address: &ChunkAddress,
+        owner: Address,
+    ) -> Result<(), StampError> {
+        let recovered = self.recover_signer(chunk_address)?;
+        if bool::from(recovered.as_slice().ct_eq(owner.as_slice())) {
+            Ok(())
+        } else {
+            Err(StampError::OwnerMismatch {
+                expected: owner,
+                actual: recovered,
+            })
+        }
     }
 
     /// Recovers the public key from this stamp.
@@ -352,9 +532,9 @@ impl Stamp {
     /// [`verify_with_pubkey`](Self::verify_with_pubkey) which is approximately
     /// 10x faster than full signature recovery.
     ///
-    /// This pair (`recover_pubkey` / `verify_with_pubkey`) is the primitive for a
-    /// future in-memory, never-persisted per-batch pubkey memoization; it is kept
-    /// deliberately even though no cache is wired up yet.
+    /// [`BatchOwnerCache`](crate::owner_cache::BatchOwnerCache) pairs it with
+    /// `verify_with_pubkey` as an in-memory, never-persisted per-batch key
+    /// cache.
     ///
     /// # Arguments
     ///
@@ -452,6 +632,82 @@ impl Stamp {
     }
 }
 
+/// Version byte leading every [`Stamp::to_cbor`] blob.
+///
+/// Version 1 follows it with a CBOR array of the batch id (byte string), the
+/// encoded [`StampIndex`] and the timestamp (unsigned integers), and the
+/// 65-byte signature (byte string).
+#[cfg(feature = "cbor")]
+pub const STAMP_CBOR_VERSION: u8 = 1;
+
+#[cfg(feature = "cbor")]
+impl Stamp {
+    /// Serializes the stamp to a versioned CBOR blob for storage.
+    ///
+    /// The blob is a [`STAMP_CBOR_VERSION`] byte followed by a CBOR value, so
+    /// stored stamps stay readable as the layout evolves. It is a storage
+    /// format only: [`to_bytes`](Self::to_bytes) stays the canonical wire
+    /// encoding.
+    pub fn to_cbor(&self) -> Vec<u8> {
+        use ciborium::Value;
+
+        let value = Value::Array(alloc::vec![
+            Value::Bytes(self.batch.as_slice().to_vec()),
+            Value::Integer(self.index.encode().into()),
+            Value::Integer(self.timestamp.into()),
+            Value::Bytes(self.sig.as_bytes().to_vec()),
+        ]);
+        let mut blob = alloc::vec![STAMP_CBOR_VERSION];
+        // Writing plain CBOR values into a `Vec` cannot fail.
+        let _ = ciborium::into_writer(&value, &mut blob);
+        blob
+    }
+
+    /// Deserializes a stamp from a [`to_cbor`](Self::to_cbor) blob.
+    ///
+    /// Returns [`StampError::InvalidData`] for an unknown version or a
+    /// malformed blob, and [`StampError::InvalidSignature`] if the signature
+    /// bytes are invalid.
+    pub fn from_cbor(blob: &[u8]) -> Result<Self, StampError> {
+        use ciborium::Value;
+
+        const MALFORMED: StampError = StampError::InvalidData("malformed CBOR stamp");
+
+        let Some((&STAMP_CBOR_VERSION, mut rest)) = blob.split_first() else {
+            return Err(StampError::InvalidData("unsupported CBOR stamp version"));
+        };
+        let value: Value = ciborium::from_reader(&mut rest).map_err(|_| MALFORMED)?;
+        if !rest.is_empty() {
+            return Err(MALFORMED);
+        }
+
+        let Value::Array(fields) = value else {
+            return Err(MALFORMED);
+        };
+        let [
+            Value::Bytes(batch),
+            Value::Integer(index),
+            Value::Integer(timestamp),
+            Value::Bytes(sig),
+        ] = fields.as_slice()
+        else {
+            return Err(MALFORMED);
+        };
+
+        let batch = BatchId::new(batch.as_slice().try_into().map_err(|_| MALFORMED)?);
+        let index = u64::try_from(*index).map_err(|_| MALFORMED)?;
+        let timestamp = u64::try_from(*timestamp).map_err(|_| MALFORMED)?;
+        let sig: [u8; SIG_SIZE] = sig.as_slice().try_into().map_err(|_| MALFORMED)?;
+        let sig = Signature::from_raw_array(&sig).map_err(|_| StampError::InvalidSignature)?;
+        Ok(Self::with_index(
+            batch,
+            StampIndex::decode(index),
+            timestamp,
+            sig,
+        ))
+    }
+}
+
 /// Reads a stamp from its 113 wire bytes: batch id, stamp index, big-endian
 /// timestamp, then the 65-byte signature.
 impl FromCursor for Stamp {
@@ -483,6 +739,136 @@ impl ToWriter for Stamp {
     }
 }
 
+/// Appends each stamp's 113 wire bytes to `out`, back to back.
+///
+/// The bulk persistence form: `stamps.len() * STAMP_SIZE` bytes with no
+/// framing, reserved up front so the buffer grows at most once. Existing
+/// contents of `out` are kept. [`read_stamps`] parses it back, and
+/// [`StampRef::iter`] views it without parsing signatures.
+pub fn write_stamps(stamps: &[Stamp], out: &mut Vec<u8>) {
+    out.reserve(stamps.len().saturating_mul(STAMP_SIZE));
+    let mut w = Writer::new(out);
+    for stamp in stamps {
+        w.put(stamp);
+    }
+}
+
+/// Parses a buffer written by [`write_stamps`] into its stamps, in order.
+///
+/// Returns [`StampError::InvalidData`] if the buffer is not a whole number
+/// of stamps, and [`StampError::InvalidSignature`] for the first stamp whose
+/// signature bytes are invalid.
+pub fn read_stamps(buf: &[u8]) -> Result<Vec<Stamp>, StampError> {
+    if !buf.len().is_multiple_of(STAMP_SIZE) {
+        return Err(StampError::InvalidData(
+            "stamp buffer must be a whole number of 113-byte stamps",
+        ));
+    }
+    buf.chunks_exact(STAMP_SIZE)
+        .map(Stamp::try_from_slice)
+        .collect()
+}
+
+/// A borrowed view of a stamp's 113 wire bytes.
+///
+/// Reads the fields straight from the buffer and leaves the signature
+/// unparsed, so indexing a large buffer of stamps costs no signature
+/// decoding. [`to_owned`](Self::to_owned) parses the full [`Stamp`] for the
+/// ones that need verifying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StampRef<'a> {
+    batch: &'a [u8; BatchId::SIZE],
+    index: &'a [u8; INDEX_SIZE],
+    timestamp: &'a [u8; TIMESTAMP_SIZE],
+    sig: &'a [u8; SIG_SIZE],
+}
+
+impl<'a> StampRef<'a> {
+    /// Views `bytes` as a stamp.
+    ///
+    /// Returns an error if the slice is not exactly 113 bytes. The signature
+    /// is not checked.
+    pub fn try_from_slice(bytes: &'a [u8]) -> Result<Self, StampError> {
+        let mut cur = Cursor::new(bytes);
+        let stamp = Self {
+            batch: take_array(&mut cur)?,
+            index: take_array(&mut cur)?,
+            timestamp: take_array(&mut cur)?,
+            sig: take_array(&mut cur)?,
+        };
+        if !cur.is_empty() {
+            return Err(StampError::InvalidData("stamp must be exactly 113 bytes"));
+        }
+        Ok(stamp)
+    }
+
+    /// Views a buffer of back-to-back stamps, in order.
+    ///
+    /// A trailing partial stamp yields an error as the last item.
+    pub fn iter(buffer: &'a [u8]) -> impl Iterator<Item = Result<Self, StampError>> + 'a {
+        buffer.chunks(STAMP_SIZE).map(Self::try_from_slice)
+    }
+
+    /// Returns the batch ID.
+    #[inline]
+    pub const fn batch(&self) -> BatchId {
+        BatchId::new(*self.batch)
+    }
+
+    /// Returns the stamp index.
+    #[inline]
+    pub const fn stamp_index(&self) -> StampIndex {
+        StampIndex::from_be_bytes(*self.index)
+    }
+
+    /// Returns the collision bucket.
+    #[inline]
+    pub const fn bucket(&self) -> u32 {
+        self.stamp_index().bucket()
+    }
+
+    /// Returns the position within the bucket.
+    #[inline]
+    pub const fn index(&self) -> u32 {
+        self.stamp_index().index()
+    }
+
+    /// Returns the timestamp.
+    #[inline]
+    pub const fn timestamp(&self) -> u64 {
+        u64::from_be_bytes(*self.timestamp)
+    }
+
+    /// Returns the raw `r || s || v` signature bytes, unparsed.
+    #[inline]
+    pub const fn signature_bytes(&self) -> &'a [u8; SIG_SIZE] {
+        self.sig
+    }
+
+    /// Parses the viewed bytes into an owned [`Stamp`].
+    ///
+    /// Returns [`StampError::InvalidSignature`] if the signature bytes are
+    /// invalid.
+    pub fn to_owned(&self) -> Result<Stamp, StampError> {
+        let sig = Signature::from_raw_array(self.sig).map_err(|_| StampError::InvalidSignature)?;
+        Ok(Stamp::with_index(
+            self.batch(),
+            self.stamp_index(),
+            self.timestamp(),
+            sig,
+        ))
+    }
+}
+
+/// Borrows the next `N` bytes of `cur` as an array.
+fn take_array<'a, const N: usize>(cur: &mut Cursor<'a>) -> Result<&'a [u8; N], StampError> {
+    let slice = cur.take_slice(N)?;
+    slice.try_into().map_err(|_| StampError::Underrun {
+        expected: N,
+        available: slice.len(),
+    })
+}
+
 /// The digest that must be signed to create a valid stamp.
 ///
 /// The digest is computed as: `keccak256(chunk_address || batch_id || index || timestamp)`
@@ -590,12 +976,136 @@ impl<'a> arbitrary::Arbitrary<'a> for Stamp {
 mod tests {
     use super::*;
     use alloy_primitives::hex;
+    use nectar_primitives::Mainnet;
     use proptest::prelude::*;
     use proptest_arbitrary_interop::arb;
 
     const TEST_BATCH_ID: &str = "c3387832bb1b88acbcd0ffdb65a08ef077d98c08d4bee576a72dbe3d36761369";
     const TEST_STAMP: &str = "c3387832bb1b88acbcd0ffdb65a08ef077d98c08d4bee576a72dbe3d367613690000cbe5000000000000018921ff0dbb29169df9e6364e26c6ca6b17745c10b9d6a36ea38e204f2e3cc64a8373c0661f5bb0a347c61d8d1689b0dcf8354117686a6a18d08cff927f526de5fc61b2b7491b";
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_stamp_json_uses_hex_strings() {
+        let stamp =
+            Stamp::from_bytes(&hex::decode(TEST_STAMP).unwrap().try_into().unwrap()).unwrap();
+        let json = serde_json::to_value(&stamp).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "batch": format!("0x{TEST_BATCH_ID}"),
+                "index": { "bucket": 0xcbe5, "index": 0 },
+                "timestamp": 0x0000_0189_21ff_0dbb_u64,
+                "sig": format!("0x{}", &TEST_STAMP[2 * 48..]),
+            })
+        );
+        assert_eq!(serde_json::from_value::<Stamp>(json).unwrap(), stamp);
+
+        // A signature of the wrong width is refused.
+        let short = serde_json::json!({
+            "batch": format!("0x{TEST_BATCH_ID}"),
+            "index": { "bucket": 0, "index": 0 },
+            "timestamp": 0,
+            "sig": "0x00",
+        });
+        assert!(serde_json::from_value::<Stamp>(short).is_err());
+    }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn test_stamp_cbor_roundtrip() {
+        let stamp =
+            Stamp::from_bytes(&hex::decode(TEST_STAMP).unwrap().try_into().unwrap()).unwrap();
+        let blob = stamp.to_cbor();
+        assert_eq!(blob[0], STAMP_CBOR_VERSION);
+        assert!(blob.len() < STAMP_SIZE + 16, "{} bytes", blob.len());
+        assert_eq!(Stamp::from_cbor(&blob).unwrap(), stamp);
+
+        let mut unknown = blob.clone();
+        unknown[0] = STAMP_CBOR_VERSION + 1;
+        assert!(matches!(
+            Stamp::from_cbor(&unknown),
+            Err(StampError::InvalidData(_))
+        ));
+        assert!(Stamp::from_cbor(&[]).is_err());
+        assert!(Stamp::from_cbor(&blob[..blob.len() - 1]).is_err());
+        let mut trailing = blob;
+        trailing.push(0);
+        assert!(Stamp::from_cbor(&trailing).is_err());
+    }
+
+    #[test]
+    fn test_stamp_ref_matches_parsed_stamp() {
+        let bytes = hex::decode(TEST_STAMP).unwrap();
+        let stamp = Stamp::try_from_slice(&bytes).unwrap();
+        let view = StampRef::try_from_slice(&bytes).unwrap();
+
+        assert_eq!(view.batch(), stamp.batch());
+        assert_eq!(view.stamp_index(), stamp.stamp_index());
+        assert_eq!(view.bucket(), stamp.bucket());
+        assert_eq!(view.index(), stamp.index());
+        assert_eq!(view.timestamp(), stamp.timestamp());
+        assert_eq!(view.signature_bytes(), &stamp.signature().as_bytes());
+        assert_eq!(view.to_owned().unwrap(), stamp);
+
+        assert!(StampRef::try_from_slice(&bytes[1..]).is_err());
+        let mut long = bytes.clone();
+        long.push(0);
+        assert!(StampRef::try_from_slice(&long).is_err());
+    }
+
+    #[test]
+    fn test_stamp_ref_scans_a_buffer() {
+        let bytes = hex::decode(TEST_STAMP).unwrap();
+        let mut buffer = [bytes.as_slice(), &bytes, &bytes].concat();
+        // A corrupt signature only shows once the stamp is parsed.
+        buffer[2 * STAMP_SIZE - 1] = 0x05;
+        buffer.extend_from_slice(&bytes[..10]);
+
+        let views: Vec<_> = StampRef::iter(&buffer).collect();
+        let view = |i: usize| views[i].clone().unwrap();
+        assert_eq!(views.len(), 4);
+        let stamp = Stamp::try_from_slice(&bytes).unwrap();
+        assert_eq!(view(0).to_owned(), Ok(stamp.clone()));
+        assert_eq!(view(1).batch(), stamp.batch());
+        assert_eq!(view(1).to_owned(), Err(StampError::InvalidSignature));
+        assert_eq!(view(2).to_owned(), Ok(stamp));
+        assert!(views[3].is_err());
+    }
+
+    #[test]
+    fn test_write_stamps_round_trips_a_contiguous_buffer() {
+        let template = Stamp::try_from_slice(&hex::decode(TEST_STAMP).unwrap()).unwrap();
+        let stamps: Vec<Stamp> = (0..1000u32)
+            .map(|i| {
+                Stamp::with_index(
+                    template.batch(),
+                    StampIndex::new(i, i / 7),
+                    u64::from(i) << 20,
+                    *template.signature(),
+                )
+            })
+            .collect();
+
+        let mut buffer = Vec::new();
+        write_stamps(&stamps, &mut buffer);
+        assert_eq!(buffer.len(), stamps.len() * STAMP_SIZE);
+        assert_eq!(&buffer[..STAMP_SIZE], stamps[0].to_bytes().as_slice());
+        assert_eq!(read_stamps(&buffer).unwrap(), stamps);
+
+        // Appends after what the buffer already holds.
+        write_stamps(&stamps[..2], &mut buffer);
+        assert_eq!(buffer.len(), 1002 * STAMP_SIZE);
+        assert_eq!(read_stamps(&buffer).unwrap()[1000..], stamps[..2]);
+
+        assert_eq!(read_stamps(&[]).unwrap(), Vec::<Stamp>::new());
+        assert!(matches!(
+            read_stamps(&buffer[..STAMP_SIZE + 1]),
+            Err(StampError::InvalidData(_))
+        ));
+        buffer[2 * STAMP_SIZE - 1] = 0x05;
+        assert_eq!(read_stamps(&buffer), Err(StampError::InvalidSignature));
+    }
+
     #[test]
     fn test_stamp_index_encode_decode() {
         let idx = StampIndex::new(0x1234, 0x5678);
@@ -605,6 +1115,38 @@ mod tests {
         assert_eq!(decoded, idx);
     }
 
+    #[test]
+    fn stamp_index_encoding_keeps_the_u32_extremes() {
+        let max = StampIndex::new(u32::MAX, u32::MAX);
+        assert_eq!(max.encode(), u64::MAX);
+        assert_eq!(StampIndex::decode(u64::MAX), max);
+
+        // A full index never carries into the bucket, nor a full bucket
+        // into the index.
+        let full_index = StampIndex::new(0, u32::MAX);
+        assert_eq!(StampIndex::decode(full_index.encode()), full_index);
+        let full_bucket = StampIndex::new(u32::MAX, 0);
+        assert_eq!(
+            StampIndex::from_be_bytes(full_bucket.to_be_bytes()),
+            full_bucket
+        );
+    }
+
+    #[test]
+    fn checked_new_rejects_buckets_beyond_the_depth() {
+        let depth = BucketDepth::<Mainnet>::new(16).unwrap();
+        assert_eq!(
+            StampIndex::checked_new(0xffff, u32::MAX, depth),
+            Some(StampIndex::new(0xffff, u32::MAX))
+        );
+        assert_eq!(StampIndex::checked_new(0x1_0000, 0, depth), None);
+        assert_eq!(StampIndex::checked_new(u32::MAX, 0, depth), None);
+
+        // At the widest depth every bucket is addressable.
+        let widest = BucketDepth::<Mainnet>::new(BucketDepth::<Mainnet>::MAX).unwrap();
+        assert!(StampIndex::checked_new(u32::MAX, 0, widest).is_some());
+    }
+
     #[test]
     fn test_stamp_index_bytes() {
         let idx = StampIndex::new(0x1234, 0x5678);
@@ -628,7 +1170,7 @@ mod tests {
         let bytes = hex::decode(TEST_STAMP).unwrap();
         let stamp = Stamp::try_from_slice(&bytes).unwrap();
 
-        let expected_batch = BatchId::from_slice(&hex::decode(TEST_BATCH_ID).unwrap());
+        let expected_batch = BatchId::from_hex(TEST_BATCH_ID).unwrap();
         assert_eq!(stamp.batch(), expected_batch);
         assert_eq!(stamp.bucket(), 52197); // 0x0000cbe5
         assert_eq!(stamp.index(), 0);
@@ -705,6 +1247,67 @@ mod tests {
         assert_eq!(recovered, expected_owner);
     }
 
+    #[test]
+    fn recovery_id_conventions_round_trip_to_bee_form() {
+        // Go interop vector: bee writes v as 27/28 (0x1c here).
+        let go = hex::decode(
+            "000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000003496cb9ac06221d39c3f6a7dd3b9c2301c1f923162b90d5443e42023f34ff908945b0da1c297190f111b7c6ebc828648ead8f7fce06c0364cb5a833410230c5c01c"
+        ).unwrap();
+        let stamp = Stamp::try_from_slice(&go).unwrap();
+        assert_eq!((stamp.normalized_v(), stamp.eth_v()), (1, 28));
+        assert_eq!(stamp.to_bytes().as_slice(), go.as_slice());
+
+        // The same signature with a raw 0/1 recovery id parses to the same
+        // stamp and serializes back in bee's form.
+        let mut raw = go;
+        raw[STAMP_SIZE - 1] = stamp.normalized_v();
+        let from_raw = Stamp::try_from_slice(&raw).unwrap();
+        assert_eq!(from_raw, stamp);
+        assert_eq!(from_raw.to_bytes()[STAMP_SIZE - 1], stamp.eth_v());
+
+        // Both parities map consistently.
+        raw[STAMP_SIZE - 1] = 0;
+        let even = Stamp::try_from_slice(&raw).unwrap();
+        assert_eq!((even.normalized_v(), even.eth_v()), (0, 27));
+        assert_eq!(even.to_bytes()[STAMP_SIZE - 1], 0x1b);
+    }
+
+    #[test]
+    fn test_compact_roundtrip() {
+        // Go interop vector: its signature is low-s, so the compact form
+        // round-trips to the identical stamp.
+        let chunk_address = ChunkAddress::new(
+            hex::decode("0000000000000000000000000000000000000000000000000000000000000002")
+                .unwrap()
+                .try_into()
+                .unwrap(),
+        );
+        let stamp = Stamp::try_from_slice(&hex::decode(
+            "000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000003496cb9ac06221d39c3f6a7dd3b9c2301c1f923162b90d5443e42023f34ff908945b0da1c297190f111b7c6ebc828648ead8f7fce06c0364cb5a833410230c5c01c"
+        ).unwrap())
+        .unwrap();
+
+        let compact = stamp.to_bytes_compact();
+        assert_eq!(compact.len(), COMPACT_STAMP_SIZE);
+        assert_eq!(compact[..48], stamp.to_bytes()[..48]);
+
+        let decoded = Stamp::from_bytes_compact(&compact).unwrap();
+        assert_eq!(decoded, stamp);
+        assert_eq!(
+            decoded.recover_signer(&chunk_address).unwrap(),
+            stamp.recover_signer(&chunk_address).unwrap()
+        );
+
+        // Flipping the packed parity bit recovers someone else.
+        let mut flipped = compact;
+        flipped[COMPACT_STAMP_SIZE - 32] ^= 0x80;
+        let other = Stamp::from_bytes_compact(&flipped).unwrap();
+        assert_ne!(
+            other.recover_signer(&chunk_address).ok(),
+            Some(stamp.recover_signer(&chunk_address).unwrap())
+        );
+    }
+
     /// Test verify method using the Go interop test vector.
     #[test]
     fn test_verify() {
@@ -727,6 +1330,44 @@ mod tests {
         // Verify with wrong owner should fail
         let result = stamp.verify(&chunk_address, wrong_owner);
         assert!(matches!(result, Err(StampError::OwnerMismatch { .. })));
+
+        // The auditing form hands back the recovered signer either way.
+        assert_eq!(
+            stamp.verify_returning_owner(&chunk_address, expected_owner),
+            Ok(expected_owner)
+        );
+        assert_eq!(
+            stamp.verify_returning_owner(&chunk_address, wrong_owner),
+            Err(StampError::OwnerMismatch {
+                expected: wrong_owner,
+                actual: expected_owner
+            })
+        );
+    }
+
+    #[test]
+    fn verify_ct_agrees_with_verify() {
+        let chunk_address = ChunkAddress::new(
+            hex::decode("0000000000000000000000000000000000000000000000000000000000000002")
+                .unwrap()
+                .try_into()
+                .unwrap(),
+        );
+        let stamp = Stamp::try_from_slice(&hex::decode(
+            "000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000003496cb9ac06221d39c3f6a7dd3b9c2301c1f923162b90d5443e42023f34ff908945b0da1c297190f111b7c6ebc828648ead8f7fce06c0364cb5a833410230c5c01c"
+        ).unwrap())
+        .unwrap();
+        let owner: Address = "8d3766440f0d7b949a5e32995d09619a7f86e632".parse().unwrap();
+        let mut near_miss = owner;
+        near_miss.0[19] ^= 1;
+
+        for candidate in [owner, near_miss, Address::ZERO] {
+            assert_eq!(
+                stamp.verify_ct(&chunk_address, candidate),
+                stamp.verify(&chunk_address, candidate)
+            );
+        }
+        assert!(stamp.verify_ct(&chunk_address, owner).is_ok());
     }
 
     /// Test recover_pubkey using the Go interop test vector.
@@ -1,6 +1,8 @@
 //! Batch storage traits for persisting batch data.
 
-use crate::{Batch, BatchId, PostageContext};
+use alloy_primitives::U256;
+
+use crate::{Batch, BatchId, NormalisedBalance, PostageContext, ttl_for_balance};
 
 /// A trait for storing and retrieving batches.
 ///
@@ -91,6 +93,50 @@ pub trait BatchStoreExt: BatchStore {
 
         Ok(batch)
     }
+
+    /// Returns the `n` batches with the least time to live, soonest first,
+    /// each with its remaining blocks at `price_per_chunk` PLUR per chunk per
+    /// block.
+    ///
+    /// The remaining per-chunk balance is measured against the store's
+    /// [`context`](BatchStore::context); expired batches come first with zero
+    /// blocks left. Ties are broken by batch id, so the order is stable.
+    fn expiring_soonest(
+        &self,
+        price_per_chunk: u64,
+        n: usize,
+    ) -> Result<Vec<(BatchId, u64)>, Self::Error> {
+        let total_amount = U256::from(self.context()?.total_amount());
+        let mut remaining = Vec::new();
+        for id in self.batch_ids()? {
+            if let Some(batch) = self.get(&id)? {
+                let value = NormalisedBalance::new(U256::from(batch.value()));
+                remaining.push((value.per_chunk(total_amount), id));
+            }
+        }
+        remaining.sort_unstable();
+        Ok(remaining
+            .into_iter()
+            .take(n)
+            .map(|(balance, id)| (id, ttl_for_balance(price_per_chunk, balance)))
+            .collect())
+    }
+
+    /// Removes every batch expired under the store's
+    /// [`context`](BatchStore::context), returning the ids removed.
+    fn prune_expired(&self) -> Result<Vec<BatchId>, Self::Error> {
+        let total_amount = self.context()?.total_amount();
+        let mut pruned = Vec::new();
+        for id in self.batch_ids()? {
+            let expired = self
+                .get(&id)?
+                .is_some_and(|batch| batch.is_expired(total_amount));
+            if expired && self.remove(&id)? {
+                pruned.push(id);
+            }
+        }
+        Ok(pruned)
+    }
 }
 
 // Blanket implementation
@@ -131,3 +177,61 @@ pub enum BatchStoreError<E: std::error::Error> {
     #[error("store error: {0}")]
     Store(#[from] E),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BucketDepth;
+    use crate::test_utils::MemoryStore;
+    use alloy_primitives::Address;
+
+    /// A store at total amount 1000 holding batch `[i; 32]` with value
+    /// `values[i]`.
+    fn store(values: &[u128]) -> MemoryStore {
+        let bucket_depth = BucketDepth::new(16).unwrap();
+        MemoryStore::new(
+            PostageContext::new(100, 1_000),
+            (0u8..).zip(values).map(|(i, &value)| {
+                Batch::new(
+                    BatchId::new([i; 32]),
+                    value,
+                    0,
+                    Address::ZERO,
+                    20,
+                    bucket_depth,
+                    false,
+                )
+            }),
+        )
+    }
+
+    #[test]
+    fn expiring_soonest_orders_by_remaining_balance() {
+        let store = store(&[5_000, 900, 1_100, 3_000]);
+        let id = |i| BatchId::new([i; 32]);
+
+        // 10 PLUR per chunk per block: 100 left is 10 blocks.
+        assert_eq!(
+            store.expiring_soonest(10, 3).unwrap(),
+            [(id(1), 0), (id(2), 10), (id(3), 200)]
+        );
+        assert_eq!(store.expiring_soonest(10, 10).unwrap().len(), 4);
+        assert!(store.expiring_soonest(10, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn prune_expired_removes_only_dead_batches() {
+        // A value equal to the total amount is expired.
+        let store = store(&[5_000, 900, 1_000, 1_001]);
+
+        assert_eq!(
+            store.prune_expired().unwrap(),
+            [BatchId::new([1; 32]), BatchId::new([2; 32])]
+        );
+        assert_eq!(
+            store.batch_ids().unwrap(),
+            [BatchId::new([0; 32]), BatchId::new([3; 32])]
+        );
+        assert!(store.prune_expired().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,249 @@
+//! Ordered stream verification.
+//!
+//! [`verify_stream`] adapts a stream of `(stamp, address)` pairs into a stream
+//! of recovered signers, one result per input, in submission order. The
+//! adapter is pull-driven: an input is only taken from upstream when the
+//! consumer asks for the next result, so backpressure falls out of the
+//! `Stream` contract with no channel or buffer to size.
+//!
+//! The module depends on `futures-core` alone and never spawns, so it runs
+//! under any executor: tokio, async-std and smol callers share this one
+//! path, with no runtime-specific channel or feature to select. The returned
+//! stream is `Unpin`, and `Send` whenever its upstream is, so it can be
+//! moved into any runtime's spawn.
+//!
+//! [`verify_stream_for_batches`] additionally rejects stamps for batches
+//! outside an allowed set, for a node that only accepts chunks for the
+//! batches it tracks.
+//!
+//! ```
+//! use futures::{StreamExt, stream};
+//! use nectar_postage::streaming::verify_stream;
+//! # use alloy_signer::SignerSync;
+//! # use alloy_signer_local::PrivateKeySigner;
+//! # use nectar_postage::{BatchId, Stamp, StampDigest, StampIndex};
+//! # use nectar_primitives::ChunkAddress;
+//! # let signer = PrivateKeySigner::random();
+//! # let address = ChunkAddress::from([7u8; 32]);
+//! # let index = StampIndex::new(0, 0);
+//! # let prehash = StampDigest::new(address, BatchId::ZERO, index, 1).to_prehash();
+//! # let sig = signer.sign_message_sync(prehash.as_slice()).unwrap();
+//! # let stamp = Stamp::with_index(BatchId::ZERO, index, 1, sig);
+//!
+//! # nectar_testing::run(async {
+//! let mut results = verify_stream(stream::iter([(stamp, address)]));
+//! assert_eq!(results.next().await.unwrap().unwrap(), signer.address());
+//! assert!(results.next().await.is_none());
+//! # });
+//! ```
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeSet;
+use core::fmt;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloy_primitives::Address;
+use futures_core::Stream;
+use nectar_primitives::ChunkAddress;
+use thiserror::Error;
+
+use crate::{BatchId, Stamp, StampError};
+
+/// Errors yielded by [`verify_stream`] and [`verify_stream_for_batches`], one
+/// per rejected input.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum StreamVerifyError {
+    /// The stamp failed signature recovery.
+    #[error(transparent)]
+    Stamp(#[from] StampError),
+
+    /// The stamp is for a batch outside the allowed set.
+    #[error("stamp for unknown batch {0}")]
+    UnknownBatch(BatchId),
+}
+
+/// Verifies a stream of stamps, yielding each recovered signer in input order.
+///
+/// Each item is verified when it is pulled, so at most one input is held at a
+/// time and a slow consumer throttles the producer. A failed item yields an
+/// error without ending the stream.
+pub fn verify_stream<St>(stream: St) -> VerifyStream<St>
+where
+    St: Stream<Item = (Stamp, ChunkAddress)>,
+{
+    VerifyStream {
+        inner: Box::pin(stream),
+        allowed: None,
+    }
+}
+
+/// Verifies a stream of stamps as [`verify_stream`] does, rejecting stamps
+/// whose batch is not in `allowed`.
+///
+/// The batch is checked before the signature, so a stamp for an unknown
+/// batch yields [`StreamVerifyError::UnknownBatch`] without a recovery.
+pub fn verify_stream_for_batches<St>(stream: St, allowed: BTreeSet<BatchId>) -> VerifyStream<St>
+where
+    St: Stream<Item = (Stamp, ChunkAddress)>,
+{
+    VerifyStream {
+        inner: Box::pin(stream),
+        allowed: Some(allowed),
+    }
+}
+
+/// Stream returned by [`verify_stream`] and [`verify_stream_for_batches`].
+#[must_use = "streams do nothing unless polled"]
+pub struct VerifyStream<St> {
+    inner: Pin<Box<St>>,
+    /// The batches accepted, or `None` to accept any.
+    allowed: Option<BTreeSet<BatchId>>,
+}
+
+impl<St> Stream for VerifyStream<St>
+where
+    St: Stream<Item = (Stamp, ChunkAddress)>,
+{
+    type Item = Result<Address, StreamVerifyError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let allowed = &this.allowed;
+        this.inner.as_mut().poll_next(cx).map(|next| {
+            next.map(|(stamp, address)| {
+                if let Some(allowed) = allowed
+                    && !allowed.contains(&stamp.batch())
+                {
+                    return Err(StreamVerifyError::UnknownBatch(stamp.batch()));
+                }
+                stamp.recover_signer(&address).map_err(Into::into)
+            })
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<St> fmt::Debug for VerifyStream<St> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VerifyStream").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use alloy_signer_local::PrivateKeySigner;
+    use futures::{StreamExt, stream};
+    use nectar_testing::{Drive, run};
+
+    use crate::test_utils::sign_stamp;
+    use crate::{BatchId, StampIndex};
+
+    fn signed(signer: &PrivateKeySigner, address: ChunkAddress, index: u32) -> Stamp {
+        signed_for(signer, BatchId::ZERO, address, index)
+    }
+
+    fn signed_for(
+        signer: &PrivateKeySigner,
+        batch: BatchId,
+        address: ChunkAddress,
+        index: u32,
+    ) -> Stamp {
+        sign_stamp(signer, batch, address, StampIndex::new(0, index), 1)
+    }
+
+    #[test]
+    fn results_follow_submission_order() {
+        let signers: Vec<_> = (0..8).map(|_| PrivateKeySigner::random()).collect();
+        let inputs: Vec<_> = signers
+            .iter()
+            .zip(0..)
+            .map(|(signer, i)| {
+                let address = ChunkAddress::from(B256::random());
+                (signed(signer, address, i), address)
+            })
+            .collect();
+
+        let results: Vec<_> = run(verify_stream(stream::iter(inputs)).collect());
+        let recovered: Vec<_> = results.into_iter().map(Result::unwrap).collect();
+        let expected: Vec<_> = signers.iter().map(PrivateKeySigner::address).collect();
+        assert_eq!(recovered, expected);
+    }
+
+    #[test]
+    fn a_bad_item_errors_without_ending_the_stream() {
+        let signer = PrivateKeySigner::random();
+        let good = ChunkAddress::from(B256::random());
+        let stamp = signed(&signer, good, 0);
+        // A zero `r` scalar parses but can never recover a key.
+        let mut bad = stamp.to_bytes();
+        bad[48..80].fill(0);
+        let bad = Stamp::from_bytes(&bad).unwrap();
+
+        let results: Vec<_> =
+            run(verify_stream(stream::iter([(bad, good), (stamp, good)])).collect());
+        assert_eq!(
+            results,
+            [
+                Err(StreamVerifyError::Stamp(StampError::InvalidSignature)),
+                Ok(signer.address()),
+            ]
+        );
+    }
+
+    #[test]
+    fn stamps_for_unknown_batches_are_rejected() {
+        let signer = PrivateKeySigner::random();
+        let address = ChunkAddress::from(B256::random());
+        let tracked = BatchId::new([1; 32]);
+        let untracked = BatchId::new([2; 32]);
+
+        let inputs = [
+            (signed_for(&signer, untracked, address, 0), address),
+            (signed_for(&signer, tracked, address, 0), address),
+        ];
+        let allowed = BTreeSet::from([tracked]);
+        let results: Vec<_> =
+            run(verify_stream_for_batches(stream::iter(inputs), allowed).collect());
+        assert_eq!(
+            results,
+            [
+                Err(StreamVerifyError::UnknownBatch(untracked)),
+                Ok(signer.address()),
+            ]
+        );
+    }
+
+    #[test]
+    fn verify_stream_moves_into_any_runtime() {
+        fn assert_spawnable<T: Stream + Send + Unpin + 'static>(_: &T) {}
+        assert_spawnable(&verify_stream(stream::empty::<(Stamp, ChunkAddress)>()));
+    }
+
+    #[test]
+    fn upstream_is_pulled_one_item_per_result() {
+        let signer = PrivateKeySigner::random();
+        let address = ChunkAddress::from(B256::random());
+        let stamp = signed(&signer, address, 0);
+
+        let pulled = core::cell::Cell::new(0_usize);
+        let upstream = stream::repeat_with(|| {
+            pulled.set(pulled.get() + 1);
+            (stamp.clone(), address)
+        });
+        let mut results = verify_stream(upstream);
+
+        for expected in 1..=3 {
+            let mut next = Drive::new(results.next());
+            assert!(next.poll().is_ready());
+            drop(next);
+            assert_eq!(pulled.get(), expected);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Fixtures shared by the crate's unit tests.
+
+use alloy_signer::SignerSync;
+use alloy_signer_local::PrivateKeySigner;
+use nectar_primitives::ChunkAddress;
+
+#[cfg(feature = "std")]
+use crate::{Batch, BatchStore};
+use crate::{BatchId, Stamp, StampDigest, StampIndex, StampValidator};
+use crate::{PostageContext, StampError};
+#[cfg(feature = "std")]
+use core::cell::{Cell, RefCell};
+#[cfg(feature = "std")]
+use core::convert::Infallible;
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// Signs a stamp for `address` under `batch` at `index` and `timestamp`.
+pub(crate) fn sign_stamp(
+    signer: &PrivateKeySigner,
+    batch: BatchId,
+    address: ChunkAddress,
+    index: StampIndex,
+    timestamp: u64,
+) -> Stamp {
+    let prehash = StampDigest::new(address, batch, index, timestamp).to_prehash();
+    let sig = signer.sign_message_sync(prehash.as_slice()).unwrap();
+    Stamp::with_index(batch, index, timestamp, sig)
+}
+
+/// A stamp in the first slot of `address`'s bucket at bucket depth 16,
+/// timestamped `timestamp`.
+pub(crate) fn stamp_at(
+    signer: &PrivateKeySigner,
+    batch: BatchId,
+    address: ChunkAddress,
+    timestamp: u64,
+) -> Stamp {
+    let index = StampIndex::new(crate::calculate_bucket(&address, 16), 0);
+    sign_stamp(signer, batch, address, index, timestamp)
+}
+
+/// [`stamp_at`] timestamp 1.
+pub(crate) fn stamp(signer: &PrivateKeySigner, batch: BatchId, address: ChunkAddress) -> Stamp {
+    stamp_at(signer, batch, address, 1)
+}
+
+/// Accepts everything, so only the batch-aware checks of
+/// [`StampValidator::validate_with_state`] run.
+pub(crate) struct Permissive;
+
+impl StampValidator for Permissive {
+    type Error = StampError;
+
+    fn validate(&self, _: &Stamp, _: &ChunkAddress, _: &PostageContext) -> Result<(), StampError> {
+        Ok(())
+    }
+}
+
+/// An in-memory [`BatchStore`] at a settable chain context.
+#[cfg(feature = "std")]
+pub(crate) struct MemoryStore {
+    batches: RefCell<BTreeMap<BatchId, Batch>>,
+    context: Cell<PostageContext>,
+}
+
+#[cfg(feature = "std")]
+impl MemoryStore {
+    /// A store at `context` holding `batches`.
+    pub(crate) fn new(context: PostageContext, batches: impl IntoIterator<Item = Batch>) -> Self {
+        Self {
+            batches: RefCell::new(
+                batches
+                    .into_iter()
+                    .map(|batch| (batch.id(), batch))
+                    .collect(),
+            ),
+            context: Cell::new(context),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl BatchStore for MemoryStore {
+    type Error = Infallible;
+
+    fn get(&self, id: &BatchId) -> Result<Option<Batch>, Infallible> {
+        Ok(self.batches.borrow().get(id).cloned())
+    }
+    fn put(&self, batch: Batch) -> Result<(), Infallible> {
+        self.batches.borrow_mut().insert(batch.id(), batch);
+        Ok(())
+    }
+    fn remove(&self, id: &BatchId) -> Result<bool, Infallible> {
+        Ok(self.batches.borrow_mut().remove(id).is_some())
+    }
+    fn contains(&self, id: &BatchId) -> Result<bool, Infallible> {
+        Ok(self.batches.borrow().contains_key(id))
+    }
+    fn context(&self) -> Result<PostageContext, Infallible> {
+        Ok(self.context.get())
+    }
+    fn set_context(&self, context: PostageContext) -> Result<(), Infallible> {
+        self.context.set(context);
+        Ok(())
+    }
+    fn batch_ids(&self) -> Result<Vec<BatchId>, Infallible> {
+        Ok(self.batches.borrow().keys().copied().collect())
+    }
+    fn count(&self) -> Result<usize, Infallible> {
+        Ok(self.batches.borrow().len())
+    }
+}
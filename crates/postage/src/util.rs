@@ -2,6 +2,8 @@
 
 use nectar_primitives::ChunkAddress;
 
+use crate::StampError;
+
 /// Returns the current timestamp in nanoseconds since the Unix epoch.
 ///
 /// This is used when creating stamps to record when they were issued.
@@ -33,6 +35,10 @@ pub const fn current_timestamp() -> u64 {
     0
 }
 
+/// Largest bucket depth [`calculate_bucket`] accepts: the bit width of the
+/// leading address word a bucket is cut from.
+pub const BUCKET_DEPTH_MAX: u8 = 32;
+
 /// Calculates which collision bucket a chunk belongs to based on its address.
 ///
 /// The bucket is determined by taking the first `bucket_depth` bits of the
@@ -49,10 +55,11 @@ pub const fn current_timestamp() -> u64 {
 ///
 /// # Panics
 ///
-/// `bucket_depth` must be in `1..=32`: the implementation shifts a `u32`
-/// right by `32 - bucket_depth`, so `bucket_depth == 0` overflows the shift
-/// (and values above 32 overflow the subtraction), which panics with
-/// overflow checks enabled and yields an unspecified value without them.
+/// `bucket_depth` must be in `1..=32` ([`BUCKET_DEPTH_MAX`]): the
+/// implementation shifts a `u32` right by `32 - bucket_depth`, so
+/// `bucket_depth == 0` overflows the shift (and values above 32 overflow the
+/// subtraction), which panics with overflow checks enabled, fails the build
+/// in a `const` context, and yields an unspecified value otherwise.
 /// Callers validate the batch geometry (e.g. `nectar-postage-usage` rejects
 /// `bucket_depth == 0` at decode) before reaching this function.
 ///
@@ -66,14 +73,79 @@ pub const fn current_timestamp() -> u64 {
 /// let bucket = calculate_bucket(&address, 16);
 /// assert_eq!(bucket, 0xCBE5);
 /// ```
+///
+/// The function is `const` and needs no `std`, so a bucket can be fixed at
+/// compile time, for example in an embedded signer:
+///
+/// ```
+/// use nectar_postage::calculate_bucket;
+/// use nectar_primitives::ChunkAddress;
+///
+/// const BUCKET: u32 = calculate_bucket(&ChunkAddress::new([0xAB; 32]), 16);
+/// assert_eq!(BUCKET, 0xABAB);
+/// ```
 #[inline]
-#[allow(clippy::indexing_slicing, clippy::unwrap_used)] // ChunkAddress is a fixed 32-byte array: `[0..4]` and the 4-byte `try_into` are infallible
 #[allow(clippy::arithmetic_side_effects)] // `32 - bucket_depth` underflow is the documented `# Panics` contract (`bucket_depth` in 1..=32)
-pub fn calculate_bucket(address: &ChunkAddress, bucket_depth: u8) -> u32 {
-    // Take the first 4 bytes as a big-endian u32
-    let leading = u32::from_be_bytes(address.as_bytes()[0..4].try_into().unwrap());
+pub const fn calculate_bucket(address: &ChunkAddress, bucket_depth: u8) -> u32 {
+    // Take the first 4 bytes as a big-endian u32. ChunkAddress is a fixed
+    // 32-byte array, so the leading word is always present.
+    let leading = match address.as_bytes().first_chunk::<4>() {
+        Some(word) => u32::from_be_bytes(*word),
+        None => 0,
+    };
     // Shift right to get only the top `bucket_depth` bits
-    leading >> (32 - bucket_depth)
+    leading >> (BUCKET_DEPTH_MAX - bucket_depth)
+}
+
+/// Calculates the collision bucket of every address in `addresses` in one
+/// pass, writing `out[i]` for `addresses[i]`.
+///
+/// Produces the same buckets as [`calculate_bucket`], but hoists the shift
+/// out of the loop so the leading-word extraction compiles to a tight,
+/// vectorizable body. Use it when preparing stamps for a whole upload at once.
+///
+/// # Errors
+///
+/// Returns [`StampError::InvalidBucketDepth`] when `bucket_depth` is outside
+/// `1..=BUCKET_DEPTH_MAX`, and [`StampError::InvalidData`] when `out` is not
+/// exactly as long as `addresses`. Nothing is written in either case.
+///
+/// # Example
+///
+/// ```
+/// use nectar_postage::{calculate_bucket, calculate_buckets};
+/// use nectar_primitives::ChunkAddress;
+///
+/// let addresses = [ChunkAddress::new([0xCB; 32]), ChunkAddress::new([0x01; 32])];
+/// let mut buckets = [0u32; 2];
+/// calculate_buckets(&addresses, 16, &mut buckets)?;
+/// assert_eq!(buckets, [0xCBCB, 0x0101]);
+/// assert_eq!(buckets[1], calculate_bucket(&addresses[1], 16));
+/// # Ok::<(), nectar_postage::StampError>(())
+/// ```
+#[inline]
+pub fn calculate_buckets(
+    addresses: &[ChunkAddress],
+    bucket_depth: u8,
+    out: &mut [u32],
+) -> Result<(), StampError> {
+    let shift = match BUCKET_DEPTH_MAX.checked_sub(bucket_depth) {
+        Some(shift) if bucket_depth > 0 => u32::from(shift),
+        _ => return Err(StampError::InvalidBucketDepth { bucket_depth }),
+    };
+    if out.len() != addresses.len() {
+        return Err(StampError::InvalidData("one bucket slot per address"));
+    }
+    for (bucket, address) in out.iter_mut().zip(addresses) {
+        // ChunkAddress is a fixed 32-byte array, so the leading word is
+        // always present.
+        let leading = address
+            .as_bytes()
+            .first_chunk::<4>()
+            .map_or(0, |word| u32::from_be_bytes(*word));
+        *bucket = leading.checked_shr(shift).unwrap_or(0);
+    }
+    Ok(())
 }
 
 /// Context for postage validation.
@@ -128,9 +200,18 @@ impl PostageContext {
     }
 }
 
+#[cfg(any(test, feature = "arbitrary"))]
+impl<'a> arbitrary::Arbitrary<'a> for PostageContext {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        // Every block and payout pair is a reachable chain state.
+        Ok(Self::new(u.arbitrary()?, u.arbitrary()?))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_calculate_bucket() {
@@ -150,6 +231,53 @@ mod tests {
         assert_eq!(calculate_bucket(&address, 4), 0xC);
     }
 
+    #[test]
+    fn calculate_bucket_evaluates_at_compile_time() {
+        const ADDRESS: ChunkAddress = ChunkAddress::new([0xCB; 32]);
+        const FULL: u32 = calculate_bucket(&ADDRESS, BUCKET_DEPTH_MAX);
+        const _: () = assert!(calculate_bucket(&ADDRESS, 1) == 1);
+        assert_eq!(FULL, 0xCBCB_CBCB);
+        assert_eq!(FULL, calculate_bucket(&ADDRESS, 32));
+    }
+
+    proptest! {
+        /// The batched path agrees with the scalar one at every depth.
+        #[test]
+        fn calculate_buckets_matches_scalar(
+            addresses in proptest::collection::vec(any::<[u8; 32]>(), 0..64),
+            bucket_depth in 1u8..=32,
+        ) {
+            let addresses: Vec<_> = addresses.into_iter().map(ChunkAddress::new).collect();
+            let mut buckets = vec![0; addresses.len()];
+            calculate_buckets(&addresses, bucket_depth, &mut buckets).unwrap();
+            for (address, bucket) in addresses.iter().zip(&buckets) {
+                prop_assert_eq!(*bucket, calculate_bucket(address, bucket_depth));
+            }
+        }
+    }
+
+    #[test]
+    fn calculate_buckets_rejects_a_mismatched_output() {
+        let addresses = [ChunkAddress::new([0xFF; 32]); 3];
+        let mut out = [7u32; 2];
+        assert_eq!(
+            calculate_buckets(&addresses, 8, &mut out),
+            Err(StampError::InvalidData("one bucket slot per address"))
+        );
+        assert_eq!(out, [7; 2]);
+    }
+
+    #[test]
+    fn calculate_buckets_rejects_an_invalid_depth() {
+        let addresses = [ChunkAddress::new([0xFF; 32]); 2];
+        for bucket_depth in [0, BUCKET_DEPTH_MAX + 1] {
+            assert_eq!(
+                calculate_buckets(&addresses, bucket_depth, &mut [0u32; 2]),
+                Err(StampError::InvalidBucketDepth { bucket_depth })
+            );
+        }
+    }
+
     #[test]
     fn test_chain_state() {
         let mut state = PostageContext::new(100, 5000);
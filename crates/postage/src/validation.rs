@@ -1,16 +1,19 @@
 //! Stamp validation traits and utilities.
 
-use crate::{PostageContext, Stamp, StampError};
-use nectar_primitives::ChunkAddress;
-
-#[cfg(any(test, feature = "std"))]
-use crate::Batch;
+use crate::{Batch, PostageContext, Stamp, StampError};
+use nectar_primitives::{ChunkAddress, SwarmSpec};
 
+#[cfg(all(test, not(feature = "std")))]
+use crate::BatchId;
 #[cfg(test)]
-use crate::{BatchId, StampIndex};
+use crate::StampIndex;
 
 #[cfg(feature = "std")]
-use crate::{BatchStore, BatchStoreExt};
+use crate::{BatchId, BatchStore, BatchStoreExt, VerifyingKey};
+#[cfg(feature = "std")]
+use alloy_signer::utils::public_key_to_address;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
 
 /// A trait for validating postage stamps.
 ///
@@ -73,7 +76,10 @@ pub trait StampValidator {
     /// - The chunk address matches the expected bucket
     ///
     /// The default implementation calls `validate`, but implementations may
-    /// override this for performance.
+    /// override this for performance. An implementation whose `validate`
+    /// checks the signature should override it, so that
+    /// [`validate_with_state`](Self::validate_with_state) recovers the signer
+    /// once rather than twice.
     fn validate_structure(
         &self,
         stamp: &Stamp,
@@ -82,6 +88,65 @@ pub trait StampValidator {
     ) -> Result<(), Self::Error> {
         self.validate(stamp, address, state)
     }
+
+    /// Validates a stamp against an already-resolved `batch`, rejecting it
+    /// if the batch has expired under `state`.
+    ///
+    /// A storing node must not accept chunks stamped by a dead batch, however
+    /// well-formed the stamp. The implementor's own policy runs first, through
+    /// [`validate_structure`](Self::validate_structure), so confirmation depth
+    /// and timestamp bounds still apply. Then `batch` must be live under
+    /// `state` and pass [`Batch::verify_stamp`]: the stamp names `batch`, its
+    /// index and bucket fit, and the batch owner signed it. That last check
+    /// recovers the signer, so a `validate_structure` that skips the
+    /// signature keeps this to one recovery.
+    ///
+    /// # Errors
+    ///
+    /// The errors of [`validate_structure`](Self::validate_structure), else
+    /// [`StampError::BatchExpired`] when the batch balance is at or below the
+    /// cumulative payout, else the errors of [`Batch::verify_stamp`].
+    fn validate_with_state<S: SwarmSpec>(
+        &self,
+        stamp: &Stamp,
+        address: &ChunkAddress,
+        batch: &Batch<S>,
+        state: &PostageContext,
+    ) -> Result<(), Self::Error>
+    where
+        Self: Sized,
+    {
+        self.validate_structure(stamp, address, state)?;
+        check_live_stamp(stamp, address, batch, state).map_err(Self::Error::from)
+    }
+}
+
+/// The batch-aware checks of [`StampValidator::validate_with_state`]:
+/// `batch` is not expired under `state` and [`Batch::verify_stamp`] accepts
+/// the stamp.
+pub(crate) fn check_live_stamp<S: SwarmSpec>(
+    stamp: &Stamp,
+    address: &ChunkAddress,
+    batch: &Batch<S>,
+    state: &PostageContext,
+) -> Result<(), StampError> {
+    check_live_batch(batch, state)?;
+    batch.verify_stamp(stamp, address)
+}
+
+/// Rejects `batch` if its balance is at or below the cumulative payout in
+/// `state`.
+const fn check_live_batch<S: SwarmSpec>(
+    batch: &Batch<S>,
+    state: &PostageContext,
+) -> Result<(), StampError> {
+    if batch.is_expired(state.total_amount()) {
+        return Err(StampError::BatchExpired {
+            value: batch.value(),
+            total_amount: state.total_amount(),
+        });
+    }
+    Ok(())
 }
 
 // Note: BatchValidation methods (validate_index, bucket_for_address, validate_bucket)
@@ -97,7 +162,9 @@ pub trait StampValidator {
 /// 3. Checks the batch is not expired
 /// 4. Validates the stamp index is within bounds
 /// 5. Validates the bucket matches the chunk address
-/// 6. Verifies the stamp signature matches the batch owner
+/// 6. Checks the stamp timestamp, if [time bounds](Self::with_time_bounds)
+///    are set
+/// 7. Verifies the stamp signature matches the batch owner
 ///
 /// # Example
 ///
@@ -114,6 +181,15 @@ pub trait StampValidator {
 pub struct StoreValidator<S> {
     store: S,
     confirmation_threshold: u64,
+    time_bounds: Option<TimeBounds>,
+}
+
+/// Accepted stamp timestamps, in nanoseconds since the Unix epoch.
+#[derive(Debug, Clone, Copy)]
+#[cfg(feature = "std")]
+struct TimeBounds {
+    min: u64,
+    max_future_skew: u64,
 }
 
 #[cfg(feature = "std")]
@@ -128,9 +204,25 @@ impl<S> StoreValidator<S> {
         Self {
             store,
             confirmation_threshold,
+            time_bounds: None,
         }
     }
 
+    /// Rejects stamps timestamped before `min_timestamp` or more than
+    /// `max_future_skew` past the local clock, with
+    /// [`StampError::TimestampOutOfRange`].
+    ///
+    /// Both are in nanoseconds, the unit stamps carry. Without this call
+    /// timestamps are not checked.
+    #[must_use]
+    pub const fn with_time_bounds(mut self, min_timestamp: u64, max_future_skew: u64) -> Self {
+        self.time_bounds = Some(TimeBounds {
+            min: min_timestamp,
+            max_future_skew,
+        });
+        self
+    }
+
     /// Returns a reference to the underlying store.
     pub const fn store(&self) -> &S {
         &self.store
@@ -164,6 +256,50 @@ impl<S: BatchStore> StoreValidator<S> {
         Ok(())
     }
 
+    /// Validates many stamps, returning one result per input in input order.
+    ///
+    /// Each batch is loaded from the store once, and the owner's public key,
+    /// once recovered from a stamp matching the owner, verifies the rest of
+    /// that batch's stamps without another recovery: the
+    /// [`verify_with_pubkey`](Stamp::verify_with_pubkey) fast path, with the
+    /// owner check done by the store. A stamp failing the fast path is
+    /// re-checked by full recovery, so its error is the one
+    /// [`validate`](Self::validate) would give.
+    pub fn validate_many(&self, stamps: &[(Stamp, ChunkAddress)]) -> Vec<Result<(), StampError>> {
+        let mut batches: HashMap<BatchId, Result<(Batch, Option<VerifyingKey>), StampError>> =
+            HashMap::new();
+
+        stamps
+            .iter()
+            .map(|(stamp, address)| {
+                let (batch, pubkey) = batches
+                    .entry(stamp.batch())
+                    .or_insert_with(|| self.get_batch_for_stamp(stamp).map(|batch| (batch, None)))
+                    .as_mut()
+                    .map_err(|e| e.clone())?;
+
+                self.validate_structure_with_batch(stamp, address, batch)?;
+
+                if let Some(key) = pubkey
+                    && stamp.verify_with_pubkey(address, key).is_ok()
+                {
+                    return Ok(());
+                }
+
+                let key = stamp.recover_pubkey(address)?;
+                let signer = public_key_to_address(&key);
+                if signer != batch.owner() {
+                    return Err(StampError::OwnerMismatch {
+                        expected: batch.owner(),
+                        actual: signer,
+                    });
+                }
+                *pubkey = Some(key);
+                Ok(())
+            })
+            .collect()
+    }
+
     /// Validates the structural properties without signature verification.
     ///
     /// This is faster than full validation when you only need to check
@@ -206,11 +342,11 @@ impl<S: BatchStore> StoreValidator<S> {
     }
 
     /// Validates structure given an already-retrieved batch.
-    fn validate_structure_with_batch(
+    fn validate_structure_with_batch<B: SwarmSpec>(
         &self,
         stamp: &Stamp,
         address: &ChunkAddress,
-        batch: &Batch,
+        batch: &Batch<B>,
     ) -> Result<(), StampError> {
         // Validate index bounds
         batch.validate_index(&stamp.stamp_index())?;
@@ -218,10 +354,81 @@ impl<S: BatchStore> StoreValidator<S> {
         // Validate bucket matches address
         batch.validate_bucket(&stamp.stamp_index(), address)?;
 
+        self.validate_timestamp(stamp)
+    }
+
+    /// Checks the stamp timestamp against the time bounds, if any.
+    fn validate_timestamp(&self, stamp: &Stamp) -> Result<(), StampError> {
+        if let Some(bounds) = self.time_bounds {
+            let max = crate::current_timestamp().saturating_add(bounds.max_future_skew);
+            let timestamp = stamp.timestamp();
+            if timestamp < bounds.min || timestamp > max {
+                return Err(StampError::TimestampOutOfRange {
+                    timestamp,
+                    min: bounds.min,
+                    max,
+                });
+            }
+        }
+
         Ok(())
     }
 }
 
+/// The checks of [`StoreValidator::validate`], with expiry also judged
+/// against the caller's `state`.
+#[cfg(feature = "std")]
+impl<S: BatchStore> StampValidator for StoreValidator<S> {
+    type Error = StampError;
+
+    fn validate(
+        &self,
+        stamp: &Stamp,
+        address: &ChunkAddress,
+        state: &PostageContext,
+    ) -> Result<(), StampError> {
+        let batch = self.get_batch_for_stamp(stamp)?;
+        check_live_batch(&batch, state)?;
+        self.validate_structure_with_batch(stamp, address, &batch)?;
+        stamp.verify(address, batch.owner())
+    }
+
+    fn validate_structure(
+        &self,
+        stamp: &Stamp,
+        address: &ChunkAddress,
+        state: &PostageContext,
+    ) -> Result<(), StampError> {
+        let batch = self.get_batch_for_stamp(stamp)?;
+        check_live_batch(&batch, state)?;
+        self.validate_structure_with_batch(stamp, address, &batch)
+    }
+
+    /// Judges the supplied `batch` rather than the store's copy: it must
+    /// have its confirmations at `state`'s block, then the stamp must meet
+    /// the time bounds and pass the checks of the provided method.
+    fn validate_with_state<B: SwarmSpec>(
+        &self,
+        stamp: &Stamp,
+        address: &ChunkAddress,
+        batch: &Batch<B>,
+        state: &PostageContext,
+    ) -> Result<(), StampError>
+    where
+        Self: Sized,
+    {
+        if !batch.is_usable(state.block(), self.confirmation_threshold) {
+            return Err(StampError::BatchNotUsable {
+                created: batch.start(),
+                current: state.block(),
+                threshold: self.confirmation_threshold,
+            });
+        }
+        self.validate_timestamp(stamp)?;
+        check_live_stamp(stamp, address, batch, state)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -349,4 +556,271 @@ mod tests {
             Err(StampError::BucketMismatch)
         ));
     }
+
+    #[cfg(feature = "std")]
+    mod store_validator {
+        use super::*;
+        use crate::test_utils::{MemoryStore, Permissive, stamp, stamp_at};
+        use alloy_primitives::B256;
+        use alloy_signer_local::PrivateKeySigner;
+
+        #[test]
+        fn validate_with_state_rejects_expired_batches() {
+            let owner = PrivateKeySigner::random();
+            let id = BatchId::from([1; 32]);
+            let batch: Batch = Batch::new(
+                id,
+                1_000,
+                0,
+                owner.address(),
+                18,
+                BucketDepth::new(16).unwrap(),
+                false,
+            );
+            let address = ChunkAddress::from(B256::random());
+            let stamp = stamp(&owner, id, address);
+
+            let live = PostageContext::new(100, 999);
+            assert_eq!(
+                Permissive.validate_with_state(&stamp, &address, &batch, &live),
+                Ok(())
+            );
+
+            for total_amount in [1_000, 5_000] {
+                let dead = PostageContext::new(100, total_amount);
+                assert_eq!(
+                    Permissive.validate_with_state(&stamp, &address, &batch, &dead),
+                    Err(StampError::BatchExpired {
+                        value: 1_000,
+                        total_amount,
+                    })
+                );
+            }
+
+            // A live batch still refuses a stamp its owner did not sign.
+            let forged = stamp_at(&PrivateKeySigner::random(), id, address, 1);
+            assert!(matches!(
+                Permissive.validate_with_state(&forged, &address, &batch, &live),
+                Err(StampError::OwnerMismatch { .. })
+            ));
+        }
+
+        #[test]
+        fn time_bounds_are_opt_in_and_reject_outliers() {
+            let owner = PrivateKeySigner::random();
+            let id = BatchId::from([1; 32]);
+            let batch = Batch::new(
+                id,
+                1,
+                0,
+                owner.address(),
+                18,
+                BucketDepth::new(16).unwrap(),
+                false,
+            );
+            let unbounded =
+                StoreValidator::new(MemoryStore::new(PostageContext::new(100, 0), [batch]), 0);
+            let minute = 60 * 1_000_000_000;
+            let now = crate::current_timestamp();
+            let address = ChunkAddress::from(B256::random());
+
+            let stale = stamp_at(&owner, id, address, now - 2 * minute);
+            let fresh = stamp_at(&owner, id, address, now);
+            let future = stamp_at(&owner, id, address, u64::MAX);
+            for stamp in [&stale, &fresh, &future] {
+                assert_eq!(unbounded.validate(stamp, &address), Ok(()));
+            }
+
+            let bounded = unbounded.with_time_bounds(now - minute, minute);
+            assert_eq!(bounded.validate(&fresh, &address), Ok(()));
+            for stamp in [&stale, &future] {
+                assert!(matches!(
+                    bounded.validate(stamp, &address),
+                    Err(StampError::TimestampOutOfRange { min, .. }) if min == now - minute
+                ));
+            }
+        }
+
+        #[test]
+        fn validate_with_state_applies_the_validator_policy() {
+            let owner = PrivateKeySigner::random();
+            let id = BatchId::from([1; 32]);
+            let batch: Batch = Batch::new(
+                id,
+                1,
+                0,
+                owner.address(),
+                18,
+                BucketDepth::new(16).unwrap(),
+                false,
+            );
+            let minute = 60 * 1_000_000_000;
+            let now = crate::current_timestamp();
+            let validator = StoreValidator::new(
+                MemoryStore::new(PostageContext::new(100, 0), [batch.clone()]),
+                0,
+            )
+            .with_time_bounds(now - minute, minute);
+            let state = PostageContext::new(100, 0);
+            let address = ChunkAddress::from(B256::random());
+
+            let fresh = stamp_at(&owner, id, address, now);
+            assert_eq!(
+                validator.validate_with_state(&fresh, &address, &batch, &state),
+                Ok(())
+            );
+
+            // The stamp is well-formed and signed by the live batch's owner;
+            // only the validator's time window refuses it.
+            let stale = stamp_at(&owner, id, address, now - 2 * minute);
+            assert_eq!(
+                Permissive.validate_with_state(&stale, &address, &batch, &state),
+                Ok(())
+            );
+            assert!(matches!(
+                validator.validate_with_state(&stale, &address, &batch, &state),
+                Err(StampError::TimestampOutOfRange { .. })
+            ));
+        }
+
+        /// Refuses the signature-checking path, to show the provided method
+        /// leaves signer recovery to its own batch check.
+        struct StructureOnly;
+
+        impl StampValidator for StructureOnly {
+            type Error = StampError;
+
+            fn validate(
+                &self,
+                _: &Stamp,
+                _: &ChunkAddress,
+                _: &PostageContext,
+            ) -> Result<(), StampError> {
+                unreachable!("validate_with_state must not run the full validate")
+            }
+
+            fn validate_structure(
+                &self,
+                _: &Stamp,
+                _: &ChunkAddress,
+                _: &PostageContext,
+            ) -> Result<(), StampError> {
+                Ok(())
+            }
+        }
+
+        #[test]
+        fn validate_with_state_recovers_through_the_batch_check_only() {
+            let owner = PrivateKeySigner::random();
+            let id = BatchId::from([1; 32]);
+            let batch: Batch = Batch::new(
+                id,
+                1,
+                0,
+                owner.address(),
+                18,
+                BucketDepth::new(16).unwrap(),
+                false,
+            );
+            let address = ChunkAddress::from(B256::random());
+            let state = PostageContext::new(100, 0);
+
+            let signed = stamp(&owner, id, address);
+            assert_eq!(
+                StructureOnly.validate_with_state(&signed, &address, &batch, &state),
+                Ok(())
+            );
+            let forged = stamp(&PrivateKeySigner::random(), id, address);
+            assert!(matches!(
+                StructureOnly.validate_with_state(&forged, &address, &batch, &state),
+                Err(StampError::OwnerMismatch { .. })
+            ));
+        }
+
+        #[test]
+        fn store_validator_judges_the_supplied_batch() {
+            let owner = PrivateKeySigner::random();
+            let id = BatchId::from([1; 32]);
+            let batch: Batch = Batch::new(
+                id,
+                1,
+                90,
+                owner.address(),
+                18,
+                BucketDepth::new(16).unwrap(),
+                false,
+            );
+            let address = ChunkAddress::from(B256::random());
+            let stamp = stamp(&owner, id, address);
+            // The store knows no batch at all.
+            let validator =
+                StoreValidator::new(MemoryStore::new(PostageContext::new(0, 0), []), 10);
+
+            assert_eq!(
+                validator.validate_with_state(
+                    &stamp,
+                    &address,
+                    &batch,
+                    &PostageContext::new(100, 0)
+                ),
+                Ok(())
+            );
+            assert_eq!(
+                validator.validate_with_state(
+                    &stamp,
+                    &address,
+                    &batch,
+                    &PostageContext::new(99, 0)
+                ),
+                Err(StampError::BatchNotUsable {
+                    created: 90,
+                    current: 99,
+                    threshold: 10,
+                })
+            );
+        }
+
+        #[test]
+        fn validate_many_matches_validate_item_by_item() {
+            let owners = [PrivateKeySigner::random(), PrivateKeySigner::random()];
+            let ids = [BatchId::from([1; 32]), BatchId::from([2; 32])];
+            let store = MemoryStore::new(
+                PostageContext::new(100, 0),
+                ids.iter().zip(&owners).map(|(&id, owner)| {
+                    Batch::new(
+                        id,
+                        1,
+                        0,
+                        owner.address(),
+                        18,
+                        BucketDepth::new(16).unwrap(),
+                        false,
+                    )
+                }),
+            );
+            let validator = StoreValidator::new(store, 0);
+            let stranger = PrivateKeySigner::random();
+
+            let mut stamps = Vec::new();
+            for round in 0..4 {
+                for (&id, owner) in ids.iter().zip(&owners) {
+                    let address = ChunkAddress::from(B256::random());
+                    // The third round is signed by someone other than the owners.
+                    let signer = if round == 2 { &stranger } else { owner };
+                    stamps.push((stamp(signer, id, address), address));
+                }
+            }
+            let address = ChunkAddress::from(B256::random());
+            stamps.push((stamp(&owners[0], BatchId::from([3; 32]), address), address));
+
+            let expected: Vec<_> = stamps
+                .iter()
+                .map(|(stamp, address)| validator.validate(stamp, address))
+                .collect();
+            assert_eq!(validator.validate_many(&stamps), expected);
+            assert_eq!(expected.iter().filter(|r| r.is_ok()).count(), 6);
+            assert!(matches!(expected[4], Err(StampError::OwnerMismatch { .. })));
+            assert!(matches!(expected[8], Err(StampError::BatchNotFound(_))));
+        }
+    }
 }
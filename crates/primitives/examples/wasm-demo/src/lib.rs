@@ -5,8 +5,10 @@
 use alloy_primitives::{hex, Address, B256};
 use alloy_signer_local::PrivateKeySigner;
 use bytes::Bytes;
+use nectar_postage::{Stamp, StampError};
 use nectar_primitives::{
-    ChunkAddress, ChunkOps, DefaultContentChunk, DefaultHasher, DefaultSingleOwnerChunk, SocId,
+    ChunkAddress, ChunkOps, DefaultContentChunk, DefaultHasher, DefaultSingleOwnerChunk,
+    OverlayAddress, SocId,
 };
 use wasm_bindgen::prelude::*;
 
@@ -391,9 +393,7 @@ pub fn create_icon_from_hex(
 /// @returns {Uint8Array} A randomly generated 32-byte address
 #[wasm_bindgen]
 pub fn generate_random_chunk_address() -> js_sys::Uint8Array {
-    let mut bytes = [0u8; 32];
-    getrandom::getrandom(&mut bytes).expect("Failed to generate random bytes");
-    js_sys::Uint8Array::from(&bytes[..])
+    js_sys::Uint8Array::from(OverlayAddress::random().as_bytes())
 }
 
 /// Generate an SVG icon based on IconData and configuration
@@ -867,9 +867,7 @@ pub fn create_single_owner_chunk(
 /// @returns {Uint8Array} A randomly generated 32-byte chunk ID
 #[wasm_bindgen]
 pub fn generate_random_chunk_id() -> js_sys::Uint8Array {
-    let mut bytes = [0u8; 32];
-    getrandom::getrandom(&mut bytes).expect("Failed to generate random bytes");
-    js_sys::Uint8Array::from(&bytes[..])
+    js_sys::Uint8Array::from(OverlayAddress::random().as_bytes())
 }
 
 /// Analyze a chunk and determine its type and properties
@@ -999,3 +997,47 @@ pub fn generate_svg_for_address(
     // Generate the SVG
     Ok(generate_svg_icon(&icon_data, config))
 }
+
+//------------------------------------------------------------------------------
+// Stamp Verification
+//------------------------------------------------------------------------------
+
+/// Verify that a postage stamp was issued by a batch owner for a chunk
+///
+/// A stamp that parses but was signed by someone else, or whose signature
+/// does not recover, verifies as `false`; malformed inputs are errors.
+///
+/// @param {Uint8Array} stamp_bytes - Serialized stamp (113 bytes)
+/// @param {Uint8Array} chunk_address - Address of the stamped chunk (32 bytes)
+/// @param {string} owner_hex - Batch owner address as hex, with or without `0x`
+/// @returns {boolean} Whether the stamp verifies
+#[wasm_bindgen]
+pub fn verify_stamp(
+    stamp_bytes: &[u8],
+    chunk_address: &[u8],
+    owner_hex: &str,
+) -> Result<bool, JsValue> {
+    set_panic_hook();
+
+    let stamp = Stamp::try_from_slice(stamp_bytes)
+        .map_err(|e| JsValue::from_str(&format!("Invalid stamp: {}", e)))?;
+
+    // Check chunk address length
+    if chunk_address.len() != 32 {
+        return Err(JsValue::from_str("Chunk address must be exactly 32 bytes"));
+    }
+    let mut address_bytes = [0u8; 32];
+    address_bytes.copy_from_slice(chunk_address);
+    let address = ChunkAddress::new(address_bytes);
+
+    let owner = match owner_hex.parse::<Address>() {
+        Ok(owner) => owner,
+        Err(_) => return Err(JsValue::from_str("Invalid hex for owner address")),
+    };
+
+    match stamp.verify(&address, owner) {
+        Ok(()) => Ok(true),
+        Err(StampError::OwnerMismatch { .. } | StampError::InvalidSignature) => Ok(false),
+        Err(e) => Err(JsValue::from_str(&format!("Failed to verify stamp: {}", e))),
+    }
+}
@@ -6,7 +6,7 @@
 //! from the content-address kind; cross-kind proximity goes through
 //! [`XorMetric`].
 
-use alloy_primitives::B256;
+use alloy_primitives::{B256, keccak256};
 use derive_more::{AsRef, Display, From, Into};
 
 #[cfg(feature = "serde")]
@@ -64,6 +64,22 @@ impl OverlayAddress {
         Self(B256::new(bytes))
     }
 
+    /// Samples a uniformly random address, for tests and tools that need an
+    /// arbitrary overlay.
+    #[cfg(feature = "std")]
+    pub fn random() -> Self {
+        Self(B256::random())
+    }
+
+    /// Derives an address deterministically as `keccak256(seed)`.
+    ///
+    /// The result is only a reproducible 32-byte identifier: it is not a
+    /// derived overlay (see [`compute_overlay`](crate::compute_overlay)) and
+    /// not a chunk address (no span, no BMT).
+    pub fn from_seed(seed: &[u8]) -> Self {
+        Self(keccak256(seed))
+    }
+
     /// Borrow the underlying 32 bytes.
     #[inline]
     pub const fn as_bytes(&self) -> &[u8] {
@@ -134,6 +150,28 @@ mod tests {
         assert_eq!(OverlayAddress::from(bytes), addr);
     }
 
+    #[test]
+    fn from_seed_is_deterministic_keccak() {
+        assert_eq!(
+            OverlayAddress::from_seed(b"node-1"),
+            OverlayAddress::from_seed(b"node-1")
+        );
+        assert_ne!(
+            OverlayAddress::from_seed(b"node-1"),
+            OverlayAddress::from_seed(b"node-2")
+        );
+        assert_eq!(
+            B256::from(OverlayAddress::from_seed(b"")),
+            alloy_primitives::KECCAK256_EMPTY
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn random_addresses_differ() {
+        assert_ne!(OverlayAddress::random(), OverlayAddress::random());
+    }
+
     #[test]
     fn with_first_byte_sets_only_the_first_byte() {
         let addr = OverlayAddress::with_first_byte(0x80);
@@ -18,8 +18,12 @@ pub const DEFAULT_BODY_SIZE: usize = BRANCHES * SEGMENT_SIZE;
 /// Span header size in bytes (u64).
 pub const SPAN_SIZE: usize = size_of::<u64>();
 
-/// Proof length in segments (log2(128) = 7).
-pub(crate) const PROOF_LENGTH: usize = 7;
+/// Levels of pair hashing from the segments to the root of a default-size
+/// body (log2(128) = 7).
+pub const BMT_DEPTH: usize = 7;
+
+/// Proof length in segments: one sibling per level.
+pub(crate) const PROOF_LENGTH: usize = BMT_DEPTH;
 
 /// Compute number of zero tree levels for a given body size.
 #[allow(clippy::arithmetic_side_effects, clippy::as_conversions)]
@@ -34,3 +38,5 @@ pub(crate) const fn zero_tree_levels(body_size: usize) -> usize {
 pub(crate) const fn branches_for_body_size(body_size: usize) -> usize {
     body_size / SEGMENT_SIZE
 }
+
+const _: () = assert!(zero_tree_levels(DEFAULT_BODY_SIZE) == BMT_DEPTH);
@@ -12,4 +12,13 @@ pub enum BmtError {
         /// The number of leaf segments in the tree.
         branches: usize,
     },
+
+    /// A proof carried a sibling path of the wrong length for the tree.
+    #[error("invalid proof length: expected {expected} sibling hashes, got {actual}")]
+    InvalidProofLength {
+        /// The number of sibling hashes a proof needs: one per tree level.
+        expected: usize,
+        /// The number of sibling hashes supplied.
+        actual: usize,
+    },
 }
@@ -12,27 +12,31 @@ use once_cell::race::OnceBox;
 
 use super::constants::*;
 use super::derived::DerivedAddress;
-
-/// Number of zero tree levels for the default body size.
-const ZERO_TREE_LEVELS: usize = zero_tree_levels(DEFAULT_BODY_SIZE);
+use super::span::Span;
 
 /// Per-level zero-subtree hashes for plain (unprefixed) hashing, computed once
 /// on first use.
-static ZERO_HASHES: OnceBox<[B256; ZERO_TREE_LEVELS]> = OnceBox::new();
+static ZERO_HASHES: OnceBox<[B256; BMT_DEPTH]> = OnceBox::new();
 
-/// The shared plain zero-hash table.
-fn zero_hash_table() -> &'static [B256; ZERO_TREE_LEVELS] {
+/// Hashes of all-zero subtrees, one per level: entry 0 is the hash of one
+/// all-zero segment pair, entry `n` the hash of two copies of entry `n - 1`,
+/// and the last entry is the root of an all-zero default-size body (before
+/// the span is wrapped in).
+///
+/// Plain (unprefixed) hashing only. Computed once, on first use, so proof
+/// verifiers and sparse hashers can short-circuit zero segments.
+pub fn zero_hashes() -> &'static [B256; BMT_DEPTH] {
     ZERO_HASHES.get_or_init(|| Box::new(zero_hash_levels(None)))
 }
 
 /// Compute the per-level zero-subtree hashes: level 0 is the hash of one
 /// all-zero segment pair, level n the hash of two level n-1 digests.
-fn zero_hash_levels(prefix: Option<&[u8]>) -> [B256; ZERO_TREE_LEVELS] {
+fn zero_hash_levels(prefix: Option<&[u8]>) -> [B256; BMT_DEPTH] {
     let mut hasher = node_hasher(prefix);
     hasher.update([0u8; SEGMENT_PAIR_LENGTH]);
     let mut current = B256::from_slice(hasher.finalize().as_slice());
 
-    let mut hashes = [B256::ZERO; ZERO_TREE_LEVELS];
+    let mut hashes = [B256::ZERO; BMT_DEPTH];
     let [levels @ .., top] = &mut hashes;
     for slot in levels {
         *slot = current;
@@ -86,7 +90,7 @@ pub(super) fn hash_pairs<'a>(
 /// BMT hasher with configurable body size.
 #[derive(Debug, Clone)]
 pub struct Hasher<const BODY_SIZE: usize = DEFAULT_BODY_SIZE> {
-    span: u64,
+    span: Span,
     prefix: Option<Vec<u8>>,
     buffer: [u8; BODY_SIZE],
     cursor: usize,
@@ -104,7 +108,7 @@ impl<const BODY_SIZE: usize> Hasher<BODY_SIZE> {
     #[inline]
     pub const fn new() -> Self {
         Self {
-            span: 0,
+            span: Span::ZERO,
             prefix: None,
             buffer: [0u8; BODY_SIZE],
             cursor: 0,
@@ -114,13 +118,13 @@ impl<const BODY_SIZE: usize> Hasher<BODY_SIZE> {
     /// Set the span of data to be hashed
     #[inline]
     pub const fn set_span(&mut self, span: u64) {
-        self.span = span;
+        self.span = Span::new(span);
     }
 
     /// Get the current span
     #[inline(always)]
     pub const fn span(&self) -> u64 {
-        self.span
+        self.span.get()
     }
 
     /// Add a prefix to the hash calculation.
@@ -143,6 +147,16 @@ impl<const BODY_SIZE: usize> Hasher<BODY_SIZE> {
         hasher
     }
 
+    /// Clear the span and data so the hasher can hash another body.
+    ///
+    /// Reuses the body buffer rather than reallocating, so a hot loop can
+    /// hash any number of chunks with one hasher. The prefix is
+    /// configuration and is kept. Equivalent to [`Reset::reset`].
+    #[inline]
+    pub fn reset(&mut self) {
+        self.reset_internal();
+    }
+
     /// Get the current prefix
     #[inline(always)]
     pub fn prefix(&self) -> &[u8] {
@@ -285,8 +299,8 @@ impl<const BODY_SIZE: usize> Hasher<BODY_SIZE> {
     /// set the table is computed on demand so each level is
     /// `keccak(prefix || left || right)`.
     #[inline(always)]
-    fn zero_hashes(&self, prefix: Option<&[u8]>) -> [B256; ZERO_TREE_LEVELS] {
-        prefix.map_or_else(|| *zero_hash_table(), |p| zero_hash_levels(Some(p)))
+    fn zero_hashes(&self, prefix: Option<&[u8]>) -> [B256; BMT_DEPTH] {
+        prefix.map_or_else(|| *zero_hashes(), |p| zero_hash_levels(Some(p)))
     }
 
     /// Hash a power-of-two subtree of `size` bytes (>= 64, taken from the
@@ -296,7 +310,7 @@ impl<const BODY_SIZE: usize> Hasher<BODY_SIZE> {
     /// Only pairs that overlap live data (the cursor) cost a Keccak; a live
     /// row with an odd node count is padded with that level's zero-subtree
     /// hash, so everything past the live nodes stays un-hashed.
-    fn hash_subtree(&self, size: usize, zero_hashes: &[B256; ZERO_TREE_LEVELS]) -> B256 {
+    fn hash_subtree(&self, size: usize, zero_hashes: &[B256; BMT_DEPTH]) -> B256 {
         debug_assert!(size.is_power_of_two());
         debug_assert!(size >= SEGMENT_PAIR_LENGTH);
 
@@ -371,7 +385,6 @@ impl<const BODY_SIZE: usize> Hasher<BODY_SIZE> {
     fn finalize_with_prefix(&self, intermediate_hash: B256) -> B256 {
         let mut hasher = node_hasher(self.prefix.as_deref());
 
-        // Add span as little-endian bytes
         hasher.update(self.span.to_le_bytes());
 
         // Add the intermediate hash
@@ -383,10 +396,14 @@ impl<const BODY_SIZE: usize> Hasher<BODY_SIZE> {
 
     /// Reset the hasher's internal state
     #[inline(always)]
-    const fn reset_internal(&mut self) {
-        // Simply reset cursor - no need to clear the buffer as it will be overwritten
+    fn reset_internal(&mut self) {
+        // The tree hashes whole segment pairs, so a shorter next body would
+        // read stale bytes past its cursor: zero the span written so far.
+        if let Some(written) = self.buffer.get_mut(..self.cursor) {
+            written.fill(0);
+        }
         self.cursor = 0;
-        self.span = 0;
+        self.span = Span::ZERO;
         // Don't reset prefix, as it's considered a configuration parameter
     }
 
@@ -490,7 +507,15 @@ impl<const BODY_SIZE: usize> FixedOutputReset for Hasher<BODY_SIZE> {
 impl<const BODY_SIZE: usize> digest::HashMarker for Hasher<BODY_SIZE> {}
 
 /// Factory for creating BMT hashers.
-#[derive(Debug, Default, Clone)]
+///
+/// Created hashers are plain, so their root is a chunk address; see
+/// [`for_prefix`](Self::for_prefix) for anchor-keyed hashing.
+///
+/// The span is the one per-chunk setting:
+/// [`create_hasher_with_span`](Self::create_hasher_with_span) takes it up
+/// front. For a leaf the span is its payload length; for an intermediate
+/// chunk it is the byte length of the whole subtree below it.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct HasherFactory<const BODY_SIZE: usize = DEFAULT_BODY_SIZE>;
 
 impl<const BODY_SIZE: usize> HasherFactory<BODY_SIZE> {
@@ -500,9 +525,71 @@ impl<const BODY_SIZE: usize> HasherFactory<BODY_SIZE> {
         Self
     }
 
+    /// A factory for content addressing; the same as [`new`](Self::new).
+    ///
+    /// Single-owner chunk bodies are hashed the same way; the SOC address is
+    /// derived from the id and owner on top of that body hash.
+    #[inline]
+    pub const fn content() -> Self {
+        Self::new()
+    }
+
+    /// A factory whose hashers are all keyed with the anchor `prefix`.
+    #[inline]
+    pub fn for_prefix(prefix: &[u8]) -> PrefixedHasherFactory<BODY_SIZE> {
+        PrefixedHasherFactory::new(prefix)
+    }
+
     /// Create a new BMT hasher.
     #[inline]
     pub const fn create_hasher(&self) -> Hasher<BODY_SIZE> {
         Hasher::new()
     }
+
+    /// Create a new BMT hasher with `span` already set.
+    #[inline]
+    pub const fn create_hasher_with_span(&self, span: u64) -> Hasher<BODY_SIZE> {
+        let mut hasher = Hasher::new();
+        hasher.set_span(span);
+        hasher
+    }
+}
+
+/// Factory for BMT hashers keyed with an anchor prefix, as
+/// [`Hasher::with_prefix`]: the root is the anchor-keyed transformed address
+/// the redistribution sampler uses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixedHasherFactory<const BODY_SIZE: usize = DEFAULT_BODY_SIZE> {
+    prefix: Vec<u8>,
+}
+
+impl<const BODY_SIZE: usize> PrefixedHasherFactory<BODY_SIZE> {
+    /// Create a factory whose hashers are keyed with `prefix`.
+    #[inline]
+    pub fn new(prefix: &[u8]) -> Self {
+        Self {
+            prefix: prefix.to_vec(),
+        }
+    }
+
+    /// The prefix every created hasher is keyed with.
+    #[inline]
+    pub fn prefix(&self) -> &[u8] {
+        &self.prefix
+    }
+
+    /// Create a new BMT hasher keyed with the factory's prefix.
+    #[inline]
+    pub fn create_hasher(&self) -> Hasher<BODY_SIZE> {
+        Hasher::with_prefix(&self.prefix)
+    }
+
+    /// Create a new BMT hasher keyed with the factory's prefix and with
+    /// `span` already set.
+    #[inline]
+    pub fn create_hasher_with_span(&self, span: u64) -> Hasher<BODY_SIZE> {
+        let mut hasher = self.create_hasher();
+        hasher.set_span(span);
+        hasher
+    }
 }
@@ -8,6 +8,7 @@
 //! - **Hasher**: Core BMT hashing functionality with span support
 //! - **Proof**: Inclusion proofs for efficient verification
 //! - **Prover**: Interface for generating and verifying proofs
+//! - **FileProof**: Chains chunk proofs up to a file root
 //!
 //! ## Example Usage
 //!
@@ -35,12 +36,14 @@ mod derived;
 pub(crate) mod error;
 mod hasher;
 mod proof;
+mod span;
 
-pub use constants::{BRANCHES, DEFAULT_BODY_SIZE, HASH_SIZE, SPAN_SIZE};
+pub use constants::{BMT_DEPTH, BRANCHES, DEFAULT_BODY_SIZE, HASH_SIZE, SPAN_SIZE};
 pub use derived::DerivedAddress;
 pub use error::BmtError;
-pub use hasher::{Hasher, HasherFactory};
-pub use proof::{Proof, Prover};
+pub use hasher::{Hasher, HasherFactory, PrefixedHasherFactory, zero_hashes};
+pub use proof::{FileProof, Proof, Prover, verify_file_proof};
+pub use span::Span;
 
 // Re-export for convenience
 pub use crate::error::{PrimitivesError, Result};
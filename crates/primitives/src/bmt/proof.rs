@@ -2,14 +2,28 @@
 
 use alloc::{vec, vec::Vec};
 use alloy_primitives::B256;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use super::error::BmtError;
 use super::hasher::{hash_pairs, node_hasher};
-use crate::bmt::{Hasher, constants::*};
+use crate::bmt::{Hasher, Span, constants::*};
 use crate::error::Result;
 
 /// Represents a proof for a specific segment in a Binary Merkle Tree
+///
+/// With the `serde` feature the proof serializes with camelCase field names
+/// and every hash and the prefix as `0x` hex. This is nectar's own transport
+/// shape, not a bee wire format:
+///
+/// ```json
+/// {"segmentIndex":3,"segment":"0x..","proofSegments":["0x..", ...],"span":4096,"prefix":"0x.."}
+/// ```
+///
+/// `prefix` is omitted when absent.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Proof {
     /// The segment index this proof is for
     pub segment_index: usize,
@@ -23,9 +37,42 @@ pub struct Proof {
     /// The span of the data
     pub span: u64,
     /// Optional prefix (used during verification)
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "prefix_serde"
+        )
+    )]
     pub prefix: Option<Vec<u8>>,
 }
 
+/// Serde for [`Proof::prefix`] as optional `0x` hex, through
+/// [`alloy_primitives::Bytes`].
+#[cfg(feature = "serde")]
+mod prefix_serde {
+    use alloc::vec::Vec;
+    use alloy_primitives::Bytes;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(
+        prefix: &Option<Vec<u8>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        prefix
+            .as_deref()
+            .map(Bytes::copy_from_slice)
+            .serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Vec<u8>>, D::Error> {
+        Ok(Option::<Bytes>::deserialize(deserializer)?.map(Vec::from))
+    }
+}
+
 impl Proof {
     /// Create a new BMT proof
     pub const fn new(
@@ -44,52 +91,194 @@ impl Proof {
         }
     }
 
+    /// Create a proof from a sibling path of unchecked length, such as one
+    /// received from a peer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BmtError::InvalidProofLength`] unless `proof_segments` holds
+    /// exactly one sibling hash per tree level.
+    pub fn try_new(
+        segment_index: usize,
+        segment: B256,
+        proof_segments: &[B256],
+        span: u64,
+        prefix: Option<Vec<u8>>,
+    ) -> Result<Self> {
+        let proof_segments = <[B256; PROOF_LENGTH]>::try_from(proof_segments).map_err(|_| {
+            BmtError::InvalidProofLength {
+                expected: PROOF_LENGTH,
+                actual: proof_segments.len(),
+            }
+        })?;
+        Ok(Self::new(
+            segment_index,
+            segment,
+            proof_segments,
+            span,
+            prefix,
+        ))
+    }
+
     /// Verify this proof against a root hash.
     ///
     /// The root is a typed 32-byte hash, so a mis-sized root cannot silently
     /// verify as a mismatch.
     pub fn verify(&self, root_hash: &B256) -> Result<bool> {
-        // Start with the segment being proven
-        let mut current_hash = self.segment;
-        let mut current_index = self.segment_index;
-
-        let prefix = self.prefix.as_deref();
-
-        // Apply each proof segment to compute the root
-        for proof_segment in &self.proof_segments {
-            // Every intermediate node is keccak(prefix || left || right);
-            // verifying without the prefix at each level would reject a valid
-            // anchor-keyed proof.
-            let mut hasher = node_hasher(prefix);
-
-            // Order matters - left then right
-            if current_index.is_multiple_of(2) {
-                hasher.update(current_hash.as_slice());
-                hasher.update(proof_segment.as_slice());
-            } else {
-                hasher.update(proof_segment.as_slice());
-                hasher.update(current_hash.as_slice());
-            }
+        Ok(self.root() == *root_hash)
+    }
 
-            // Get hash for next level
-            current_hash = B256::from_slice(hasher.finalize().as_slice());
-            current_index /= 2;
+    /// The root this proof implies for its segment: the value
+    /// [`verify`](Self::verify) compares against.
+    #[must_use]
+    pub fn root(&self) -> B256 {
+        implied_root(&self.segment, self.segment_index, self)
+    }
+}
+
+/// Inclusion proof of a data segment in a file's chunk tree.
+///
+/// Chains the BMT proof of the segment within its leaf chunk with one BMT
+/// proof per intermediate chunk on the way up, each proving the child's
+/// address as a segment of its parent's body. A client holding only the file
+/// root can check a single fetched chunk without the rest of the file.
+#[derive(Clone, Debug)]
+pub struct FileProof {
+    /// Proof of the data segment within the leaf chunk.
+    pub leaf: Proof,
+    /// Proofs of each child address within its parent, lowest level first;
+    /// empty when the file is a single chunk.
+    pub path: Vec<Proof>,
+}
+
+impl FileProof {
+    /// Create a new file proof
+    pub const fn new(leaf: Proof, path: Vec<Proof>) -> Self {
+        Self { leaf, path }
+    }
+
+    /// Verify this proof against a file root, the address of the leaf chunk
+    /// it covers, and the byte `offset` it claims within a file of `span`
+    /// bytes.
+    #[must_use]
+    pub fn verify(&self, root: &B256, leaf_chunk_address: &B256, offset: u64, span: u64) -> bool {
+        verify_file_proof(root, leaf_chunk_address, offset, span, self)
+    }
+}
+
+/// Check that `proof` ties the leaf chunk at `leaf_chunk_address` to the file
+/// `root`, at byte `offset` of a file of `span` bytes.
+///
+/// The leaf proof must imply the leaf address, every level must prove the
+/// address implied by the level below it, and the topmost level must imply
+/// `root`. Each level must also sit at the segment index and span that
+/// `offset` selects in the chunk tree of `span`, so a valid proof for one
+/// offset is rejected at any offset in another leaf or segment.
+#[must_use]
+pub fn verify_file_proof(
+    root: &B256,
+    leaf_chunk_address: &B256,
+    offset: u64,
+    span: u64,
+    proof: &FileProof,
+) -> bool {
+    if proof.leaf.root() != *leaf_chunk_address || !at_offset(proof, offset, span) {
+        return false;
+    }
+    let mut current = *leaf_chunk_address;
+    for level in &proof.path {
+        if level.segment != current {
+            return false;
         }
+        current = level.root();
+    }
+    current == *root
+}
 
-        // Final step: add prefix (if any) and span to compute the root hash
-        let mut hasher = node_hasher(prefix);
+/// Whether every level of `proof` has the span and segment index that
+/// `offset` selects when descending the chunk tree of a `span`-byte file.
+fn at_offset(proof: &FileProof, offset: u64, span: u64) -> bool {
+    use crate::cast::u64_from_usize;
 
-        // Add span as little-endian bytes
-        hasher.update(self.span.to_le_bytes());
+    if offset >= span {
+        return false;
+    }
+    let body = u64_from_usize(DEFAULT_BODY_SIZE);
+    let mut within = offset;
+    let mut level_span = span;
+    for level in proof.path.iter().rev() {
+        if level_span <= body || level.span != level_span {
+            return false;
+        }
+        let sub = child_subspan(level_span);
+        let (Some(index), Some(start)) = (within.checked_div(sub), within.checked_rem(sub)) else {
+            return false;
+        };
+        if u64_from_usize(level.segment_index) != index {
+            return false;
+        }
+        // The child covers `sub` bytes, or the remainder for the last one.
+        level_span = sub.min(level_span.saturating_sub(index.saturating_mul(sub)));
+        within = start;
+    }
+    level_span <= body
+        && proof.leaf.span == level_span
+        && Some(u64_from_usize(proof.leaf.segment_index))
+            == within.checked_div(u64_from_usize(SEGMENT_SIZE))
+}
 
-        // Add the intermediate hash
-        hasher.update(current_hash.as_slice());
+/// Bytes covered by each child reference of an intermediate chunk spanning
+/// `span`: the largest full subtree that does not cover the whole span.
+const fn child_subspan(span: u64) -> u64 {
+    let branches = crate::cast::u64_from_usize(BRANCHES);
+    let mut sub = crate::cast::u64_from_usize(DEFAULT_BODY_SIZE);
+    while let Some(covered) = sub.checked_mul(branches)
+        && covered < span
+    {
+        sub = covered;
+    }
+    sub
+}
+
+/// Fold `segment` at `index` up the sibling path of `proof`, then wrap the
+/// result with the proof's span (and prefix, if any) to give the root.
+fn implied_root(segment: &B256, index: usize, proof: &Proof) -> B256 {
+    let prefix = proof.prefix.as_deref();
 
-        let computed_root = B256::from_slice(hasher.finalize().as_slice());
+    // Start with the segment being proven
+    let mut current_hash = *segment;
+    let mut current_index = index;
+
+    // Apply each proof segment to compute the root
+    for proof_segment in &proof.proof_segments {
+        // Every intermediate node is keccak(prefix || left || right);
+        // verifying without the prefix at each level would reject a valid
+        // anchor-keyed proof.
+        let mut hasher = node_hasher(prefix);
+
+        // Order matters - left then right
+        if current_index.is_multiple_of(2) {
+            hasher.update(current_hash.as_slice());
+            hasher.update(proof_segment.as_slice());
+        } else {
+            hasher.update(proof_segment.as_slice());
+            hasher.update(current_hash.as_slice());
+        }
 
-        // Compare with provided root hash
-        Ok(computed_root == *root_hash)
+        // Get hash for next level
+        current_hash = B256::from_slice(hasher.finalize().as_slice());
+        current_index /= 2;
     }
+
+    // Final step: add prefix (if any) and span to compute the root hash
+    let mut hasher = node_hasher(prefix);
+
+    hasher.update(Span::new(proof.span).to_le_bytes());
+
+    // Add the intermediate hash
+    hasher.update(current_hash.as_slice());
+
+    B256::from_slice(hasher.finalize().as_slice())
 }
 
 /// Extension trait to add proof-related functionality to BMTHasher
@@ -99,6 +288,16 @@ pub trait Prover {
 
     /// Verify a proof against a root hash
     fn verify_proof(proof: &Proof, root_hash: &B256) -> Result<bool>;
+
+    /// Compute the root implied by `segment` sitting at `index` under the
+    /// sibling path, span and prefix of `proof`.
+    ///
+    /// Lets a light client that received a segment separately from its
+    /// proof check it against a known root without the rest of the chunk.
+    /// The proof's own segment and index are ignored.
+    fn root_from_proof(segment: &[u8; 32], index: usize, proof: &Proof) -> B256 {
+        implied_root(&B256::from(*segment), index, proof)
+    }
 }
 
 impl Prover for Hasher {
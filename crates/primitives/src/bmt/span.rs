@@ -0,0 +1,93 @@
+//! The span header of a BMT body.
+
+use derive_more::{Display, From, Into};
+
+use super::SPAN_SIZE;
+use crate::error::WrongLength;
+
+/// The number of payload bytes a BMT body stands for: its own length for a
+/// leaf, the total length of the subtree below it for an intermediate chunk.
+///
+/// On the wire, and wherever the span enters a hash, it is **always 8 bytes
+/// little-endian**. Convert only through
+/// [`to_le_bytes`](Self::to_le_bytes) and
+/// [`from_le_bytes`](Self::from_le_bytes) so the byte order is never chosen
+/// at the call site.
+///
+/// ```
+/// use nectar_primitives::bmt::Span;
+///
+/// let span = Span::new(4096);
+/// assert_eq!(span.to_le_bytes(), [0, 0x10, 0, 0, 0, 0, 0, 0]);
+/// assert_eq!(Span::from_le_bytes(span.to_le_bytes()), span);
+/// ```
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Display, From, Into,
+)]
+#[display("{_0}")]
+pub struct Span(u64);
+
+impl Span {
+    /// The span of an empty body.
+    pub const ZERO: Self = Self(0);
+
+    /// A span of `bytes` payload bytes.
+    #[inline]
+    pub const fn new(bytes: u64) -> Self {
+        Self(bytes)
+    }
+
+    /// The number of payload bytes.
+    #[inline]
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+
+    /// The wire encoding: 8 bytes, little-endian.
+    #[inline]
+    pub const fn to_le_bytes(self) -> [u8; SPAN_SIZE] {
+        self.0.to_le_bytes()
+    }
+
+    /// Decodes the wire encoding: 8 bytes, little-endian.
+    #[inline]
+    pub const fn from_le_bytes(bytes: [u8; SPAN_SIZE]) -> Self {
+        Self(u64::from_le_bytes(bytes))
+    }
+}
+
+impl TryFrom<&[u8]> for Span {
+    type Error = WrongLength;
+
+    /// Decodes a little-endian span from exactly [`SPAN_SIZE`] bytes.
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        let bytes: [u8; SPAN_SIZE] = slice.try_into().map_err(|_| WrongLength {
+            expected: SPAN_SIZE,
+            got: slice.len(),
+        })?;
+        Ok(Self::from_le_bytes(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn span_is_little_endian() {
+        let span = Span::new(0x0102_0304_0506_0708);
+        assert_eq!(span.to_le_bytes(), [8, 7, 6, 5, 4, 3, 2, 1]);
+        assert_eq!(
+            Span::try_from([8, 7, 6, 5, 4, 3, 2, 1].as_slice()),
+            Ok(span)
+        );
+        assert_eq!(
+            Span::try_from([0; 7].as_slice()),
+            Err(WrongLength {
+                expected: SPAN_SIZE,
+                got: 7
+            })
+        );
+        assert_eq!(u64::from(span), 0x0102_0304_0506_0708);
+    }
+}
@@ -759,3 +759,206 @@ fn test_proof_segment_out_of_bounds_error() {
         other => panic!("expected SegmentOutOfBounds, got {other:?}"),
     }
 }
+
+/// A sibling path of the wrong length, as an untrusted peer might send, is a
+/// typed error rather than a panic or a silent mismatch.
+#[test]
+fn test_proof_try_new_rejects_truncated_path() {
+    let data = vec![0x42u8; DEFAULT_BODY_SIZE];
+    let mut hasher = DefaultHasher::new();
+    hasher.set_span(data.len() as u64);
+    hasher.update(&data);
+    let proof = hasher.generate_proof(&data, 5).unwrap();
+
+    let rebuilt = Proof::try_new(
+        proof.segment_index,
+        proof.segment,
+        &proof.proof_segments,
+        proof.span,
+        None,
+    )
+    .unwrap();
+    assert!(rebuilt.verify(&hasher.sum()).unwrap());
+
+    for len in [0, PROOF_LENGTH - 1, PROOF_LENGTH + 1] {
+        let mut path = proof.proof_segments.to_vec();
+        path.resize(len, B256::ZERO);
+        let err = Proof::try_new(5, proof.segment, &path, proof.span, None).unwrap_err();
+        match err {
+            PrimitivesError::Bmt(BmtError::InvalidProofLength { expected, actual }) => {
+                assert_eq!((expected, actual), (PROOF_LENGTH, len));
+            }
+            other => panic!("expected InvalidProofLength, got {other:?}"),
+        }
+    }
+}
+
+/// The root recomputed from a segment and its sibling path equals the
+/// hasher's `sum()` for every segment, and a tampered segment or a wrong
+/// index yields a different root.
+#[test]
+fn test_root_from_proof_matches_sum() {
+    let mut data = vec![0u8; DEFAULT_BODY_SIZE];
+    rand::rng().fill(&mut data[..]);
+    let mut hasher = DefaultHasher::new();
+    hasher.set_span(data.len() as u64);
+    hasher.update(&data);
+    let root = hasher.sum();
+
+    for index in [0, 1, 63, 64, 127] {
+        let proof = hasher.generate_proof(&data, index).unwrap();
+        let segment: [u8; 32] = data[index * 32..(index + 1) * 32].try_into().unwrap();
+        assert_eq!(
+            DefaultHasher::root_from_proof(&segment, index, &proof),
+            root
+        );
+        assert_eq!(proof.root(), root);
+
+        let mut tampered = segment;
+        tampered[0] ^= 1;
+        assert_ne!(
+            DefaultHasher::root_from_proof(&tampered, index, &proof),
+            root
+        );
+        assert_ne!(
+            DefaultHasher::root_from_proof(&segment, index ^ 1, &proof),
+            root
+        );
+    }
+}
+
+/// Each zero-hash level is the hash of two copies of the level below, and the
+/// top level is the body root of an all-zero chunk.
+#[test]
+fn test_zero_hashes_table() {
+    let table = zero_hashes();
+    assert_eq!(table.len(), BMT_DEPTH);
+    assert_eq!(table[0], alloy_primitives::keccak256([0u8; 64]));
+    for pair in table.windows(2) {
+        assert_eq!(
+            pair[1],
+            alloy_primitives::keccak256([pair[0], pair[0]].concat())
+        );
+    }
+
+    let mut hasher = DefaultHasher::new();
+    hasher.set_span(DEFAULT_BODY_SIZE as u64);
+    hasher.update(&[0u8; DEFAULT_BODY_SIZE]);
+    let mut expected = alloy_primitives::Keccak256::new();
+    expected.update((DEFAULT_BODY_SIZE as u64).to_le_bytes());
+    expected.update(table[BMT_DEPTH - 1]);
+    assert_eq!(hasher.sum(), expected.finalize());
+}
+
+/// Preset factories build hashers identical to hand-configured ones.
+#[test]
+fn test_hasher_factory_presets_match_manual_configuration() {
+    let data = b"hasher factory preset";
+    let span = data.len() as u64;
+
+    let mut manual = DefaultHasher::new();
+    manual.set_span(span);
+    manual.update(data);
+    let mut preset: DefaultHasher = HasherFactory::content().create_hasher_with_span(span);
+    preset.update(data);
+    assert_eq!(preset.sum(), manual.sum());
+    assert_eq!(
+        HasherFactory::<DEFAULT_BODY_SIZE>::new(),
+        HasherFactory::content()
+    );
+
+    let anchor = [0x5a; 32];
+    let mut manual = DefaultHasher::with_prefix(&anchor);
+    manual.set_span(span);
+    manual.update(data);
+    let factory: PrefixedHasherFactory = HasherFactory::for_prefix(&anchor);
+    assert_eq!(factory.prefix(), anchor);
+    let mut preset = factory.create_hasher_with_span(span);
+    preset.update(data);
+    assert_eq!(preset.sum(), manual.sum());
+    assert_eq!(factory.create_hasher().prefix(), anchor);
+}
+
+/// A reset hasher reuses its buffer: bytes left past the new cursor by a
+/// longer earlier body must not leak into the next root.
+#[test]
+fn test_reset_hasher_matches_fresh() {
+    let mut reused = DefaultHasher::new();
+    for (len, fill) in [
+        (DEFAULT_BODY_SIZE, 0xff),
+        (1000, 0xaa),
+        (10, 0x01),
+        (0, 0),
+        (65, 0x7f),
+    ] {
+        let data = vec![fill; len];
+        reused.reset();
+        reused.set_span(len as u64);
+        reused.update(&data);
+
+        let mut fresh = DefaultHasher::new();
+        fresh.set_span(len as u64);
+        fresh.update(&data);
+        assert_eq!(reused.sum(), fresh.sum(), "reused root diverged at {len}");
+    }
+
+    // The prefix is configuration and survives a reset.
+    let mut prefixed = DefaultHasher::with_prefix(b"anchor");
+    prefixed.update(&[0xff; 128]);
+    prefixed.reset();
+    prefixed.update(b"abc");
+    let mut fresh = DefaultHasher::with_prefix(b"anchor");
+    fresh.update(b"abc");
+    assert_eq!(prefixed.sum(), fresh.sum());
+
+    // The digest reset path clears the same way.
+    let mut digest = DefaultHasher::new();
+    digest.update(&[0xff; 1000]);
+    let _ = digest.finalize_fixed_reset();
+    digest.update(b"abc");
+    let mut fresh = DefaultHasher::new();
+    fresh.update(b"abc");
+    assert_eq!(digest.sum(), fresh.sum());
+}
+
+/// A proof round-trips through JSON with camelCase field names, hex hashes
+/// and an optional hex prefix.
+#[cfg(feature = "serde")]
+#[test]
+fn test_proof_json_round_trip() {
+    let data = vec![0x17u8; 300];
+    let mut hasher = DefaultHasher::with_prefix(&[0xab, 0xcd]);
+    hasher.set_span(data.len() as u64);
+    hasher.update(&data);
+    let root = hasher.sum();
+    let proof = hasher.generate_proof(&data, 3).unwrap();
+
+    let json = serde_json::to_value(&proof).unwrap();
+    let object = json.as_object().unwrap();
+    let mut keys: Vec<_> = object.keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    assert_eq!(
+        keys,
+        ["prefix", "proofSegments", "segment", "segmentIndex", "span"]
+    );
+    assert_eq!(json["segmentIndex"], 3);
+    assert_eq!(json["span"], 300);
+    assert_eq!(json["prefix"], "0xabcd");
+    assert_eq!(json["segment"], format!("0x{}", hex::encode([0x17u8; 32])));
+    assert_eq!(
+        json["proofSegments"].as_array().unwrap().len(),
+        PROOF_LENGTH
+    );
+
+    let decoded: Proof = serde_json::from_value(json).unwrap();
+    assert_eq!(decoded.proof_segments, proof.proof_segments);
+    assert_eq!(decoded.prefix, proof.prefix);
+    assert!(decoded.verify(&root).unwrap());
+
+    // No prefix: the field is omitted and decodes back to `None`.
+    let plain = Proof::new(0, B256::ZERO, [B256::ZERO; PROOF_LENGTH], 0, None);
+    let json = serde_json::to_string(&plain).unwrap();
+    assert!(!json.contains("prefix"));
+    let decoded: Proof = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded.prefix, None);
+}
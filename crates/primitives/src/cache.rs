@@ -1,8 +1,11 @@
-//! Caching utilities for lazy computed values
+//! Caching utilities for lazy computed values and bounded lookups
 //!
 //! This module provides components for caching expensive computations
-//! that only need to be calculated once.
+//! that only need to be calculated once, and [`FifoCache`], the bounded map
+//! behind the crate's and downstream crates' lookup caches.
 
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
 use std::sync::OnceLock;
 
 /// Generic cache for lazily computed values.
@@ -38,6 +41,12 @@ impl<T> OnceCache<T> {
     {
         self.value.get_or_init(compute_fn)
     }
+
+    /// The cached value, if it has been computed.
+    #[cfg(test)]
+    pub(crate) fn get(&self) -> Option<&T> {
+        self.value.get()
+    }
 }
 
 impl<T> Default for OnceCache<T> {
@@ -53,3 +62,244 @@ impl<T: Clone> Clone for OnceCache<T> {
             .map_or_else(Self::new, |value| Self::with_value(value.clone()))
     }
 }
+
+/// A map remembering at most `capacity` entries, evicting in insertion
+/// order.
+///
+/// Once full, the entry inserted longest ago is evicted first. Neither a
+/// lookup nor a re-insert of a remembered key refreshes its place, and a
+/// zero capacity remembers nothing. An [`unbounded`](Self::unbounded) cache
+/// never evicts.
+///
+/// ```
+/// use nectar_primitives::cache::FifoCache;
+///
+/// let mut cache = FifoCache::new(2);
+/// cache.insert("a", 1);
+/// cache.insert("b", 2);
+/// cache.insert("c", 3);
+/// assert_eq!(cache.get(&"a"), None);
+/// assert_eq!(cache.get(&"c"), Some(&3));
+/// ```
+#[derive(Debug, Clone)]
+pub struct FifoCache<K, V> {
+    /// Each value with the sequence number it was inserted under.
+    entries: HashMap<K, (u64, V)>,
+    /// Insertion order, oldest first, kept only when bounded. A key removed
+    /// since its insertion stays here until evicted past or compacted; its
+    /// sequence number no longer matches the entry's.
+    order: VecDeque<(u64, K)>,
+    next: u64,
+    capacity: Option<usize>,
+}
+
+impl<K: Eq + Hash + Clone, V> FifoCache<K, V> {
+    /// Creates a cache remembering at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::with_capacity(capacity),
+            order: VecDeque::with_capacity(capacity),
+            next: 0,
+            capacity: Some(capacity),
+        }
+    }
+
+    /// Creates a cache that remembers every entry.
+    pub fn unbounded() -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            next: 0,
+            capacity: None,
+        }
+    }
+
+    /// The value remembered for `key`, if any.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.entries.get(key).map(|(_, value)| value)
+    }
+
+    /// The value remembered for `key`, mutably, if any.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.entries.get_mut(key).map(|(_, value)| value)
+    }
+
+    /// Remembers `value` for `key`, evicting the oldest entry if full.
+    ///
+    /// A key already remembered takes the new value in place.
+    pub fn insert(&mut self, key: K, value: V) {
+        if self.capacity == Some(0) {
+            return;
+        }
+        if let Some(held) = self.get_mut(&key) {
+            *held = value;
+            return;
+        }
+        if self
+            .capacity
+            .is_some_and(|capacity| self.entries.len() >= capacity)
+        {
+            self.evict_oldest();
+        }
+        let seq = self.next;
+        self.next = seq.wrapping_add(1);
+        if self.capacity.is_some() {
+            self.order.push_back((seq, key.clone()));
+        }
+        self.entries.insert(key, (seq, value));
+    }
+
+    /// Forgets `key`, returning its value if it was remembered.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let (_, value) = self.entries.remove(key)?;
+        self.compact();
+        Some(value)
+    }
+
+    /// Keeps only the entries for which `keep` returns `true`.
+    pub fn retain(&mut self, mut keep: impl FnMut(&K, &V) -> bool) {
+        self.entries.retain(|key, (_, value)| keep(key, value));
+        self.compact();
+    }
+
+    /// Forgets every entry.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+
+    /// Number of entries currently remembered.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no entry is remembered.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Maximum number of entries remembered, `None` when unbounded.
+    pub const fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    /// Whether `(seq, key)` in the order still names a remembered entry.
+    fn is_live(&self, seq: u64, key: &K) -> bool {
+        self.entries.get(key).is_some_and(|(held, _)| *held == seq)
+    }
+
+    /// Evicts the oldest remembered entry, skipping removed keys.
+    fn evict_oldest(&mut self) {
+        while let Some((seq, key)) = self.order.pop_front() {
+            if self.is_live(seq, &key) {
+                self.entries.remove(&key);
+                return;
+            }
+        }
+    }
+
+    /// Drops removed keys from the order once they outnumber the live ones,
+    /// so removal stays amortised constant time.
+    fn compact(&mut self) {
+        if self.order.len() > self.entries.len().saturating_mul(2) {
+            let mut order = core::mem::take(&mut self.order);
+            order.retain(|(seq, key)| self.is_live(*seq, key));
+            self.order = order;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_the_oldest_entry_once_full() {
+        let mut cache = FifoCache::new(2);
+        cache.insert(1, 'a');
+        cache.insert(2, 'b');
+        // A hit does not refresh the entry.
+        assert_eq!(cache.get(&1), Some(&'a'));
+        cache.insert(3, 'c');
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&1), None);
+        assert_eq!(cache.get(&2), Some(&'b'));
+        assert_eq!(cache.get(&3), Some(&'c'));
+    }
+
+    #[test]
+    fn reinserting_a_key_keeps_its_place() {
+        let mut cache = FifoCache::new(2);
+        cache.insert(1, 'a');
+        cache.insert(2, 'b');
+        cache.insert(1, 'z');
+        assert_eq!(cache.get(&1), Some(&'z'));
+        cache.insert(3, 'c');
+        assert_eq!(cache.get(&1), None);
+        assert_eq!(cache.get(&2), Some(&'b'));
+    }
+
+    #[test]
+    fn zero_capacity_remembers_nothing() {
+        let mut cache = FifoCache::new(0);
+        cache.insert(1, 'a');
+        assert!(cache.is_empty());
+        assert_eq!(cache.capacity(), Some(0));
+    }
+
+    #[test]
+    fn removed_keys_are_skipped_by_eviction() {
+        let mut cache = FifoCache::new(2);
+        cache.insert(1, 'a');
+        cache.insert(2, 'b');
+        assert_eq!(cache.remove(&1), Some('a'));
+        assert_eq!(cache.remove(&1), None);
+
+        // The freed slot takes a new key without evicting the survivor, and a
+        // re-inserted key starts at the back of the order.
+        cache.insert(1, 'c');
+        assert_eq!((cache.get(&1), cache.get(&2)), (Some(&'c'), Some(&'b')));
+        cache.insert(3, 'd');
+        assert_eq!((cache.get(&1), cache.get(&2)), (Some(&'c'), None));
+    }
+
+    #[test]
+    fn removal_churn_keeps_the_order_compact() {
+        let mut cache = FifoCache::new(4);
+        cache.insert(0, ());
+        for key in 1..1000 {
+            cache.insert(key, ());
+            cache.remove(&key);
+        }
+        assert_eq!(cache.len(), 1);
+        assert!(cache.order.len() <= 2);
+    }
+
+    #[test]
+    fn retain_filters_entries_and_their_order() {
+        let mut cache = FifoCache::new(3);
+        for key in 1..=3 {
+            cache.insert(key, key * 10);
+        }
+        cache.retain(|key, _| key % 2 == 1);
+        assert_eq!(cache.len(), 2);
+        cache.insert(4, 40);
+        cache.insert(5, 50);
+        assert_eq!(cache.get(&1), None);
+        assert_eq!(cache.get(&3), Some(&30));
+    }
+
+    #[test]
+    fn unbounded_cache_never_evicts() {
+        let mut cache = FifoCache::unbounded();
+        for key in 0..100 {
+            cache.insert(key, ());
+        }
+        assert_eq!(cache.len(), 100);
+        assert_eq!(cache.capacity(), None);
+        assert!(cache.order.is_empty());
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
@@ -3,7 +3,7 @@
 //! This module provides [`AnyChunk`], an enum that can hold any chunk type
 //! for runtime polymorphism without requiring trait objects.
 
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 
 use crate::bmt::DEFAULT_BODY_SIZE;
 use crate::error::Result;
@@ -105,6 +105,13 @@ impl<const BODY_SIZE: usize> ChunkOps for AnyChunk<BODY_SIZE> {
         }
     }
 
+    fn write_to(&self, buf: &mut BytesMut) {
+        match self {
+            Self::Content(c) => c.write_to(buf),
+            Self::SingleOwner(c) => c.write_to(buf),
+        }
+    }
+
     fn into_bytes(self) -> Bytes {
         match self {
             Self::Content(c) => c.into_bytes(),
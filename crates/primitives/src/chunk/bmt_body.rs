@@ -7,15 +7,24 @@ use bytes::{Bytes, BytesMut};
 use std::marker::PhantomData;
 use std::sync::OnceLock;
 
-use crate::bmt::{DEFAULT_BODY_SIZE, DerivedAddress, Hasher, SPAN_SIZE};
+use crate::EncryptedChunkRef;
+use crate::bmt::{
+    DEFAULT_BODY_SIZE, DerivedAddress, HASH_SIZE, Hasher, Proof, Prover, SPAN_SIZE, Span,
+};
 use crate::chunk::ChunkAddress;
 use crate::chunk::error::{self, ChunkError};
 use crate::error::{PrimitivesError, Result};
 
+/// Set in the top span byte of a chunk bee erasure-coded.
+const REDUNDANCY_FLAG: u64 = 0x80 << 56;
+
+/// The span proper under a redundancy-level top byte.
+const SPAN_MASK: u64 = u64::MAX >> 8;
+
 /// A BMT body with configurable maximum size.
 #[derive(Debug, Clone)]
 pub struct BmtBody<const BODY_SIZE: usize = DEFAULT_BODY_SIZE> {
-    span: u64,
+    span: Span,
     data: Bytes,
     cached_hash: OnceLock<DerivedAddress>,
 }
@@ -35,7 +44,10 @@ impl<const BODY_SIZE: usize> PartialEq for BmtBody<BODY_SIZE> {
 impl<const BODY_SIZE: usize> Eq for BmtBody<BODY_SIZE> {}
 
 impl<const BODY_SIZE: usize> BmtBody<BODY_SIZE> {
-    const fn new_unchecked(span: u64, data: Bytes) -> Self {
+    /// A body from raw parts, skipping the span/length agreement check.
+    ///
+    /// The caller guarantees `data.len() <= BODY_SIZE`.
+    pub(crate) const fn new_unchecked(span: Span, data: Bytes) -> Self {
         Self {
             span,
             data,
@@ -50,7 +62,7 @@ impl<const BODY_SIZE: usize> BmtBody<BODY_SIZE> {
 
     /// Get the span of this body
     pub const fn span(&self) -> u64 {
-        self.span
+        self.span.get()
     }
 
     /// Get the data of this body
@@ -64,6 +76,67 @@ impl<const BODY_SIZE: usize> BmtBody<BODY_SIZE> {
         SPAN_SIZE + self.data.len()
     }
 
+    /// Check that a span claiming an intermediate chunk is backed by a
+    /// reference list.
+    ///
+    /// The builder ties a leaf's span to its payload length but takes a span
+    /// over `BODY_SIZE` on trust. An intermediate payload holds one reference
+    /// per child subtree the span covers, of either the plain or the
+    /// encrypted width, so any other length means span and payload disagree,
+    /// e.g. a large span over truncated data.
+    ///
+    /// An encrypted chunk carries a ciphertext span over a payload padded to
+    /// `BODY_SIZE`, so a full payload may hide any span and is not checked.
+    ///
+    /// Bee marks an erasure-coded intermediate chunk by setting the top span
+    /// byte to `0x80 | level`; the span proper is the low seven bytes, and
+    /// parity references follow the data references in the payload.
+    pub(crate) fn validate_span(&self) -> error::Result<()> {
+        let raw = self.span.get();
+        let coded = raw & REDUNDANCY_FLAG != 0;
+        let span = if coded { raw & SPAN_MASK } else { raw };
+        let data_len = self.data.len();
+        if span <= crate::cast::u64_from_usize(BODY_SIZE) || data_len == BODY_SIZE {
+            return Ok(());
+        }
+        let plain = Self::implied_refs_len(span, HASH_SIZE);
+        let encrypted = Self::implied_refs_len(span, EncryptedChunkRef::SIZE);
+        let holds = |implied: Option<usize>, ref_size: usize| {
+            implied.is_some_and(|len| match data_len.checked_sub(len) {
+                Some(0) => true,
+                Some(parities) => coded && parities.is_multiple_of(ref_size),
+                None => false,
+            })
+        };
+        if holds(plain, HASH_SIZE) || holds(encrypted, EncryptedChunkRef::SIZE) {
+            return Ok(());
+        }
+        Err(ChunkError::invalid_size(
+            "intermediate span over a mismatched reference list",
+            plain.unwrap_or(BODY_SIZE),
+            data_len,
+        ))
+    }
+
+    /// The reference-list length an intermediate `span` implies for
+    /// references of `ref_size` bytes: one per child subtree, where a child
+    /// covers the largest `BODY_SIZE * branches^k` that still leaves more
+    /// than one child. `None` when the list could not fit a body.
+    fn implied_refs_len(span: u64, ref_size: usize) -> Option<usize> {
+        let branches = crate::cast::u64_from_usize(BODY_SIZE.checked_div(ref_size)?);
+        if branches < 2 {
+            return None;
+        }
+        let mut child = crate::cast::u64_from_usize(BODY_SIZE);
+        while let Some(parent) = child.checked_mul(branches)
+            && span > parent
+        {
+            child = parent;
+        }
+        let refs = usize::try_from(span.div_ceil(child)).ok()?;
+        refs.checked_mul(ref_size).filter(|&len| len <= BODY_SIZE)
+    }
+
     /// Append the body wire bytes (`span || payload`) to `out`.
     ///
     /// The sole body encoder: the standalone [`Bytes`] conversion and the
@@ -73,7 +146,7 @@ impl<const BODY_SIZE: usize> BmtBody<BODY_SIZE> {
         out.extend_from_slice(self.data.as_ref());
     }
 
-    /// The span half of the body wire encoding, serialised little-endian.
+    /// The span half of the body wire encoding.
     pub(crate) const fn span_bytes(&self) -> [u8; SPAN_SIZE] {
         self.span.to_le_bytes()
     }
@@ -90,7 +163,7 @@ impl<const BODY_SIZE: usize> BmtBody<BODY_SIZE> {
 
     fn calculate_hash(&self) -> DerivedAddress {
         let mut hasher: Hasher<BODY_SIZE> = Hasher::new();
-        hasher.set_span(self.span);
+        hasher.set_span(self.span.get());
         hasher.update(self.data.as_ref());
         hasher.sum_derived()
     }
@@ -108,12 +181,28 @@ impl<const BODY_SIZE: usize> BmtBody<BODY_SIZE> {
     /// `anchor`, and the body is borrowed (nothing is cloned).
     pub fn transformed_root(&self, anchor: &[u8]) -> alloy_primitives::B256 {
         let mut hasher: Hasher<BODY_SIZE> = Hasher::with_prefix(anchor);
-        hasher.set_span(self.span);
+        hasher.set_span(self.span.get());
         hasher.update(self.data.as_ref());
         hasher.sum()
     }
 }
 
+impl BmtBody<DEFAULT_BODY_SIZE> {
+    /// Proof that the 32-byte payload segment at `index` is part of this
+    /// body, verifiable against [`hash`](Self::hash).
+    ///
+    /// Only at the default body size, the tree geometry [`Prover`] covers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `index` is past the last segment of the tree.
+    pub fn prove_segment(&self, index: usize) -> Result<Proof> {
+        let mut hasher: Hasher = Hasher::new();
+        hasher.set_span(self.span.get());
+        hasher.generate_proof(self.data.as_ref(), index)
+    }
+}
+
 fn validate_data<const BODY_SIZE: usize>(data: impl Into<Bytes>) -> error::Result<Bytes> {
     let data = data.into();
     if data.len() > BODY_SIZE {
@@ -137,7 +226,6 @@ impl<const BODY_SIZE: usize> From<BmtBody<BODY_SIZE>> for Bytes {
 impl<const BODY_SIZE: usize> TryFrom<Bytes> for BmtBody<BODY_SIZE> {
     type Error = PrimitivesError;
 
-    #[allow(clippy::unwrap_used)] // infallible: split_to(SPAN_SIZE) yields exactly 8 bytes (length checked above)
     fn try_from(mut buf: Bytes) -> Result<Self> {
         if buf.len() < SPAN_SIZE {
             return Err(ChunkError::invalid_size(
@@ -149,7 +237,7 @@ impl<const BODY_SIZE: usize> TryFrom<Bytes> for BmtBody<BODY_SIZE> {
         }
 
         let span_bytes = buf.split_to(SPAN_SIZE);
-        let span = u64::from_le_bytes(span_bytes.as_ref().try_into().unwrap());
+        let span = Span::try_from(span_bytes.as_ref())?.get();
         let data = buf;
 
         Self::builder().with_span(span).with_data(data)?.build()
@@ -260,7 +348,7 @@ impl<const BODY_SIZE: usize> BmtBodyBuilder<BODY_SIZE, ReadyToBuild> {
     #[allow(clippy::unwrap_used)] // the ReadyToBuild typestate guarantees span and data are Some
     pub(crate) fn build(self) -> Result<BmtBody<BODY_SIZE>> {
         Ok(BmtBody::new_unchecked(
-            self.span.unwrap(),
+            Span::new(self.span.unwrap()),
             self.data.unwrap(),
         ))
     }
@@ -271,16 +359,26 @@ impl<'a, const BODY_SIZE: usize> arbitrary::Arbitrary<'a> for BmtBody<BODY_SIZE>
     #[allow(clippy::arithmetic_side_effects, clippy::unwrap_used)] // test-input generator: BODY_SIZE + 1 sums small constants, and the builder cannot fail on the generated span/data combination
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
         // Decide whether to generate a leaf chunk (span == data_len) or an
-        // intermediate chunk (span > BODY_SIZE, full body).
+        // intermediate chunk (span > BODY_SIZE over the references it implies).
         let is_leaf: bool = u.arbitrary()?;
+        let branches = crate::cast::u64_from_usize(BODY_SIZE / HASH_SIZE);
 
-        let (span, data_len) = if is_leaf {
+        let (span, data_len) = if is_leaf || branches < 2 {
             let data_len: usize = u.int_in_range(0..=BODY_SIZE)?;
             (crate::cast::u64_from_usize(data_len), data_len)
         } else {
-            // Intermediate node: span exceeds BODY_SIZE, body is always full.
-            let span = u.int_in_range(crate::cast::u64_from_usize(BODY_SIZE) + 1..=u64::MAX)?;
-            (span, BODY_SIZE)
+            // Intermediate node: `refs` children of `child` bytes each, the
+            // last one possibly partial.
+            let mut child = crate::cast::u64_from_usize(BODY_SIZE);
+            for _ in 0..u.int_in_range(0..=8u8)? {
+                match child.checked_mul(branches * branches) {
+                    Some(_) => child *= branches,
+                    None => break,
+                }
+            }
+            let refs = u.int_in_range(2..=branches)?;
+            let span = u.int_in_range(child * (refs - 1) + 1..=child * refs)?;
+            (span, crate::cast::usize_from_u64(refs) * HASH_SIZE)
         };
 
         let mut buf = vec![0; data_len];
@@ -8,7 +8,7 @@ use alloy_primitives::{B256, hex};
 use bytes::{Bytes, BytesMut};
 use std::fmt;
 
-use crate::bmt::DEFAULT_BODY_SIZE;
+use crate::bmt::{DEFAULT_BODY_SIZE, Hasher, Span};
 use crate::error::Result;
 use crate::wire;
 
@@ -88,6 +88,27 @@ impl<const BODY_SIZE: usize> ContentChunk<BODY_SIZE> {
         ))
     }
 
+    /// The empty chunk: span 0 and no payload.
+    ///
+    /// This is the one canonical empty chunk. [`new`](Self::new) on empty
+    /// data and a wire decode of the bare 8-byte zero span build the same
+    /// body, so all three share an address: the root of an empty file. At the
+    /// default body size that is bee's
+    /// `b34ca8c22b9e982354f9c7f50b470d66db428d880c8a904d5fe4ec9713171526`;
+    /// other body sizes hash a different zero tree and differ.
+    ///
+    /// ```
+    /// use nectar_primitives::{ChunkOps, DefaultContentChunk};
+    ///
+    /// let empty = DefaultContentChunk::empty();
+    /// assert_eq!(empty.span(), 0);
+    /// assert_eq!(empty.address(), DefaultContentChunk::new(Vec::new()).unwrap().address());
+    /// ```
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self::from_body(BmtBody::new_unchecked(Span::ZERO, Bytes::new()))
+    }
+
     /// Create a ContentChunk from a pre-existing BmtBody.
     ///
     /// This is an advanced method for when you already have a BmtBody,
@@ -119,6 +140,39 @@ impl<const BODY_SIZE: usize> ContentChunk<BODY_SIZE> {
     }
 }
 
+/// The address [`ContentChunk::new`] would give `data`, without building the
+/// chunk.
+///
+/// Hashes `data` under its own length as span, straight from the borrowed
+/// slice: no body is copied and no chunk or cache is allocated. For
+/// address-only work such as dedup checks.
+///
+/// ```
+/// use nectar_primitives::{ChunkOps, DefaultContentChunk, content_address};
+///
+/// let data = b"only the address, please";
+/// let address = content_address(data).unwrap();
+/// assert_eq!(address, *DefaultContentChunk::new(data.as_slice()).unwrap().address());
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if `data` exceeds [`DEFAULT_BODY_SIZE`].
+pub fn content_address(data: &[u8]) -> Result<ChunkAddress> {
+    if data.len() > DEFAULT_BODY_SIZE {
+        return Err(ChunkError::invalid_size(
+            "data exceeds maximum chunk size",
+            DEFAULT_BODY_SIZE,
+            data.len(),
+        )
+        .into());
+    }
+    let mut hasher: Hasher = Hasher::new();
+    hasher.set_span(crate::cast::u64_from_usize(data.len()));
+    hasher.update(data);
+    Ok(ChunkAddress::from(hasher.sum()))
+}
+
 /// Result of encrypting a content chunk.
 #[cfg(feature = "encryption")]
 #[derive(Debug, Clone)]
@@ -153,7 +207,7 @@ impl<const BODY_SIZE: usize> EncryptedContentChunk<BODY_SIZE> {
     #[allow(clippy::indexing_slicing)] // a ContentChunk's wire bytes always start with an 8-byte span, so [..SPAN_SIZE] holds
     pub fn decrypt(&self) -> Result<ContentChunk<BODY_SIZE>> {
         use super::encryption::transcrypt;
-        use crate::bmt::SPAN_SIZE;
+        use crate::bmt::{SPAN_SIZE, Span};
 
         let encrypted_data: Bytes = self.chunk.clone().into();
         let key = self.encrypted_ref.key();
@@ -165,7 +219,7 @@ impl<const BODY_SIZE: usize> EncryptedContentChunk<BODY_SIZE> {
         let span_ctr = (BODY_SIZE / super::encryption::EncryptionKey::SIZE) as u32;
         let mut span_buf = [0u8; SPAN_SIZE];
         transcrypt(key, span_ctr, &encrypted_data[..SPAN_SIZE], &mut span_buf)?;
-        let data_length = crate::cast::usize_from_u64(u64::from_le_bytes(span_buf));
+        let data_length = crate::cast::usize_from_u64(Span::from_le_bytes(span_buf).get());
 
         let decrypted =
             super::encryption::decrypt_chunk_data::<BODY_SIZE>(&encrypted_data, key, data_length)?;
@@ -280,6 +334,12 @@ mod tests {
             prop_assert_eq!(new_chunk.address(), chunk.address());
         }
 
+        #[test]
+        fn test_content_address_matches_new(data in proptest::collection::vec(any::<u8>(), 0..=DEFAULT_BODY_SIZE)) {
+            let chunk = DefaultContentChunk::new(data.clone()).unwrap();
+            prop_assert_eq!(content_address(&data).unwrap(), *chunk.address());
+        }
+
         #[test]
         fn test_new_content_chunk(data in proptest::collection::vec(any::<u8>(), 0..DEFAULT_BODY_SIZE)) {
             let chunk = DefaultContentChunk::new(data.clone()).unwrap();
@@ -354,6 +414,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn empty_chunk_has_one_canonical_address() {
+        let bee_empty = b256!("b34ca8c22b9e982354f9c7f50b470d66db428d880c8a904d5fe4ec9713171526");
+
+        let empty = DefaultContentChunk::empty();
+        assert_eq!(empty.address().as_ref(), bee_empty);
+        assert!(empty.data().is_empty());
+
+        let from_new = DefaultContentChunk::new(Vec::new()).unwrap();
+        let from_wire = DefaultContentChunk::try_from(0u64.to_le_bytes().as_slice()).unwrap();
+        assert_eq!(from_new, empty);
+        assert_eq!(from_wire, empty);
+        assert_eq!(from_wire.address(), empty.address());
+        assert_eq!(content_address(&[]).unwrap().as_ref(), bee_empty);
+    }
+
     #[test]
     fn test_specific_content_hash() {
         // Test with known valid data and hash
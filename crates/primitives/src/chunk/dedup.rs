@@ -0,0 +1,157 @@
+//! Skipping the BMT hash for content an uploader has already chunked.
+//!
+//! A chunk's address costs a full BMT hash: 255 keccak calls over a 4 KiB
+//! body. [`DedupCache`] remembers the address of recently built content
+//! chunks under a single keccak of their payload, so re-submitted data (a
+//! shared library block, an unchanged file region) resolves to its address
+//! without a tree hash, and the caller can skip storing it again.
+
+use alloy_primitives::{B256, keccak256};
+
+use crate::bmt::DEFAULT_BODY_SIZE;
+use crate::cache::FifoCache;
+use crate::error::Result;
+
+use super::address::ChunkAddress;
+use super::content::ContentChunk;
+use super::traits::ChunkOps;
+
+/// Outcome of [`DedupCache::get_or_build`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DedupLookup<const BODY_SIZE: usize = DEFAULT_BODY_SIZE> {
+    /// The data was seen before: its chunk's address, nothing was built.
+    Hit(ChunkAddress),
+    /// The data is new: the chunk built from it, now remembered.
+    Miss(ContentChunk<BODY_SIZE>),
+}
+
+impl<const BODY_SIZE: usize> DedupLookup<BODY_SIZE> {
+    /// The chunk address, whether remembered or freshly computed.
+    pub fn address(&self) -> ChunkAddress {
+        match self {
+            Self::Hit(address) => *address,
+            Self::Miss(chunk) => *chunk.address(),
+        }
+    }
+}
+
+/// Remembers the addresses of up to `capacity` recently built content
+/// chunks in a [`FifoCache`], keyed by the keccak256 of their payload.
+///
+/// ```
+/// use nectar_primitives::ChunkOps;
+/// use nectar_primitives::chunk::{DedupCache, DedupLookup};
+///
+/// let mut cache = DedupCache::<4096>::new(1024);
+/// let DedupLookup::Miss(chunk) = cache.get_or_build(b"shared block").unwrap() else {
+///     unreachable!("first sight of the data");
+/// };
+/// // Chunk and store it; the next upload of the same bytes skips both.
+/// let again = cache.get_or_build(b"shared block").unwrap();
+/// assert_eq!(again, DedupLookup::Hit(*chunk.address()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct DedupCache<const BODY_SIZE: usize = DEFAULT_BODY_SIZE> {
+    addresses: FifoCache<B256, ChunkAddress>,
+    hits: u64,
+    misses: u64,
+}
+
+impl<const BODY_SIZE: usize> DedupCache<BODY_SIZE> {
+    /// Creates a cache remembering at most `capacity` chunks.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            addresses: FifoCache::new(capacity),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Returns the remembered address of `data`, or builds its content chunk
+    /// and remembers that.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` exceeds `BODY_SIZE`; nothing is remembered.
+    pub fn get_or_build(&mut self, data: &[u8]) -> Result<DedupLookup<BODY_SIZE>> {
+        let digest = keccak256(data);
+        if let Some(address) = self.addresses.get(&digest) {
+            self.hits = self.hits.saturating_add(1);
+            return Ok(DedupLookup::Hit(*address));
+        }
+
+        let chunk = ContentChunk::new(data.to_vec())?;
+        self.misses = self.misses.saturating_add(1);
+        self.addresses.insert(digest, *chunk.address());
+        Ok(DedupLookup::Miss(chunk))
+    }
+
+    /// Number of chunks currently remembered.
+    pub fn len(&self) -> usize {
+        self.addresses.len()
+    }
+
+    /// Whether no chunk is remembered.
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty()
+    }
+
+    /// Maximum number of chunks remembered.
+    pub const fn capacity(&self) -> usize {
+        match self.addresses.capacity() {
+            Some(capacity) => capacity,
+            None => usize::MAX,
+        }
+    }
+
+    /// Lookups answered from the cache.
+    pub const fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Lookups that built a chunk.
+    pub const fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Forgets every remembered chunk, keeping the counters.
+    pub fn clear(&mut self) {
+        self.addresses.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Cache = DedupCache<DEFAULT_BODY_SIZE>;
+
+    #[test]
+    fn hits_skip_the_build_and_match_its_address() {
+        let mut cache = Cache::new(8);
+        let first = cache.get_or_build(b"hello").unwrap();
+        let DedupLookup::Miss(chunk) = &first else {
+            panic!("expected a miss, got {first:?}");
+        };
+        assert_eq!(
+            *chunk.address(),
+            *ContentChunk::<DEFAULT_BODY_SIZE>::new(b"hello".as_slice())
+                .unwrap()
+                .address()
+        );
+
+        assert_eq!(
+            cache.get_or_build(b"hello").unwrap(),
+            DedupLookup::Hit(first.address())
+        );
+        assert!(matches!(
+            cache.get_or_build(b"world").unwrap(),
+            DedupLookup::Miss(_)
+        ));
+        assert_eq!((cache.hits(), cache.misses(), cache.len()), (1, 2, 2));
+
+        // Oversized data is refused and not remembered.
+        assert!(cache.get_or_build(&[0; DEFAULT_BODY_SIZE + 1]).is_err());
+        assert_eq!(cache.len(), 2);
+    }
+}
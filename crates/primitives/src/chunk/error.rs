@@ -1,6 +1,7 @@
 use super::address::ChunkAddress;
 use thiserror::Error;
 
+use super::type_id::ChunkTypeId;
 use super::type_tag::ChunkTypeTag;
 
 /// Result type for chunk operations
@@ -46,11 +47,35 @@ pub enum ChunkError {
     #[error("Invalid chunk signature: {0}")]
     InvalidSignature(String),
 
+    /// Single-owner chunk was signed by someone other than the expected owner
+    #[error("Chunk owner mismatch: expected {expected}, got {actual}")]
+    OwnerMismatch {
+        /// Owner the chunk was checked against
+        expected: alloy_primitives::Address,
+        /// Owner recovered from the chunk's signature
+        actual: alloy_primitives::Address,
+    },
+
     /// Unsupported chunk type tag: an unknown id, or an unknown version of a
     /// known id (each `(id, version)` pair is a distinct acceptance rule)
     #[error("Unsupported chunk type tag: {0}")]
     UnsupportedTag(ChunkTypeTag),
 
+    /// A custom chunk type claimed an id in the standard range (0-127),
+    /// which belongs to the built-in types
+    #[error("Chunk type id {0} is reserved for standard types")]
+    ReservedTypeId(ChunkTypeId),
+
+    /// Fewer dispersed replicas than requested landed in distinct
+    /// neighbourhoods
+    #[error("Only {found} of {requested} dispersed replicas fit distinct neighbourhoods")]
+    InsufficientReplicas {
+        /// Replicas requested
+        requested: usize,
+        /// Distinct neighbourhoods the 256 mined bytes reached
+        found: usize,
+    },
+
     /// Wire buffer underrun
     #[error(transparent)]
     Underrun(#[from] crate::wire::Underrun),
@@ -85,4 +110,14 @@ impl ChunkError {
     pub const fn unsupported_tag(tag: ChunkTypeTag) -> Self {
         Self::UnsupportedTag(tag)
     }
+
+    /// Construct a [`ReservedTypeId`](Self::ReservedTypeId) error
+    pub const fn reserved_type_id(id: ChunkTypeId) -> Self {
+        Self::ReservedTypeId(id)
+    }
+
+    /// Construct an [`InsufficientReplicas`](Self::InsufficientReplicas) error
+    pub const fn insufficient_replicas(requested: usize, found: usize) -> Self {
+        Self::InsufficientReplicas { requested, found }
+    }
 }
@@ -7,7 +7,7 @@
 
 use bytes::{Bytes, BytesMut};
 
-use crate::bmt::DEFAULT_BODY_SIZE;
+use crate::bmt::{DEFAULT_BODY_SIZE, Proof};
 use crate::cache::OnceCache;
 use crate::error::{PrimitivesError, Result};
 use crate::wire;
@@ -65,6 +65,61 @@ impl<H: ChunkHeader, const BODY_SIZE: usize> ChunkInner<H, BODY_SIZE> {
     }
 }
 
+impl<H: ChunkHeader> ChunkInner<H, DEFAULT_BODY_SIZE> {
+    /// Proof that the payload segment at `index` is part of this chunk's
+    /// body, without rebuilding the body from the wire form.
+    ///
+    /// The proof verifies against the body's BMT root,
+    /// `self.body().hash()`: the address of a content chunk, and the wrapped
+    /// content address a single-owner chunk's signature commits to.
+    ///
+    /// ```
+    /// use alloy_signer_local::PrivateKeySigner;
+    /// use nectar_primitives::{DefaultSingleOwnerChunk, SocId};
+    ///
+    /// let signer = PrivateKeySigner::random();
+    /// let chunk = DefaultSingleOwnerChunk::new(SocId::ZERO, vec![7u8; 100], &signer).unwrap();
+    ///
+    /// let proof = chunk.prove_segment(2).unwrap();
+    /// assert!(proof.verify(&chunk.body().hash().into()).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `index` is past the last segment of the tree.
+    pub fn prove_segment(&self, index: usize) -> Result<Proof> {
+        self.body.prove_segment(index)
+    }
+}
+
+/// Derives and caches the address of every chunk in `chunks`, spread across
+/// the rayon pool.
+///
+/// A chunk read back from storage hashes its body lazily, on the first
+/// [`ChunkOps::address`] call. Warming a freshly loaded set up front turns
+/// that serial cold-cache cost into one parallel pass; afterwards each
+/// `address()` is a cache read.
+///
+/// ```
+/// use nectar_primitives::{ChunkOps, DefaultContentChunk, warm_addresses};
+///
+/// let chunks: Vec<_> = (0u8..16)
+///     .map(|i| DefaultContentChunk::new(vec![i; 1024]).unwrap())
+///     .collect();
+/// warm_addresses(&chunks);
+/// assert!(!chunks[0].address().is_zero());
+/// ```
+pub fn warm_addresses<H, const BODY_SIZE: usize>(chunks: &[ChunkInner<H, BODY_SIZE>])
+where
+    H: ChunkHeader + Sync,
+{
+    use rayon::prelude::*;
+
+    chunks.par_iter().for_each(|chunk| {
+        chunk.address();
+    });
+}
+
 impl<H: ChunkHeader, const BODY_SIZE: usize> ChunkOps for ChunkInner<H, BODY_SIZE> {
     fn address(&self) -> &ChunkAddress {
         self.address
@@ -103,6 +158,12 @@ impl<H: ChunkHeader, const BODY_SIZE: usize> ChunkOps for ChunkInner<H, BODY_SIZ
             .seal_transformed(self.address(), self.body.transformed_root(anchor))
     }
 
+    fn write_to(&self, buf: &mut BytesMut) {
+        buf.reserve(self.size());
+        self.header.encode(buf);
+        self.body.encode(buf);
+    }
+
     fn into_bytes(self) -> Bytes {
         self.into()
     }
@@ -135,8 +196,7 @@ impl<H: ChunkHeader + Eq, const BODY_SIZE: usize> Eq for ChunkInner<H, BODY_SIZE
 impl<H: ChunkHeader, const BODY_SIZE: usize> From<ChunkInner<H, BODY_SIZE>> for Bytes {
     fn from(chunk: ChunkInner<H, BODY_SIZE>) -> Self {
         let mut bytes = BytesMut::with_capacity(chunk.size());
-        chunk.header.encode(&mut bytes);
-        chunk.body.encode(&mut bytes);
+        chunk.write_to(&mut bytes);
         bytes.freeze()
     }
 }
@@ -150,6 +210,9 @@ impl<H: ChunkHeader, const BODY_SIZE: usize> TryFrom<Bytes> for ChunkInner<H, BO
 
         // decode consumed exactly H::SIZE bytes, so the slice holds.
         let body = BmtBody::try_from(bytes.slice(H::SIZE..))?;
+        if H::STRICT_SPAN {
+            body.validate_span()?;
+        }
 
         Ok(Self::from_header_and_body(header, body))
     }
@@ -235,6 +298,69 @@ mod tests {
         assert!(soc.verify(soc.address()).is_ok());
     }
 
+    /// `write_to` appends exactly the `Into<Bytes>` encoding, so one buffer
+    /// serializes a run of chunks back to back.
+    #[test]
+    fn write_to_matches_into_bytes() {
+        let cac = DefaultContentChunk::new(b"reused buffer".to_vec()).unwrap();
+        let soc = DefaultSingleOwnerChunk::try_from(soc_test_vector().as_slice()).unwrap();
+        let cac_wire: Bytes = cac.clone().into();
+        let soc_wire: Bytes = soc.clone().into();
+
+        let mut buf = BytesMut::new();
+        cac.write_to(&mut buf);
+        assert_eq!(buf.as_ref(), cac_wire.as_ref());
+        assert_eq!(buf.len(), cac.size());
+
+        soc.write_to(&mut buf);
+        assert_eq!(&buf[cac_wire.len()..], soc_wire.as_ref());
+
+        buf.clear();
+        soc.write_to(&mut buf);
+        assert_eq!(buf.freeze(), soc_wire);
+    }
+
+    /// Segment proofs come straight off the carrier for both aliases and
+    /// verify against the body root: the content address, or the address a
+    /// single-owner chunk wraps.
+    #[test]
+    fn prove_segment_verifies_against_the_body_hash() {
+        let cac = DefaultContentChunk::new(vec![0x5a; 1000]).unwrap();
+        let proof = cac.prove_segment(17).unwrap();
+        assert_eq!(proof.segment.as_slice(), &cac.data()[17 * 32..18 * 32]);
+        assert!(proof.verify(&(*cac.address()).into()).unwrap());
+
+        let soc = DefaultSingleOwnerChunk::try_from(soc_test_vector().as_slice()).unwrap();
+        let proof = soc.prove_segment(0).unwrap();
+        assert_eq!(&proof.segment[..3], b"foo");
+        assert!(proof.verify(&soc.body().hash().into()).unwrap());
+        assert!(proof.verify(&(*soc.unwrap_cac().address()).into()).unwrap());
+        assert!(!proof.verify(&(*soc.address()).into()).unwrap());
+
+        assert!(soc.prove_segment(crate::bmt::BRANCHES).is_err());
+    }
+
+    /// Warming fills each cache with the address a cold chunk derives.
+    #[test]
+    fn warm_addresses_caches_the_lazy_address() {
+        let chunks: Vec<_> = (0u16..64)
+            .map(|i| DefaultContentChunk::new(i.to_be_bytes().repeat(100)).unwrap())
+            .collect();
+        let cold: Vec<_> = chunks
+            .iter()
+            .map(|chunk| DefaultContentChunk::from_body(chunk.body().clone()))
+            .collect();
+
+        warm_addresses(&chunks);
+        for (warm, cold) in chunks.iter().zip(&cold) {
+            assert!(warm.address.get().is_some());
+            assert!(cold.address.get().is_none());
+            assert_eq!(warm.address(), cold.address());
+        }
+
+        warm_addresses::<super::super::content::CacHeader, DEFAULT_BODY_SIZE>(&[]);
+    }
+
     /// The carrier derives type metadata from the header predicate.
     #[test]
     fn type_metadata_comes_from_the_header() {
@@ -64,6 +64,9 @@
 //!   member pairs.
 //! - Tag uniqueness: force the compile-time guard with a
 //!   `const _: () = MyRegistry::DISTINCT_TAGS;` item.
+//! - Reserved ids: take the custom id from [`ChunkTypeId::try_custom`], and
+//!   force `const _: () = MyRegistry::RESERVED_IDS;` so no custom member
+//!   can claim a standard-range id and receive built-in chunks.
 //!
 //! A derive for the envelope delegation is deferred until a registry has a
 //! third member: hand-written impls are small at two variants, and a derive
@@ -181,13 +184,13 @@
 //! #         }
 //! #     }
 //! #
-//! #     fn into_bytes(self) -> Bytes {
+//! #     fn write_to(&self, buf: &mut BytesMut) {
 //! #         match self {
-//! #             Self::Content(c) => c.into_bytes(),
-//! #             Self::Tagged(c) => c.into_bytes(),
+//! #             Self::Content(c) => c.write_to(buf),
+//! #             Self::Tagged(c) => c.write_to(buf),
 //! #         }
 //! #     }
-//!     // data, size, span, owner, transformed_address, and into_bytes
+//!     // data, size, span, owner, transformed_address, and write_to
 //!     // delegate the same way.
 //! }
 //!
@@ -242,6 +245,7 @@
 //! }
 //!
 //! const _: () = ForkChunkSet::DISTINCT_TAGS;
+//! const _: () = ForkChunkSet::RESERVED_IDS;
 //!
 //! // The typestate currency accepts the grown registry unchanged.
 //! let payload = b"custom swarm payload";
@@ -263,6 +267,7 @@ mod any_chunk;
 mod bmt_body;
 mod chunk_type;
 mod content;
+mod dedup;
 pub mod encryption;
 pub(crate) mod error;
 mod inner;
@@ -270,6 +275,7 @@ mod reference;
 mod registry;
 mod single_owner;
 mod soc_id;
+mod splitter;
 mod traits;
 mod trust;
 mod type_id;
@@ -281,7 +287,10 @@ pub mod wasm;
 // Re-export the address type, error type, and core traits
 pub use address::ChunkAddress;
 pub use error::ChunkError;
-pub use inner::ChunkInner;
+pub use inner::{ChunkInner, warm_addresses};
+
+#[cfg(feature = "erasure")]
+pub(crate) use bmt_body::BmtBody;
 pub use traits::{ChunkHeader, ChunkOps, HeaderedChunk};
 
 // Re-export the typestate trust carrier
@@ -302,8 +311,10 @@ pub use type_tag::{ChunkTypeTag, ChunkVersion, TagWireError};
 // Re-export the concrete chunk types and their headers
 #[cfg(feature = "encryption")]
 pub use content::EncryptedContentChunk;
-pub use content::{CacHeader, ContentChunk};
+pub use content::{CacHeader, ContentChunk, content_address};
+pub use dedup::{DedupCache, DedupLookup};
 #[cfg(feature = "encryption")]
 pub use encryption::ChunkEncrypt;
 pub use single_owner::{SingleOwnerChunk, SocHeader};
 pub use soc_id::SocId;
+pub use splitter::ChunkSplitter;
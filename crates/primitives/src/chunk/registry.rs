@@ -57,6 +57,45 @@ impl ChunkTypeInfo {
         }
         None
     }
+
+    /// Find a member claiming a standard-range id it does not own, if any.
+    ///
+    /// Ids 0-127 belong to the built-in types: an entry there must carry the
+    /// built-in name of its id, so a custom header cannot take over a
+    /// built-in id (or an unassigned standard one) and receive its chunks.
+    /// `const` so registries can assert it at compile time
+    /// ([`ChunkRegistry::RESERVED_IDS`]).
+    pub const fn reserved_tag(members: &[Self]) -> Option<ChunkTypeTag> {
+        let mut rest = members;
+        while let [head, tail @ ..] = rest {
+            if head.tag.id.is_standard() {
+                let owned = match head.tag.id.name() {
+                    Some(name) => str_eq(name, head.name),
+                    None => false,
+                };
+                if !owned {
+                    return Some(head.tag);
+                }
+            }
+            rest = tail;
+        }
+        None
+    }
+}
+
+/// Byte-wise string equality usable in `const` contexts.
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (mut a, mut b) = (a.as_bytes(), b.as_bytes());
+    loop {
+        match (a, b) {
+            ([], []) => return true,
+            ([x, a_rest @ ..], [y, b_rest @ ..]) if *x == *y => {
+                a = a_rest;
+                b = b_rest;
+            }
+            _ => return false,
+        }
+    }
 }
 
 /// Compile-time registry of the chunk types one network accepts.
@@ -64,8 +103,9 @@ impl ChunkTypeInfo {
 /// The closed envelope type is the type-level set: [`Envelope`](Self::Envelope)
 /// carries the body size, so no const generic appears on the trait, and
 /// [`MEMBERS`](Self::MEMBERS) is its inspectable description. Implementations
-/// are hand-written per network; force the duplicate-tag guard with a
-/// `const _: () = MyRegistry::DISTINCT_TAGS;` item next to the impl. The
+/// are hand-written per network; force the duplicate-tag and reserved-id
+/// guards with `const _: () = MyRegistry::DISTINCT_TAGS;` and
+/// `const _: () = MyRegistry::RESERVED_IDS;` items next to the impl. The
 /// downstream extension pattern (custom header plus downstream-owned
 /// envelope) is worked through in the [`chunk`](crate::chunk) module docs.
 ///
@@ -97,6 +137,14 @@ pub trait ChunkRegistry: Send + Sync + 'static {
         "duplicate chunk type tag in registry MEMBERS"
     );
 
+    /// Compile-time reserved-id guard: evaluating this const fails the build
+    /// when a [`MEMBERS`](Self::MEMBERS) entry other than a built-in type
+    /// claims a standard-range id (see [`ChunkTypeInfo::reserved_tag`]).
+    const RESERVED_IDS: () = assert!(
+        ChunkTypeInfo::reserved_tag(Self::MEMBERS).is_none(),
+        "custom chunk type claims a reserved standard id in registry MEMBERS"
+    );
+
     /// Whether this registry accepts the exact `(id, version)` tag.
     fn supports(tag: ChunkTypeTag) -> bool {
         Self::MEMBERS.iter().any(|member| member.tag == tag)
@@ -107,6 +155,21 @@ pub trait ChunkRegistry: Send + Sync + 'static {
         Self::MEMBERS.iter().any(|member| member.tag.id == id)
     }
 
+    /// The distinct chunk type ids this registry accepts, in
+    /// [`MEMBERS`](Self::MEMBERS) order.
+    ///
+    /// An id registered under several versions is listed once; query
+    /// [`supports_id`](Self::supports_id) for a single id.
+    fn registered_type_ids() -> Vec<ChunkTypeId> {
+        let mut ids: Vec<ChunkTypeId> = Vec::with_capacity(Self::MEMBERS.len());
+        for member in Self::MEMBERS {
+            if !ids.contains(&member.tag.id) {
+                ids.push(member.tag.id);
+            }
+        }
+        ids
+    }
+
     /// Structurally decode the typed form produced by
     /// [`encode_typed`](Self::encode_typed): the tag routes to a member and
     /// the payload is decoded, but nothing certifies. The result is only a
@@ -179,6 +242,7 @@ impl<const BODY_SIZE: usize> ChunkRegistry for AnyChunkSet<BODY_SIZE> {
 }
 
 const _: () = StandardChunkSet::DISTINCT_TAGS;
+const _: () = StandardChunkSet::RESERVED_IDS;
 
 /// Registry that accepts only content-addressed chunks, carried directly as
 /// [`ContentChunk`]: a single-member set needs no envelope enum.
@@ -218,6 +282,7 @@ impl ChunkRegistry for ContentOnlyChunkSet {
 }
 
 const _: () = ContentOnlyChunkSet::DISTINCT_TAGS;
+const _: () = ContentOnlyChunkSet::RESERVED_IDS;
 
 #[cfg(test)]
 mod tests {
@@ -280,6 +345,55 @@ mod tests {
         assert_eq!(members[1].header_size, 97);
     }
 
+    #[test]
+    fn registered_type_ids_lists_each_id_once() {
+        struct VersionedSet;
+
+        impl ChunkRegistry for VersionedSet {
+            type Envelope = AnyChunk;
+
+            const MEMBERS: &'static [ChunkTypeInfo] = &[
+                ChunkTypeInfo::of::<CacHeader>(),
+                ChunkTypeInfo {
+                    tag: ChunkTypeTag::new(ChunkTypeId::custom(200), ChunkVersion::new(0)),
+                    name: "custom",
+                    header_size: 0,
+                },
+                ChunkTypeInfo {
+                    tag: ChunkTypeTag::new(ChunkTypeId::custom(200), ChunkVersion::new(1)),
+                    name: "custom",
+                    header_size: 0,
+                },
+            ];
+
+            fn parse_typed(bytes: &[u8]) -> Result<Self::Envelope> {
+                StandardChunkSet::parse_typed(bytes)
+            }
+
+            fn decode_wire(address: &ChunkAddress, data: Bytes) -> Result<Self::Envelope> {
+                StandardChunkSet::decode_wire(address, data)
+            }
+
+            fn encode_typed(chunk: &Self::Envelope) -> Vec<u8> {
+                StandardChunkSet::encode_typed(chunk)
+            }
+        }
+        const _: () = VersionedSet::DISTINCT_TAGS;
+
+        assert_eq!(
+            VersionedSet::registered_type_ids(),
+            [ChunkTypeId::CONTENT, ChunkTypeId::custom(200)]
+        );
+        assert_eq!(
+            StandardChunkSet::registered_type_ids(),
+            [ChunkTypeId::CONTENT, ChunkTypeId::SINGLE_OWNER]
+        );
+        assert_eq!(
+            ContentOnlyChunkSet::registered_type_ids(),
+            [ChunkTypeId::CONTENT]
+        );
+    }
+
     #[test]
     fn content_only_supports() {
         assert!(ContentOnlyChunkSet::supports(CAC_TAG));
@@ -309,6 +423,35 @@ mod tests {
         assert_eq!(ChunkTypeInfo::duplicate_tag(&dup), Some(CAC_TAG));
     }
 
+    #[test]
+    fn reserved_tag_scan() {
+        assert_eq!(ChunkTypeInfo::reserved_tag(StandardChunkSet::MEMBERS), None);
+
+        let custom = |id: ChunkTypeId| ChunkTypeInfo {
+            tag: ChunkTypeTag::new(id, ChunkVersion::new(0)),
+            name: "tagged",
+            header_size: 0,
+        };
+        let accepted = [
+            ChunkTypeInfo::of::<CacHeader>(),
+            custom(ChunkTypeId::custom(0xe0)),
+        ];
+        assert_eq!(ChunkTypeInfo::reserved_tag(&accepted), None);
+
+        // A custom member on a built-in id, or on an unassigned standard one.
+        for id in [
+            ChunkTypeId::CONTENT,
+            ChunkTypeId::SINGLE_OWNER,
+            ChunkTypeId::new(50),
+        ] {
+            let rejected = [ChunkTypeInfo::of::<SocHeader>(), custom(id)];
+            assert_eq!(
+                ChunkTypeInfo::reserved_tag(&rejected),
+                Some(ChunkTypeTag::new(id, ChunkVersion::new(0)))
+            );
+        }
+    }
+
     #[test]
     fn standard_typed_round_trip() {
         let content = DefaultContentChunk::new(&b"hello registry"[..]).unwrap();
@@ -15,6 +15,7 @@ use crate::bmt::DEFAULT_BODY_SIZE;
 use crate::chunk::error::{self, ChunkError};
 use crate::error::Result;
 use crate::wire;
+use crate::xor_metric::XorMetric;
 
 use super::address::ChunkAddress;
 use super::bmt_body::BmtBody;
@@ -91,13 +92,30 @@ impl SocHeader {
     }
 
     /// The SOC address derivation: `keccak256(id || owner)`.
-    fn address_for(id: SocId, owner: Address) -> ChunkAddress {
+    pub(crate) fn address_for(id: SocId, owner: Address) -> ChunkAddress {
         let mut hasher = Keccak256::new();
         hasher.update(id.as_slice());
         hasher.update(owner);
         ChunkAddress::from(hasher.finalize())
     }
 
+    /// The `mined_byte` values whose dispersed replica of a body hashing to
+    /// `target` lands within proximity order `po` of it, ascending.
+    fn replica_bytes_near(target: ChunkAddress, po: u8) -> Vec<u8> {
+        // `proximity` caps at MAX_PO, so a higher order would admit nothing.
+        let po = po.min(crate::MAX_PO);
+        let mut id = B256::from(target);
+        (0..=u8::MAX)
+            .filter(|&byte| {
+                if let Some(first) = id.first_mut() {
+                    *first = byte;
+                }
+                let replica = Self::address_for(SocId::from(id), DISPERSED_REPLICA_OWNER);
+                replica.proximity(&target).get() >= po
+            })
+            .collect()
+    }
+
     /// Dispersed-replica rule: `id[1..]` must equal `body_hash[1..]`; only the
     /// first id byte is mined.
     fn is_valid_replica(&self, body_hash: B256) -> bool {
@@ -113,6 +131,9 @@ impl ChunkHeader for SocHeader {
     const VERSION: ChunkVersion = ChunkVersion::new(0);
     const NAME: &'static str = "single_owner";
     const SIZE: usize = ID_SIZE + SIGNATURE_SIZE;
+    /// The owner signs whatever body it likes, so its address cannot
+    /// vouch for the span.
+    const STRICT_SPAN: bool = true;
 
     /// Total commitment: an unrecoverable signature commits under the zero
     /// owner, an address [`validate`](ChunkHeader::validate) then rejects.
@@ -225,6 +246,74 @@ impl<const BODY_SIZE: usize> SingleOwnerChunk<BODY_SIZE> {
             .build()
     }
 
+    /// Lists the `mined_byte` values whose dispersed replica of `body` lands
+    /// within proximity order `po` of the body's content address, ascending.
+    ///
+    /// Each byte passed to [`new_dispersed_replica`](Self::new_dispersed_replica)
+    /// yields a replica at a different address; this finds the ones in the
+    /// original chunk's neighbourhood without signing any of them. Orders past
+    /// [`MAX_PO`](crate::MAX_PO) are measured at `MAX_PO`, and at most a
+    /// fraction `2^-po` of the bytes is expected to qualify.
+    pub fn replica_bytes_for_neighbourhood(body: &BmtBody<BODY_SIZE>, po: u8) -> Vec<u8> {
+        SocHeader::replica_bytes_near(body.hash(), po)
+    }
+
+    /// Mines `n` dispersed replicas of `body`, each in a different
+    /// neighbourhood.
+    ///
+    /// The address space is split into the `2^d` neighbourhoods of the
+    /// smallest `d` with `2^d >= n`, and the lowest `mined_byte` reaching each
+    /// unclaimed neighbourhood is kept until `n` are found, so the replicas
+    /// spread roughly evenly. Replicas come back ordered by `mined_byte`, and
+    /// the same body always yields the same set. This is bee's redundancy
+    /// dispersal: its levels ask for 2, 4, 8 or 16 replicas.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ChunkError::InsufficientReplicas` when the 256 candidate
+    /// bytes reach fewer than `n` distinct neighbourhoods, which is certain
+    /// past 256 and likely as `n` nears it.
+    pub fn dispersed_replica_set(body: BmtBody<BODY_SIZE>, n: usize) -> Result<Vec<Self>> {
+        let depth = n
+            .checked_next_power_of_two()
+            .ok_or(ChunkError::insufficient_replicas(n, 0))?
+            .trailing_zeros();
+        // Past depth 8 the neighbourhoods outnumber the mined bytes.
+        let shift = 8u32
+            .checked_sub(depth)
+            .ok_or(ChunkError::insufficient_replicas(n, 0))?;
+
+        let mut id = B256::from(body.hash());
+        let mut claimed = [false; 256];
+        let mut mined = Vec::with_capacity(n);
+        for byte in 0..=u8::MAX {
+            if mined.len() == n {
+                break;
+            }
+            if let Some(first) = id.first_mut() {
+                *first = byte;
+            }
+            let replica = SocHeader::address_for(SocId::from(id), DISPERSED_REPLICA_OWNER);
+            let leading = replica.as_bytes().first().copied().unwrap_or_default();
+            // A zero depth is a single neighbourhood.
+            let neighbourhood = leading.checked_shr(shift).unwrap_or(0);
+            if let Some(taken) = claimed.get_mut(usize::from(neighbourhood))
+                && !*taken
+            {
+                *taken = true;
+                mined.push(byte);
+            }
+        }
+
+        if mined.len() < n {
+            return Err(ChunkError::insufficient_replicas(n, mined.len()).into());
+        }
+        mined
+            .into_iter()
+            .map(|byte| Self::new_dispersed_replica(byte, body.clone()))
+            .collect()
+    }
+
     /// Create a SingleOwnerChunk from pre-computed parts.
     ///
     /// This is an advanced method for reconstructing chunks from storage
@@ -254,6 +343,26 @@ impl<const BODY_SIZE: usize> SingleOwnerChunk<BODY_SIZE> {
         self.header().owner(self.body().hash().into())
     }
 
+    /// Check that this chunk was signed by `expected`.
+    ///
+    /// Address verification only shows a chunk is internally consistent; a
+    /// feed or access-control reader also needs to know it came from a known
+    /// publisher. This does not check the address: pair it with
+    /// [`verify`](crate::chunk::ChunkOps::verify) for an untrusted chunk.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ChunkError::Signature` if the signature recovery fails, and
+    /// `ChunkError::OwnerMismatch` if it recovers another owner.
+    pub fn verify_owner(&self, expected: Address) -> error::Result<()> {
+        let actual = self.owner()?;
+        if actual == expected {
+            Ok(())
+        } else {
+            Err(ChunkError::OwnerMismatch { expected, actual })
+        }
+    }
+
     // Checks if the chunk is a valid dispersed replica
     #[cfg(test)]
     fn is_valid_replica(&self) -> bool {
@@ -570,6 +679,27 @@ mod tests {
             prop_assert!(chunk.verify(chunk.address()).is_ok());
         }
 
+        #[test]
+        fn replica_bytes_land_in_the_neighbourhood(
+            data in proptest::collection::vec(any::<u8>(), 1..DEFAULT_BODY_SIZE),
+            po in 0u8..4,
+        ) {
+            let body = BmtBody::<DEFAULT_BODY_SIZE>::builder().auto_from_data(data).unwrap().build().unwrap();
+            let target = body.hash();
+            let bytes = DefaultSingleOwnerChunk::replica_bytes_for_neighbourhood(&body, po);
+            if po == 0 {
+                prop_assert_eq!(bytes.len(), 256);
+            }
+            // Signing is slow: check a couple of bytes either side.
+            let left_out = (0..=u8::MAX).filter(|b| !bytes.contains(b)).take(2);
+            for byte in bytes.iter().copied().take(2).chain(left_out) {
+                let replica = DefaultSingleOwnerChunk::new_dispersed_replica(byte, body.clone()).unwrap();
+                prop_assert!(replica.is_valid_replica());
+                let inside = replica.address().proximity(&target).get() >= po;
+                prop_assert_eq!(inside, bytes.contains(&byte));
+            }
+        }
+
         #[test]
         fn test_chunk_creation(id in arb::<SocId>(), data in proptest::collection::vec(any::<u8>(), 1..DEFAULT_BODY_SIZE)) {
             let wallet = get_test_wallet();
@@ -659,6 +789,156 @@ mod tests {
         assert_eq!(chunk.data(), &data);
     }
 
+    #[test]
+    fn test_verify_owner() {
+        let wallet = get_test_wallet();
+        let chunk = DefaultSingleOwnerChunk::new(SocId::ZERO, b"foo".to_vec(), &wallet).unwrap();
+
+        assert!(chunk.verify_owner(wallet.address()).is_ok());
+
+        let other = PrivateKeySigner::random().address();
+        assert!(matches!(
+            chunk.verify_owner(other),
+            Err(ChunkError::OwnerMismatch { expected, actual })
+                if expected == other && actual == wallet.address()
+        ));
+    }
+
+    #[test]
+    fn intermediate_span_over_truncated_data_is_rejected() {
+        let wallet = get_test_wallet();
+        let chunk = DefaultSingleOwnerChunk::new(SocId::ZERO, vec![7u8; 100], &wallet).unwrap();
+        let mut wire = Vec::from(Bytes::from(chunk));
+
+        // Claim a 1 MiB subtree over the 100 payload bytes: two plain
+        // references of 512 KiB each.
+        wire[SocHeader::SIZE..SocHeader::SIZE + 8].copy_from_slice(&(1u64 << 20).to_le_bytes());
+        assert!(matches!(
+            DefaultSingleOwnerChunk::try_from(wire.as_slice()),
+            Err(PrimitivesError::Chunk(ChunkError::InvalidSize {
+                expected: 64,
+                actual: 100,
+                ..
+            }))
+        ));
+
+        // Whole references that miscount the span's children are rejected.
+        wire.truncate(SocHeader::SIZE + 8 + 96);
+        assert!(DefaultSingleOwnerChunk::try_from(wire.as_slice()).is_err());
+
+        // The implied plain or encrypted reference lists parse.
+        for refs_len in [64, 256] {
+            wire.resize(SocHeader::SIZE + 8 + refs_len, 7);
+            let chunk = DefaultSingleOwnerChunk::try_from(wire.as_slice()).unwrap();
+            assert_eq!(chunk.span(), 1 << 20);
+        }
+    }
+
+    /// A bee redundancy level in the top span byte is masked off, and parity
+    /// references may follow the data references.
+    #[test]
+    fn level_encoded_span_allows_parity_references() {
+        let wallet = get_test_wallet();
+        let chunk = DefaultSingleOwnerChunk::new(SocId::ZERO, vec![7u8; 64], &wallet).unwrap();
+        let mut wire = Vec::from(Bytes::from(chunk));
+
+        // Level 2 over a 1 MiB subtree: two data references, then parities.
+        let span = (1u64 << 20) | (0x82 << 56);
+        wire[SocHeader::SIZE..SocHeader::SIZE + 8].copy_from_slice(&span.to_le_bytes());
+        for refs_len in [64, 64 + 32, 64 + 3 * 32] {
+            wire.resize(SocHeader::SIZE + 8 + refs_len, 7);
+            let chunk = DefaultSingleOwnerChunk::try_from(wire.as_slice()).unwrap();
+            assert_eq!(chunk.span(), span);
+        }
+
+        // Fewer than the data references, or a partial parity reference,
+        // still disagree with the span.
+        for refs_len in [32, 100] {
+            wire.resize(SocHeader::SIZE + 8 + refs_len, 7);
+            assert!(DefaultSingleOwnerChunk::try_from(wire.as_slice()).is_err());
+        }
+    }
+
+    /// An encrypted chunk's ciphertext span over its padded payload survives
+    /// wrapping in a SOC and re-parsing.
+    #[test]
+    fn encrypted_body_round_trips_through_a_replica() {
+        let mut wire = 0x9d3a_71c4_0be2_5f18u64.to_le_bytes().to_vec();
+        wire.extend([0x5a; DEFAULT_BODY_SIZE]);
+        let cac = ContentChunk::<DEFAULT_BODY_SIZE>::try_from(wire.as_slice()).unwrap();
+
+        let replica =
+            DefaultSingleOwnerChunk::new_dispersed_replica(3, cac.body().clone()).unwrap();
+        let parsed = DefaultSingleOwnerChunk::try_from(Bytes::from(replica.clone())).unwrap();
+        assert_eq!(parsed, replica);
+        assert_eq!(parsed.span(), 0x9d3a_71c4_0be2_5f18);
+    }
+
+    /// Orders past `MAX_PO` are measured at `MAX_PO`, where a replica can
+    /// still qualify.
+    #[test]
+    fn replica_bytes_past_max_po_are_measured_at_max_po() {
+        // Mined so that byte 131's replica shares the first 31 bits.
+        let target = ChunkAddress::new(hex!(
+            "73000000000000a42f0000000000000000000000000000000000000000000000"
+        ));
+        for po in [crate::MAX_PO, 32, u8::MAX] {
+            assert_eq!(SocHeader::replica_bytes_near(target, po), [131]);
+        }
+    }
+
+    #[test]
+    fn test_dispersed_replica_set() {
+        let body = BmtBody::<DEFAULT_BODY_SIZE>::builder()
+            .auto_from_data(b"replicate me".to_vec())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        for n in [0, 1, 2, 4, 8, 16] {
+            let replicas = DefaultSingleOwnerChunk::dispersed_replica_set(body.clone(), n).unwrap();
+            assert_eq!(replicas.len(), n);
+
+            let mut first_bytes = Vec::new();
+            let mut neighbourhoods = Vec::new();
+            for replica in &replicas {
+                assert!(replica.is_valid_replica());
+                assert!(replica.verify(replica.address()).is_ok());
+                assert_eq!(replica.body(), &body);
+                first_bytes.push(replica.id().as_slice()[0]);
+                let depth = n.next_power_of_two().trailing_zeros();
+                neighbourhoods.push(replica.address().as_bytes()[0].checked_shr(8 - depth));
+            }
+            // Distinct mined bytes, lowest first.
+            assert!(first_bytes.windows(2).all(|pair| pair[0] < pair[1]));
+            neighbourhoods.sort_unstable();
+            neighbourhoods.dedup();
+            assert_eq!(neighbourhoods.len(), n);
+
+            // Mining is deterministic.
+            assert_eq!(
+                DefaultSingleOwnerChunk::dispersed_replica_set(body.clone(), n).unwrap(),
+                replicas
+            );
+        }
+
+        assert!(matches!(
+            DefaultSingleOwnerChunk::dispersed_replica_set(body.clone(), 257),
+            Err(PrimitivesError::Chunk(ChunkError::InsufficientReplicas {
+                requested: 257,
+                found: 0
+            }))
+        ));
+        // No power of two reaches it.
+        assert!(matches!(
+            DefaultSingleOwnerChunk::dispersed_replica_set(body, usize::MAX),
+            Err(PrimitivesError::Chunk(ChunkError::InsufficientReplicas {
+                requested: usize::MAX,
+                found: 0
+            }))
+        ));
+    }
+
     #[test]
     fn test_new_signed() {
         let id = SocId::ZERO;
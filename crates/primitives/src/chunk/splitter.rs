@@ -0,0 +1,191 @@
+//! Lazy content-chunk splitting over a reader.
+
+use std::io::{self, Read};
+
+use bytes::BytesMut;
+
+use crate::bmt::DEFAULT_BODY_SIZE;
+use crate::error::Result;
+
+use super::content::ContentChunk;
+
+/// Iterator cutting a reader into content chunks of up to `BODY_SIZE` bytes.
+///
+/// Only one chunk's worth of input is held at a time, so a file or socket
+/// can be chunked without loading it whole. Every chunk but the last is
+/// full; an empty reader yields a single empty chunk, the content chunk of
+/// an empty file. These are the leaf chunks only: building the intermediate
+/// tree over them is the file pipeline's job.
+///
+/// Interrupted reads are retried. Any other read error is yielded once and
+/// ends the iteration.
+///
+/// ```
+/// use nectar_primitives::{ChunkOps, ChunkSplitter};
+///
+/// let data = vec![7u8; 10_000];
+/// let chunks: Vec<_> = ChunkSplitter::<_>::new(data.as_slice())
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(chunks.len(), 3);
+/// assert_eq!(chunks.iter().map(|c| c.data().len()).sum::<usize>(), data.len());
+/// ```
+#[derive(Debug)]
+pub struct ChunkSplitter<R, const BODY_SIZE: usize = DEFAULT_BODY_SIZE> {
+    reader: R,
+    started: bool,
+    done: bool,
+}
+
+impl<R: Read, const BODY_SIZE: usize> ChunkSplitter<R, BODY_SIZE> {
+    /// Split the bytes of `reader` into content chunks.
+    pub const fn new(reader: R) -> Self {
+        Self {
+            reader,
+            started: false,
+            done: false,
+        }
+    }
+
+    /// Recover the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Read up to `BODY_SIZE` bytes, looping over short reads.
+    fn fill(&mut self) -> io::Result<BytesMut> {
+        let mut buf = BytesMut::zeroed(BODY_SIZE);
+        let mut filled = 0usize;
+        while let Some(rest) = buf.get_mut(filled..) {
+            if rest.is_empty() {
+                break;
+            }
+            match self.reader.read(rest) {
+                Ok(0) => break,
+                Ok(n) => filled = filled.saturating_add(n).min(BODY_SIZE),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        buf.truncate(filled);
+        Ok(buf)
+    }
+}
+
+impl<R: Read, const BODY_SIZE: usize> Iterator for ChunkSplitter<R, BODY_SIZE> {
+    type Item = Result<ContentChunk<BODY_SIZE>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let data = match self.fill() {
+            Ok(data) => data,
+            Err(e) => {
+                self.done = true;
+                return Some(Err(e.into()));
+            }
+        };
+        // A short body is the end of input; an empty one past the first
+        // chunk carries no bytes and is not emitted.
+        if data.len() < BODY_SIZE {
+            self.done = true;
+            if data.is_empty() && self.started {
+                return None;
+            }
+        }
+        self.started = true;
+        Some(ContentChunk::new(data.freeze()))
+    }
+}
+
+impl<R: Read, const BODY_SIZE: usize> core::iter::FusedIterator for ChunkSplitter<R, BODY_SIZE> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PrimitivesError;
+    use crate::chunk::ChunkOps;
+
+    const BODY: usize = 64;
+
+    fn split(data: &[u8]) -> Vec<ContentChunk<BODY>> {
+        ChunkSplitter::<_, BODY>::new(data)
+            .collect::<Result<_>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn concatenated_chunks_reproduce_the_input() {
+        for (len, count) in [
+            (0, 1),
+            (1, 1),
+            (BODY - 1, 1),
+            (BODY, 1),
+            (BODY + 1, 2),
+            (5 * BODY, 5),
+            (5 * BODY + 3, 6),
+        ] {
+            let data: Vec<u8> = (0..len).map(|i| i as u8).collect();
+            let chunks = split(&data);
+            assert_eq!(chunks.len(), count, "chunk count at {len}");
+            let joined: Vec<u8> = chunks.iter().flat_map(|c| c.data().to_vec()).collect();
+            assert_eq!(joined, data, "round trip at {len}");
+            for chunk in &chunks[..count - 1] {
+                assert_eq!(chunk.data().len(), BODY);
+            }
+            assert_eq!(
+                chunks[0],
+                ContentChunk::new(data[..len.min(BODY)].to_vec()).unwrap()
+            );
+        }
+    }
+
+    /// Hands out one byte per read and interrupts every other call.
+    struct Trickle<'a> {
+        data: &'a [u8],
+        interrupt: bool,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(io::ErrorKind::Interrupted.into());
+            }
+            let Some((first, rest)) = self.data.split_first() else {
+                return Ok(0);
+            };
+            buf[0] = *first;
+            self.data = rest;
+            Ok(1)
+        }
+    }
+
+    #[test]
+    fn short_and_interrupted_reads_fill_whole_chunks() {
+        let data: Vec<u8> = (0..3 * BODY as u32 / 2).map(|i| i as u8).collect();
+        let trickled: Vec<_> = ChunkSplitter::<_, BODY>::new(Trickle {
+            data: &data,
+            interrupt: false,
+        })
+        .collect::<Result<_>>()
+        .unwrap();
+        assert_eq!(trickled, split(&data));
+    }
+
+    struct Broken;
+
+    impl Read for Broken {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+    }
+
+    #[test]
+    fn a_read_error_is_yielded_once() {
+        let mut splitter = ChunkSplitter::<_, BODY>::new(Broken);
+        assert!(matches!(splitter.next(), Some(Err(PrimitivesError::Io(_)))));
+        assert!(splitter.next().is_none());
+    }
+}
@@ -81,6 +81,16 @@ pub trait ChunkHeader: Sized + Send + Sync + 'static {
     /// writes exactly this many.
     const SIZE: usize;
 
+    /// Whether decoding cross-checks a span over `BODY_SIZE` against the
+    /// payload, rejecting one that is not a whole reference list. A full
+    /// payload may be ciphertext and is exempt, and a span carrying a bee
+    /// redundancy level may be followed by parity references.
+    ///
+    /// Off by default: a content chunk's address commits to its span, so a
+    /// lying span only derives a different address. Types parsed from
+    /// untrusted bytes under a caller-chosen address opt in.
+    const STRICT_SPAN: bool = false;
+
     /// Derive the chunk address this header commits to over `body_hash`.
     ///
     /// Total: inputs that cannot certify still commit to *some* address,
@@ -171,11 +181,26 @@ pub trait ChunkOps: Send + Sync + 'static {
     /// (e.g. proof witness indices); the BMT span is always LE.
     fn transformed_address(&self, anchor: &[u8]) -> ChunkAddress;
 
+    /// Append this chunk's bare wire bytes (`header || span || payload`),
+    /// exactly [`size`](Self::size) of them, to `buf`.
+    ///
+    /// Lets a caller serializing many chunks reuse one buffer rather than
+    /// allocating per chunk through [`into_bytes`](Self::into_bytes).
+    fn write_to(&self, buf: &mut BytesMut);
+
     /// Convert this chunk into its bare wire bytes (`header || span ||
     /// payload`), the inverse of the carrier's `TryFrom<Bytes>` decode.
+    ///
+    /// The default encodes through [`write_to`](Self::write_to) into one
+    /// buffer of exactly [`size`](Self::size) bytes.
     fn into_bytes(self) -> Bytes
     where
-        Self: Sized;
+        Self: Sized,
+    {
+        let mut buf = BytesMut::with_capacity(self.size());
+        self.write_to(&mut buf);
+        buf.freeze()
+    }
 }
 
 /// Carrier trait tying a chunk to its wire header type.
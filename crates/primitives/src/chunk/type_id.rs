@@ -5,6 +5,8 @@
 
 use core::fmt;
 
+use super::error::ChunkError;
+
 /// Wire-level chunk type identifier.
 ///
 /// This type represents the type ID byte used in chunk headers for serialization
@@ -73,6 +75,33 @@ impl ChunkTypeId {
         Self(id)
     }
 
+    /// Create a custom chunk type ID, refusing ids in the standard range.
+    ///
+    /// The checked form of [`custom`](Self::custom): a custom type sharing an
+    /// id with a built-in type would be routed to the wrong acceptance rule.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ChunkError::ReservedTypeId`] for ids 0-127.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nectar_primitives::ChunkTypeId;
+    ///
+    /// assert!(ChunkTypeId::try_custom(0xe0).is_ok());
+    /// assert!(ChunkTypeId::try_custom(1).is_err());
+    /// ```
+    #[inline]
+    pub const fn try_custom(id: u8) -> Result<Self, ChunkError> {
+        let id = Self(id);
+        if id.is_custom() {
+            Ok(id)
+        } else {
+            Err(ChunkError::reserved_type_id(id))
+        }
+    }
+
     /// Get the raw byte value of this type ID.
     #[inline]
     pub const fn as_u8(self) -> u8 {
@@ -196,6 +225,19 @@ mod tests {
         assert!(ChunkTypeId::custom(200).is_custom());
     }
 
+    #[test]
+    fn test_try_custom() {
+        for id in 128..=u8::MAX {
+            assert_eq!(ChunkTypeId::try_custom(id).unwrap().as_u8(), id);
+        }
+        for id in 0..128 {
+            assert!(matches!(
+                ChunkTypeId::try_custom(id),
+                Err(ChunkError::ReservedTypeId(reserved)) if reserved.as_u8() == id
+            ));
+        }
+    }
+
     #[test]
     fn test_name() {
         assert_eq!(ChunkTypeId::CONTENT.name(), Some("content"));
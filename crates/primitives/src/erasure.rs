@@ -0,0 +1,336 @@
+//! Reed-Solomon erasure coding over chunk groups.
+//!
+//! Bee's redundancy levels protect each group of sibling chunks (the children
+//! of one intermediate chunk) with parity chunks, so the group survives the
+//! loss of up to as many chunks as it has parities. This module computes those
+//! parities and recovers lost chunks from any sufficient subset.
+//!
+//! The code is systematic and follows bee's shard layout: each chunk is one
+//! shard of `SPAN_SIZE + BODY_SIZE` bytes holding its wire encoding
+//! (`span || payload`), zero-padded. Parity shards are read back the same way,
+//! so a parity chunk's span is the first eight bytes of its shard and its
+//! payload always fills the whole body.
+//!
+//! The BMT pads short payloads with zeros, so padding never changes a chunk's
+//! address: a recovered chunk always keeps its original address. A recovered
+//! leaf (span at most `BODY_SIZE`) is also byte-identical to the original; a
+//! recovered intermediate chunk keeps the zero padding, as its reference count
+//! is not recorded in the shard.
+//!
+//! ```
+//! use nectar_primitives::{DefaultContentChunk, erasure};
+//!
+//! let chunks: Vec<DefaultContentChunk> = [b"one".as_slice(), b"two", b"three"]
+//!     .into_iter()
+//!     .map(|data| DefaultContentChunk::new(data).unwrap())
+//!     .collect();
+//! let parities = erasure::encode(&chunks, 2).unwrap();
+//!
+//! // Lose the first two data chunks; the parities stand in for them.
+//! let available = vec![
+//!     (2, chunks[2].clone()),
+//!     (3, parities[0].clone()),
+//!     (4, parities[1].clone()),
+//! ];
+//! let recovered = erasure::reconstruct(&available, 5, 2).unwrap();
+//! assert_eq!(recovered, chunks);
+//! ```
+
+use bytes::Bytes;
+use reed_solomon_erasure::galois_8::ReedSolomon;
+use thiserror::Error;
+
+use crate::bmt::{SPAN_SIZE, Span};
+use crate::cast::{u64_from_usize, usize_from_u64};
+use crate::chunk::{BmtBody, ContentChunk};
+
+/// Most shards (data plus parity) one group can hold over GF(2^8).
+pub const MAX_SHARDS: usize = 256;
+
+/// Errors from erasure coding a chunk group.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum ErasureError {
+    /// The group has no data chunks, fewer chunks than parities, or more
+    /// shards than [`MAX_SHARDS`].
+    #[error("unsupported group of {total} chunks with {parities} parities")]
+    InvalidGeometry {
+        /// Data plus parity chunks in the group.
+        total: usize,
+        /// Parity chunks in the group.
+        parities: usize,
+    },
+
+    /// A chunk index lies outside the group.
+    #[error("chunk index {index} is outside a group of {total}")]
+    IndexOutOfRange {
+        /// The offending index.
+        index: usize,
+        /// Data plus parity chunks in the group.
+        total: usize,
+    },
+
+    /// Two chunks were supplied for the same index.
+    #[error("chunk index {0} supplied more than once")]
+    DuplicateIndex(usize),
+
+    /// Too few chunks survive to recover the group.
+    #[error("{available} chunks available, {required} required")]
+    TooFewChunks {
+        /// Distinct chunks supplied.
+        available: usize,
+        /// Data chunks in the group: the minimum for recovery.
+        required: usize,
+    },
+}
+
+/// Computes `parities` parity chunks over the data chunks of one group.
+///
+/// The returned chunks take group indices `chunks.len()..chunks.len() +
+/// parities`, after the data chunks, which is the order [`reconstruct`]
+/// expects. No parities yields an empty vector.
+///
+/// # Errors
+///
+/// Returns [`ErasureError::InvalidGeometry`] if `chunks` is empty or the group
+/// exceeds [`MAX_SHARDS`].
+pub fn encode<const BODY_SIZE: usize>(
+    chunks: &[ContentChunk<BODY_SIZE>],
+    parities: usize,
+) -> Result<Vec<ContentChunk<BODY_SIZE>>, ErasureError> {
+    let data = chunks.len();
+    let total = data.saturating_add(parities);
+    check_geometry(total, parities)?;
+    let Some(coder) = coder(data, parities) else {
+        return Ok(Vec::new());
+    };
+
+    let mut shards: Vec<Vec<u8>> = chunks.iter().map(to_shard).collect();
+    shards.resize(total, vec![0; shard_size::<BODY_SIZE>()]);
+    coder
+        .encode(&mut shards)
+        .map_err(|_| ErasureError::InvalidGeometry { total, parities })?;
+
+    Ok(shards
+        .into_iter()
+        .skip(data)
+        .map(|shard| from_shard(shard, false))
+        .collect())
+}
+
+/// Recovers the data chunks of a group from any `total - parities` of its
+/// chunks.
+///
+/// `available` pairs each surviving chunk with its group index: data chunks
+/// first, then the parities in the order [`encode`] returned them. The result
+/// holds the group's data chunks in index order; supplied data chunks are
+/// returned as given.
+///
+/// # Errors
+///
+/// Returns [`ErasureError::InvalidGeometry`] for an impossible group,
+/// [`ErasureError::IndexOutOfRange`] or [`ErasureError::DuplicateIndex`] for
+/// a malformed `available`, and [`ErasureError::TooFewChunks`] if fewer than
+/// `total - parities` chunks survive.
+pub fn reconstruct<const BODY_SIZE: usize>(
+    available: &[(usize, ContentChunk<BODY_SIZE>)],
+    total: usize,
+    parities: usize,
+) -> Result<Vec<ContentChunk<BODY_SIZE>>, ErasureError> {
+    check_geometry(total, parities)?;
+    let data = total.saturating_sub(parities);
+
+    let mut shards: Vec<Option<Vec<u8>>> = vec![None; total];
+    let mut originals: Vec<Option<&ContentChunk<BODY_SIZE>>> = vec![None; data];
+    for (index, chunk) in available {
+        let slot = shards
+            .get_mut(*index)
+            .ok_or(ErasureError::IndexOutOfRange {
+                index: *index,
+                total,
+            })?;
+        if slot.replace(to_shard(chunk)).is_some() {
+            return Err(ErasureError::DuplicateIndex(*index));
+        }
+        if let Some(original) = originals.get_mut(*index) {
+            *original = Some(chunk);
+        }
+    }
+
+    let present = shards.iter().flatten().count();
+    if present < data {
+        return Err(ErasureError::TooFewChunks {
+            available: present,
+            required: data,
+        });
+    }
+    if let Some(coder) = coder(data, parities) {
+        coder
+            .reconstruct_data(&mut shards)
+            .map_err(|_| ErasureError::TooFewChunks {
+                available: present,
+                required: data,
+            })?;
+    }
+
+    Ok(originals
+        .into_iter()
+        .zip(shards)
+        .map(|(original, shard)| {
+            original.map_or_else(|| from_shard(shard.unwrap_or_default(), true), Clone::clone)
+        })
+        .collect())
+}
+
+/// The coder for a group, or `None` when it has no parities.
+fn coder(data: usize, parities: usize) -> Option<ReedSolomon> {
+    ReedSolomon::new(data, parities).ok()
+}
+
+/// Rejects groups the code cannot carry.
+const fn check_geometry(total: usize, parities: usize) -> Result<(), ErasureError> {
+    if parities >= total || total > MAX_SHARDS {
+        return Err(ErasureError::InvalidGeometry { total, parities });
+    }
+    Ok(())
+}
+
+/// Bytes per shard: a span and a full body.
+const fn shard_size<const BODY_SIZE: usize>() -> usize {
+    SPAN_SIZE.saturating_add(BODY_SIZE)
+}
+
+/// A chunk's wire encoding, zero-padded to a full shard.
+fn to_shard<const BODY_SIZE: usize>(chunk: &ContentChunk<BODY_SIZE>) -> Vec<u8> {
+    let body = chunk.body();
+    let mut shard = Vec::with_capacity(shard_size::<BODY_SIZE>());
+    shard.extend_from_slice(&body.span_bytes());
+    shard.extend_from_slice(body.data());
+    shard.resize(shard_size::<BODY_SIZE>(), 0);
+    shard
+}
+
+/// The chunk a shard encodes. For a data shard, a leaf span trims the payload
+/// back to its length; otherwise, and always for parity, the whole body is
+/// kept, padding included.
+fn from_shard<const BODY_SIZE: usize>(shard: Vec<u8>, data: bool) -> ContentChunk<BODY_SIZE> {
+    let mut payload = Bytes::from(shard);
+    let span_bytes = payload.split_to(SPAN_SIZE.min(payload.len()));
+    let span = Span::try_from(span_bytes.as_ref()).unwrap_or_default();
+    if data && span.get() <= u64_from_usize(BODY_SIZE) {
+        payload.truncate(usize_from_u64(span.get()));
+    }
+    payload.truncate(BODY_SIZE);
+    ContentChunk::from_body(BmtBody::new_unchecked(span, payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DefaultContentChunk;
+    use crate::bmt::DEFAULT_BODY_SIZE;
+    use crate::chunk::ChunkOps;
+
+    fn group(sizes: &[usize]) -> Vec<DefaultContentChunk> {
+        sizes
+            .iter()
+            .enumerate()
+            .map(|(i, &size)| {
+                let data: Vec<u8> = (0..size).map(|j| (i * 31 + j) as u8).collect();
+                DefaultContentChunk::new(data).unwrap()
+            })
+            .collect()
+    }
+
+    fn indexed(chunks: &[DefaultContentChunk], offset: usize) -> Vec<(usize, DefaultContentChunk)> {
+        chunks
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(i, chunk)| (i + offset, chunk))
+            .collect()
+    }
+
+    #[test]
+    fn lost_data_chunks_are_recovered_at_their_addresses() {
+        let chunks = group(&[DEFAULT_BODY_SIZE, 1, 0, 1000, 4095]);
+        let parities = encode(&chunks, 3).unwrap();
+        assert_eq!(parities.len(), 3);
+
+        // Keep two data chunks and all parities: the three lost ones come back.
+        let mut available = vec![(1, chunks[1].clone()), (4, chunks[4].clone())];
+        available.extend(indexed(&parities, chunks.len()));
+        let recovered = reconstruct(&available, 8, 3).unwrap();
+
+        assert_eq!(recovered, chunks);
+        for (recovered, original) in recovered.iter().zip(&chunks) {
+            assert_eq!(recovered.address(), original.address());
+        }
+    }
+
+    #[test]
+    fn intermediate_chunks_keep_their_address() {
+        let references = Bytes::from(vec![7u8; 3 * 32]);
+        let intermediate =
+            DefaultContentChunk::from_body(BmtBody::new_unchecked(Span::new(3 * 4096), references));
+        let chunks = vec![intermediate.clone(), group(&[10])[0].clone()];
+        let parities = encode(&chunks, 1).unwrap();
+
+        let available = vec![(1, chunks[1].clone()), (2, parities[0].clone())];
+        let recovered = reconstruct(&available, 3, 1).unwrap();
+        assert_eq!(recovered[0].address(), intermediate.address());
+        assert_eq!(recovered[0].body().span(), 3 * 4096);
+    }
+
+    #[test]
+    fn parity_chunks_are_deterministic_full_bodies() {
+        let chunks = group(&[5, 6, 7]);
+        let parities = encode(&chunks, 2).unwrap();
+        assert_eq!(parities, encode(&chunks, 2).unwrap());
+        // Every parity payload fills the body whatever its span bytes say.
+        for parity in &parities {
+            assert!(parity.body().span() <= DEFAULT_BODY_SIZE as u64);
+            assert_eq!(parity.body().data().len(), DEFAULT_BODY_SIZE);
+        }
+    }
+
+    #[test]
+    fn no_parities_is_the_identity() {
+        let chunks = group(&[3, 4]);
+        assert!(encode(&chunks, 0).unwrap().is_empty());
+        assert_eq!(reconstruct(&indexed(&chunks, 0), 2, 0).unwrap(), chunks);
+    }
+
+    #[test]
+    fn malformed_groups_are_rejected() {
+        let chunks = group(&[3, 4, 5]);
+        let parities = encode(&chunks, 2).unwrap();
+
+        assert_eq!(
+            encode::<DEFAULT_BODY_SIZE>(&[], 2),
+            Err(ErasureError::InvalidGeometry {
+                total: 2,
+                parities: 2
+            })
+        );
+        assert!(matches!(
+            encode(&chunks, MAX_SHARDS),
+            Err(ErasureError::InvalidGeometry { .. })
+        ));
+        assert_eq!(
+            reconstruct(&indexed(&chunks[..2], 0), 5, 2),
+            Err(ErasureError::TooFewChunks {
+                available: 2,
+                required: 3
+            })
+        );
+        assert_eq!(
+            reconstruct(&[(5, parities[0].clone())], 5, 2),
+            Err(ErasureError::IndexOutOfRange { index: 5, total: 5 })
+        );
+        assert_eq!(
+            reconstruct(&[(0, chunks[0].clone()), (0, chunks[0].clone())], 5, 2),
+            Err(ErasureError::DuplicateIndex(0))
+        );
+    }
+}
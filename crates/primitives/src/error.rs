@@ -89,4 +89,8 @@ pub enum PrimitivesError {
     /// A byte slice had the wrong width for a fixed-width type
     #[error(transparent)]
     WrongLength(#[from] WrongLength),
+
+    /// A sequence feed has no index left to publish at
+    #[error("feed index exhausted")]
+    FeedIndexExhausted,
 }
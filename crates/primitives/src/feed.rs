@@ -0,0 +1,305 @@
+//! Feed identifiers for mutable content.
+//!
+//! A feed is a series of [`SingleOwnerChunk`](crate::SingleOwnerChunk)
+//! updates one owner publishes under one topic. Readers find an update
+//! without knowing its content because its SOC id depends only on the topic
+//! and the update's [`FeedIndex`]: `keccak256(topic || index)`, with the
+//! index encoded as in bee `pkg/feeds`:
+//!
+//! - **sequence**: the update number as 8 big-endian bytes;
+//! - **epoch**: `keccak256(start || level)`, with `start` as 8 big-endian
+//!   bytes and `level` as one byte.
+//!
+//! ```
+//! use alloy_primitives::B256;
+//! use alloy_signer_local::PrivateKeySigner;
+//! use nectar_primitives::feed::{FeedIndex, feed_address, feed_id};
+//! use nectar_primitives::{ChunkOps, DefaultSingleOwnerChunk, SocId};
+//!
+//! let publisher = PrivateKeySigner::random();
+//! let topic = B256::repeat_byte(0x42);
+//!
+//! let index = FeedIndex::Sequence(0);
+//! let update = DefaultSingleOwnerChunk::new(
+//!     SocId::from(feed_id(topic, index)),
+//!     b"first update".as_slice(),
+//!     &publisher,
+//! )
+//! .unwrap();
+//!
+//! // A reader locates the update from the topic, index and owner alone.
+//! assert_eq!(*update.address(), feed_address(topic, index, publisher.address()));
+//! ```
+//!
+//! A [`FeedWriter`] does the index bookkeeping for a sequence feed:
+//!
+//! ```
+//! use alloy_primitives::B256;
+//! use alloy_signer_local::PrivateKeySigner;
+//! use nectar_primitives::ChunkOps;
+//! use nectar_primitives::feed::{FeedIndex, FeedWriter, feed_address};
+//!
+//! let topic = B256::repeat_byte(0x42);
+//! let mut writer = FeedWriter::new(topic, PrivateKeySigner::random());
+//!
+//! let first = writer.update(1_700_000_000, b"first update").unwrap();
+//! let owner = writer.signer().address();
+//! assert_eq!(*first.address(), feed_address(topic, FeedIndex::Sequence(0), owner));
+//! assert_eq!(writer.next_index(), FeedIndex::Sequence(1));
+//! ```
+
+use alloy_primitives::{Address, B256, Keccak256};
+use alloy_signer::SignerSync;
+
+use crate::DefaultSingleOwnerChunk;
+use crate::chunk::{ChunkAddress, SocHeader, SocId};
+use crate::error::{PrimitivesError, Result};
+
+/// Position of one update within a feed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FeedIndex {
+    /// The `n`th update of a sequence feed, counting from zero.
+    Sequence(u64),
+    /// An epoch of an epoch-based feed: the time span of `2^level` units
+    /// beginning at `start`.
+    Epoch {
+        /// First instant the epoch covers.
+        start: u64,
+        /// Level of the epoch; higher levels span longer ranges.
+        level: u8,
+    },
+}
+
+impl FeedIndex {
+    /// Feeds this index's encoding into `hasher`.
+    fn hash_into(self, hasher: &mut Keccak256) {
+        match self {
+            Self::Sequence(n) => hasher.update(n.to_be_bytes()),
+            Self::Epoch { start, level } => {
+                let mut epoch = Keccak256::new();
+                epoch.update(start.to_be_bytes());
+                epoch.update([level]);
+                hasher.update(epoch.finalize());
+            }
+        }
+    }
+}
+
+/// The SOC id of the update at `index` of the feed under `topic`.
+///
+/// Pass it to [`SingleOwnerChunk::new`](crate::SingleOwnerChunk::new) to
+/// publish the update.
+#[must_use]
+pub fn feed_id(topic: B256, index: FeedIndex) -> B256 {
+    let mut hasher = Keccak256::new();
+    hasher.update(topic);
+    index.hash_into(&mut hasher);
+    hasher.finalize()
+}
+
+/// The chunk address of the update at `index` of the feed `owner` publishes
+/// under `topic`: `keccak256(feed_id || owner)`.
+#[must_use]
+pub fn feed_address(topic: B256, index: FeedIndex, owner: Address) -> ChunkAddress {
+    SocHeader::address_for(SocId::from(feed_id(topic, index)), owner)
+}
+
+/// Publisher of a sequence feed: signs successive updates under one topic.
+///
+/// Each [`update`](Self::update) is the SOC at the next
+/// [`FeedIndex::Sequence`] position, as bee's sequence feed `Putter` writes
+/// them: the payload is the update's timestamp as 8 big-endian bytes followed
+/// by the data, which is what bee feed readers parse.
+#[derive(Debug, Clone)]
+pub struct FeedWriter<S> {
+    topic: B256,
+    signer: S,
+    next: u64,
+}
+
+impl<S: SignerSync> FeedWriter<S> {
+    /// A writer for a fresh feed, whose first update takes index 0.
+    #[must_use]
+    pub const fn new(topic: B256, signer: S) -> Self {
+        Self::resume(topic, signer, 0)
+    }
+
+    /// A writer for an existing feed, whose next update takes index `next`.
+    #[must_use]
+    pub const fn resume(topic: B256, signer: S, next: u64) -> Self {
+        Self {
+            topic,
+            signer,
+            next,
+        }
+    }
+
+    /// The feed's topic.
+    #[must_use]
+    pub const fn topic(&self) -> B256 {
+        self.topic
+    }
+
+    /// The signer updates are published with.
+    #[must_use]
+    pub const fn signer(&self) -> &S {
+        &self.signer
+    }
+
+    /// The index the next [`update`](Self::update) will take.
+    #[must_use]
+    pub const fn next_index(&self) -> FeedIndex {
+        FeedIndex::Sequence(self.next)
+    }
+
+    /// Signs `data`, timestamped `at` (Unix seconds, as bee stamps feed
+    /// updates), as the next update and advances the index.
+    ///
+    /// The index only advances when the chunk is built, so a failed update
+    /// can be retried at the same position.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the timestamped payload does not fit a chunk or
+    /// signing fails, and [`PrimitivesError::FeedIndexExhausted`] at index
+    /// `u64::MAX`, which has no successor to advance to.
+    pub fn update(&mut self, at: u64, data: &[u8]) -> Result<DefaultSingleOwnerChunk> {
+        let following = self
+            .next
+            .checked_add(1)
+            .ok_or(PrimitivesError::FeedIndexExhausted)?;
+        let id = SocId::from(feed_id(self.topic, self.next_index()));
+        let payload = [at.to_be_bytes().as_slice(), data].concat();
+        let chunk = DefaultSingleOwnerChunk::new(id, payload, &self.signer)?;
+        self.next = following;
+        Ok(chunk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChunkOps;
+    use alloy_primitives::{address, b256, keccak256};
+    use alloy_signer_local::PrivateKeySigner;
+
+    const TOPIC: B256 = b256!("0x1200000000000000000000000000000000000000000000000000000000000034");
+
+    #[test]
+    fn sequence_id_hashes_the_big_endian_index() {
+        let mut preimage = TOPIC.to_vec();
+        preimage.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0x01, 0x02]);
+        assert_eq!(
+            feed_id(TOPIC, FeedIndex::Sequence(0x0102)),
+            keccak256(&preimage)
+        );
+
+        assert_ne!(
+            feed_id(TOPIC, FeedIndex::Sequence(0)),
+            feed_id(TOPIC, FeedIndex::Sequence(1))
+        );
+    }
+
+    #[test]
+    fn sequence_update_matches_pinned_vector() {
+        // Computed outside this crate with a reference Keccak-256, from the
+        // bee `pkg/feeds` layout: id = keccak256(topic || index_be8),
+        // address = keccak256(id || owner).
+        let owner = address!("0x8d3766440f0d7b949a5e32995d09619a7f86e632");
+        assert_eq!(
+            feed_id(TOPIC, FeedIndex::Sequence(1)),
+            b256!("0x9392a2c113b9bbb5867978223e1cd08df19002ad2c47389e4705f9e84bae7ad8")
+        );
+        assert_eq!(
+            feed_address(TOPIC, FeedIndex::Sequence(0), owner),
+            ChunkAddress::from(b256!(
+                "0x0a5cb15f8c9303a5303f47c6a445c59d6a51f35ec469b8c236ee5f268e45360f"
+            ))
+        );
+        assert_eq!(
+            feed_address(TOPIC, FeedIndex::Sequence(1), owner),
+            ChunkAddress::from(b256!(
+                "0xd405dea5db993f525533fad0013e07010e95df0ffbb41e1ee1f40535d5ecc781"
+            ))
+        );
+    }
+
+    #[test]
+    fn epoch_id_hashes_the_hashed_epoch() {
+        let start = 1_700_000_000u64;
+        let epoch = keccak256([start.to_be_bytes().as_slice(), &[31]].concat());
+        let preimage = [TOPIC.as_slice(), epoch.as_slice()].concat();
+        assert_eq!(
+            feed_id(TOPIC, FeedIndex::Epoch { start, level: 31 }),
+            keccak256(&preimage)
+        );
+
+        // The level is part of the id: parent and child epochs differ.
+        assert_ne!(
+            feed_id(TOPIC, FeedIndex::Epoch { start, level: 31 }),
+            feed_id(TOPIC, FeedIndex::Epoch { start, level: 30 })
+        );
+    }
+
+    #[test]
+    fn writer_publishes_consecutive_sequence_updates() {
+        let signer = PrivateKeySigner::random();
+        let owner = signer.address();
+        let mut writer = FeedWriter::new(TOPIC, signer);
+
+        let first = writer.update(1_700_000_000, b"one").unwrap();
+        let second = writer.update(1_700_000_060, b"two").unwrap();
+
+        assert_eq!(
+            B256::from(first.id()),
+            feed_id(TOPIC, FeedIndex::Sequence(0))
+        );
+        assert_eq!(
+            B256::from(second.id()),
+            feed_id(TOPIC, FeedIndex::Sequence(1))
+        );
+        assert_ne!(first.id(), second.id());
+        assert_eq!(
+            *second.address(),
+            feed_address(TOPIC, FeedIndex::Sequence(1), owner)
+        );
+        assert_eq!(second.owner().unwrap(), owner);
+        assert_eq!(writer.next_index(), FeedIndex::Sequence(2));
+    }
+
+    #[test]
+    fn failed_update_keeps_the_index() {
+        let mut writer = FeedWriter::resume(TOPIC, PrivateKeySigner::random(), 7);
+        // The timestamp takes 8 bytes of the body.
+        let oversized = std::vec![0u8; crate::DEFAULT_BODY_SIZE - 7];
+        assert!(writer.update(0, &oversized).is_err());
+        assert_eq!(writer.next_index(), FeedIndex::Sequence(7));
+    }
+
+    #[test]
+    fn exhausted_index_is_refused_not_reused() {
+        let mut writer = FeedWriter::resume(TOPIC, PrivateKeySigner::random(), u64::MAX - 1);
+        writer.update(0, b"last").unwrap();
+        assert_eq!(writer.next_index(), FeedIndex::Sequence(u64::MAX));
+        for _ in 0..2 {
+            assert!(matches!(
+                writer.update(0, b"again"),
+                Err(PrimitivesError::FeedIndexExhausted)
+            ));
+            assert_eq!(writer.next_index(), FeedIndex::Sequence(u64::MAX));
+        }
+    }
+
+    #[test]
+    fn update_payload_is_bee_timestamp_then_data() {
+        let mut writer = FeedWriter::new(TOPIC, PrivateKeySigner::random());
+        let update = writer.update(0x0102_0304_0506_0708, b"hello").unwrap();
+
+        // bee `pkg/feeds` update: span || timestamp (u64 BE) || payload.
+        assert_eq!(update.span(), 13);
+        assert_eq!(
+            update.data().as_ref(),
+            [1, 2, 3, 4, 5, 6, 7, 8, b'h', b'e', b'l', b'l', b'o'].as_slice()
+        );
+    }
+}
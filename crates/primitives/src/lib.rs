@@ -52,11 +52,14 @@ pub use bytes;
 pub mod address;
 pub mod bin;
 pub mod bmt;
-mod cache;
+pub mod cache;
 mod cast;
 pub mod chunk;
 pub mod entry_ref;
+#[cfg(feature = "erasure")]
+pub mod erasure;
 pub mod error;
+pub mod feed;
 #[cfg(any(test, feature = "arbitrary"))]
 pub mod generators;
 pub mod marker;
@@ -67,6 +70,8 @@ pub mod nonce;
 pub mod oracles;
 pub mod overlay;
 pub mod proximity_order;
+pub mod proximity_tree;
+pub mod reserve;
 pub mod signing;
 pub mod spec;
 pub mod store;
@@ -94,6 +99,7 @@ pub use network_id::NetworkId;
 pub use nonce::Nonce;
 pub use overlay::compute_overlay;
 pub use proximity_order::{ProximityOrder, ProximityOrderError};
+pub use proximity_tree::ProximityTree;
 pub use spec::{Mainnet, SwarmSpec, Testnet};
 pub use timestamp::{Timestamp, TimestampError};
 pub use xor_metric::{EXTENDED_PO, MAX_PO, XorMetric};
@@ -103,7 +109,7 @@ pub use xor_metric::{EXTENDED_PO, MAX_PO, XorMetric};
 pub type SwarmAddress = OverlayAddress;
 
 // Core BMT functionality
-pub use bmt::{Hasher, HasherFactory, Proof, Prover};
+pub use bmt::{FileProof, Hasher, HasherFactory, PrefixedHasherFactory, Proof, Prover, Span};
 
 // Core chunk functionality
 pub use chunk::{
@@ -122,6 +128,7 @@ pub use chunk::{
     ChunkOps,
     ChunkRef,
     ChunkRegistry,
+    ChunkSplitter,
     ChunkType,
     ChunkTypeId,
     ChunkTypeInfo,
@@ -129,6 +136,8 @@ pub use chunk::{
     ChunkVersion,
     ContentChunk,
     ContentOnlyChunkSet,
+    DedupCache,
+    DedupLookup,
     HeaderedChunk,
     IntoVerified,
     RefKind,
@@ -143,6 +152,8 @@ pub use chunk::{
     Unverified,
     Verified,
     WrongRefKind,
+    content_address,
+    warm_addresses,
 };
 
 /// Default BMT hasher.
@@ -158,8 +169,8 @@ pub type DefaultMemoryStore = MemoryStore<StandardChunkSet>;
 
 // Chunk storage traits
 pub use store::{
-    ChunkGet, ChunkHas, ChunkPut, ChunkStoreError, MemoryStore, RetryConfig, RetryingChunkGet,
-    Sleeper, TrustedGet,
+    ChunkDelete, ChunkGet, ChunkHas, ChunkPut, ChunkStoreError, MemoryStore, RetryConfig,
+    RetryingChunkGet, Sleeper, TrustedGet,
 };
 
 // The width-agnostic reference union: the manifest-to-file bridge type.
@@ -0,0 +1,285 @@
+//! Address set ordered for nearest-neighbour queries in the XOR metric.
+//!
+//! Sorting 32-byte points big-endian places every shared prefix in one
+//! contiguous run, so a prefix is a subtree of an implicit binary trie and
+//! a range lookup on an ordered set visits it. [`ProximityTree`] walks that
+//! trie from the root, taking at each bit the half that agrees with the
+//! target first: every point in that half is closer than every point in the
+//! other, so points come out in increasing XOR distance without sorting.
+//!
+//! ```
+//! use nectar_primitives::{ChunkAddress, ProximityTree};
+//!
+//! let mut tree = ProximityTree::new();
+//! for byte in [0x00, 0x0f, 0xf0, 0xff] {
+//!     tree.insert(ChunkAddress::new([byte; 32]));
+//! }
+//!
+//! let target = ChunkAddress::new([0x0e; 32]);
+//! assert_eq!(
+//!     tree.closest_n(&target, 2),
+//!     [ChunkAddress::new([0x0f; 32]), ChunkAddress::new([0x00; 32])]
+//! );
+//! ```
+
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use crate::ChunkAddress;
+use crate::xor_metric::XorMetric;
+
+/// Bit width of a point.
+const POINT_BITS: usize = 256;
+
+/// An ordered set of addresses answering "which `n` are closest to this
+/// target" in the XOR metric.
+///
+/// Insert and remove are `O(log n)`. [`closest_n`](Self::closest_n) costs
+/// `O(log n)` per trie level it descends: at most 256 levels on the path to
+/// the nearest point, and one extra branch per further point returned.
+///
+/// Generic over the address kind; it defaults to [`ChunkAddress`], the kind
+/// a storage node indexes its reserve by. The kind's `Ord` must be the
+/// big-endian byte order, which every address newtype here derives.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProximityTree<A = ChunkAddress> {
+    points: BTreeSet<A>,
+}
+
+impl<A> Default for ProximityTree<A> {
+    fn default() -> Self {
+        Self {
+            points: BTreeSet::new(),
+        }
+    }
+}
+
+impl<A> ProximityTree<A>
+where
+    A: XorMetric + Ord + Copy + From<[u8; 32]>,
+{
+    /// Creates an empty tree.
+    pub const fn new() -> Self {
+        Self {
+            points: BTreeSet::new(),
+        }
+    }
+
+    /// Adds an address; returns `false` if it was already present.
+    pub fn insert(&mut self, address: A) -> bool {
+        self.points.insert(address)
+    }
+
+    /// Removes an address; returns `false` if it was absent.
+    pub fn remove(&mut self, address: &A) -> bool {
+        self.points.remove(address)
+    }
+
+    /// Returns `true` if the address is present.
+    pub fn contains(&self, address: &A) -> bool {
+        self.points.contains(address)
+    }
+
+    /// Returns the number of addresses held.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns `true` if the tree holds no address.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Iterates the addresses in ascending byte order.
+    pub fn iter(&self) -> impl Iterator<Item = &A> {
+        self.points.iter()
+    }
+
+    /// Returns up to `n` addresses, nearest to `target` first.
+    ///
+    /// The target may be any address kind: a chunk address is routinely
+    /// measured against a node overlay. A target held in the tree is its own
+    /// nearest neighbour at distance zero.
+    pub fn closest_n(&self, target: &impl XorMetric, n: usize) -> Vec<A> {
+        let mut out = Vec::with_capacity(n.min(self.points.len()));
+        if n > 0 {
+            self.descend(target.point(), [0u8; 32], 0, n, &mut out);
+        }
+        out
+    }
+
+    /// Emits the points under the prefix `lo` of `depth` bits into `out`,
+    /// nearest to `target` first, stopping once `out` holds `n`.
+    fn descend(&self, target: &[u8; 32], lo: [u8; 32], depth: usize, n: usize, out: &mut Vec<A>) {
+        let mut run = self
+            .points
+            .range(A::from(lo)..=A::from(with_ones_from(lo, depth)));
+        let Some(&first) = run.next() else {
+            return;
+        };
+        // A lone point needs no further split; nor, at full depth, does the
+        // one point a 256-bit prefix can name.
+        if depth >= POINT_BITS || run.next().is_none() {
+            out.push(first);
+            return;
+        }
+
+        let near = bit(target, depth);
+        for side in [near, !near] {
+            if out.len() >= n {
+                return;
+            }
+            let child = if side { with_bit(lo, depth) } else { lo };
+            self.descend(target, child, depth.saturating_add(1), n, out);
+        }
+    }
+}
+
+impl<A> FromIterator<A> for ProximityTree<A>
+where
+    A: XorMetric + Ord + Copy + From<[u8; 32]>,
+{
+    fn from_iter<I: IntoIterator<Item = A>>(iter: I) -> Self {
+        Self {
+            points: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<A> Extend<A> for ProximityTree<A>
+where
+    A: XorMetric + Ord + Copy + From<[u8; 32]>,
+{
+    fn extend<I: IntoIterator<Item = A>>(&mut self, iter: I) {
+        self.points.extend(iter);
+    }
+}
+
+/// Bit `index` of a point, most significant first.
+fn bit(point: &[u8; 32], index: usize) -> bool {
+    point
+        .get(index / 8)
+        .is_some_and(|byte| byte & (0x80 >> (index % 8)) != 0)
+}
+
+/// `point` with bit `index` set.
+fn with_bit(mut point: [u8; 32], index: usize) -> [u8; 32] {
+    if let Some(byte) = point.get_mut(index / 8) {
+        *byte |= 0x80 >> (index % 8);
+    }
+    point
+}
+
+/// `point` with every bit from `index` on set: the upper bound of the
+/// prefix it names.
+fn with_ones_from(mut point: [u8; 32], index: usize) -> [u8; 32] {
+    if let Some(byte) = point.get_mut(index / 8) {
+        *byte |= 0xFF >> (index % 8);
+    }
+    for byte in point.iter_mut().skip((index / 8).saturating_add(1)) {
+        *byte = 0xFF;
+    }
+    point
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OverlayAddress;
+    use proptest::prelude::*;
+
+    fn brute_force(points: &[ChunkAddress], target: &ChunkAddress, n: usize) -> Vec<ChunkAddress> {
+        let mut sorted: Vec<_> = points.to_vec();
+        sorted.sort_by_key(|p| target.distance(p));
+        sorted.dedup();
+        sorted.truncate(n);
+        sorted
+    }
+
+    #[test]
+    fn empty_tree_and_zero_n_yield_nothing() {
+        let mut tree = ProximityTree::new();
+        assert!(tree.closest_n(&ChunkAddress::ZERO, 3).is_empty());
+        tree.insert(ChunkAddress::ZERO);
+        assert!(tree.closest_n(&ChunkAddress::ZERO, 0).is_empty());
+    }
+
+    #[test]
+    fn insert_and_remove_track_membership() {
+        let mut tree = ProximityTree::new();
+        let a = ChunkAddress::new([1; 32]);
+        assert!(tree.insert(a));
+        assert!(!tree.insert(a));
+        assert!(tree.contains(&a));
+        assert_eq!(tree.len(), 1);
+        assert!(tree.remove(&a));
+        assert!(!tree.remove(&a));
+        assert!(tree.is_empty());
+    }
+
+    #[test]
+    fn a_held_target_is_its_own_nearest() {
+        let tree: ProximityTree = [0x10, 0x20, 0x30]
+            .map(|b| ChunkAddress::new([b; 32]))
+            .into_iter()
+            .collect();
+        let target = ChunkAddress::new([0x20; 32]);
+        assert_eq!(tree.closest_n(&target, 1), [target]);
+    }
+
+    #[test]
+    fn neighbours_differing_only_in_the_last_bit_are_found() {
+        let mut low = [0u8; 32];
+        low[31] = 0b10;
+        let mut high = low;
+        high[31] = 0b11;
+        let tree: ProximityTree = [ChunkAddress::new(low), ChunkAddress::new(high)]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            tree.closest_n(&ChunkAddress::new(high), 2),
+            [ChunkAddress::new(high), ChunkAddress::new(low)]
+        );
+    }
+
+    #[test]
+    fn targets_may_be_another_address_kind() {
+        let tree: ProximityTree = [0x00, 0x80]
+            .map(|b| ChunkAddress::new([b; 32]))
+            .into_iter()
+            .collect();
+        let overlay = OverlayAddress::with_first_byte(0xC0);
+        assert_eq!(tree.closest_n(&overlay, 1), [ChunkAddress::new([0x80; 32])]);
+    }
+
+    proptest! {
+        #[test]
+        fn closest_n_matches_a_full_sort(
+            points in prop::collection::vec(any::<[u8; 32]>(), 0..64),
+            target in any::<[u8; 32]>(),
+            n in 0usize..80,
+        ) {
+            let points: Vec<_> = points.into_iter().map(ChunkAddress::new).collect();
+            let target = ChunkAddress::new(target);
+            let tree: ProximityTree = points.iter().copied().collect();
+            prop_assert_eq!(tree.closest_n(&target, n), brute_force(&points, &target, n));
+        }
+
+        #[test]
+        fn clustered_points_match_a_full_sort(
+            suffixes in prop::collection::vec(any::<u8>(), 1..32),
+            target in any::<u8>(),
+            n in 1usize..40,
+        ) {
+            // Points sharing 31 bytes force the descent to the deepest levels.
+            let at = |b: u8| {
+                let mut bytes = [0xAB; 32];
+                bytes[31] = b;
+                ChunkAddress::new(bytes)
+            };
+            let points: Vec<_> = suffixes.into_iter().map(at).collect();
+            let tree: ProximityTree = points.iter().copied().collect();
+            prop_assert_eq!(tree.closest_n(&at(target), n), brute_force(&points, &at(target), n));
+        }
+    }
+}
@@ -0,0 +1,368 @@
+//! Reserve sampling for the redistribution game.
+//!
+//! A node taking part in a storage-incentive round re-hashes every chunk of
+//! its reserve under the round's anchor and commits to the
+//! [`SAMPLE_SIZE`] chunks whose *transformed addresses* (see
+//! [`ChunkOps::transformed_address`]) sort lowest. Keying the hash with the
+//! anchor makes the sample unpredictable before the round and binds it to
+//! the reserve actually held.
+//!
+//! [`sample`] makes that selection over any chunk iterator. Stamp and batch
+//! validity are the caller's to filter beforehand: the sampler sees only
+//! chunk bytes.
+//!
+//! A [`NonInclusionProof`] shows a transformed address is absent from a
+//! committed sample by opening its neighbours in the sorted order, as the
+//! `claim` flow orders its witnesses.
+//!
+//! ```
+//! use alloy_primitives::B256;
+//! use nectar_primitives::chunk::ContentChunk;
+//! use nectar_primitives::reserve::{SAMPLE_SIZE, sample};
+//!
+//! let chunks = (0u32..64).map(|i| ContentChunk::<4096>::new(i.to_be_bytes().to_vec()).unwrap());
+//! let anchor = B256::repeat_byte(0x5a);
+//!
+//! let reserve_sample = sample(chunks, &anchor, 0);
+//! assert_eq!(reserve_sample.items().len(), SAMPLE_SIZE);
+//! let _commitment = reserve_sample.hash();
+//! ```
+
+use alloc::vec::Vec;
+
+use alloy_primitives::B256;
+
+use crate::bmt::{DEFAULT_BODY_SIZE, HASH_SIZE, Hasher, Proof, Prover};
+use crate::chunk::{ChunkAddress, ChunkOps};
+use crate::xor_metric::XorMetric;
+
+/// Number of chunks a reserve sample commits to.
+pub const SAMPLE_SIZE: usize = 16;
+
+/// One sampled chunk: its address and its anchor-keyed transformed address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SampleItem {
+    /// The chunk's address.
+    pub address: ChunkAddress,
+    /// The chunk's transformed address under the round's anchor.
+    pub transformed_address: ChunkAddress,
+}
+
+/// The chunks a reserve commits to for one round, lowest transformed address
+/// first.
+///
+/// Holds fewer than [`SAMPLE_SIZE`] items only when the neighbourhood has
+/// fewer chunks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReserveSample {
+    items: Vec<SampleItem>,
+}
+
+impl ReserveSample {
+    /// The sampled items, ascending by transformed address.
+    pub fn items(&self) -> &[SampleItem] {
+        &self.items
+    }
+
+    /// Whether the sample holds a full [`SAMPLE_SIZE`] items.
+    pub const fn is_full(&self) -> bool {
+        self.items.len() == SAMPLE_SIZE
+    }
+
+    /// The sample commitment: the content address of the chunk packing each
+    /// item's `address || transformed_address` in order.
+    pub fn hash(&self) -> ChunkAddress {
+        let mut hasher = self.hasher();
+        for item in &self.items {
+            hasher.update(item.address.as_bytes());
+            hasher.update(item.transformed_address.as_bytes());
+        }
+        ChunkAddress::from(hasher.sum())
+    }
+
+    /// Prove `target` is not a transformed address in this sample.
+    ///
+    /// Opens the items ranked either side of `target` against
+    /// [`hash`](Self::hash). A target below the first item is proven by the
+    /// first item alone: nothing in the sample sorts lower. A target past the
+    /// last item is proven by the last item alone, which for a full sample
+    /// shows it is not among the lowest [`SAMPLE_SIZE`].
+    ///
+    /// `None` when `target` is in the sample, or the sample is empty and has
+    /// no commitment to open.
+    pub fn non_inclusion_proof(&self, target: &ChunkAddress) -> Option<NonInclusionProof> {
+        let at = self
+            .items
+            .partition_point(|item| item.transformed_address < *target);
+        if self
+            .items
+            .get(at)
+            .is_some_and(|item| item.transformed_address == *target)
+        {
+            return None;
+        }
+
+        let hasher = self.hasher();
+        let packed: Vec<u8> = self
+            .items
+            .iter()
+            .flat_map(|item| [item.address.as_bytes(), item.transformed_address.as_bytes()])
+            .flatten()
+            .copied()
+            .collect();
+        let open = |position: usize| {
+            position
+                .checked_mul(2)
+                .and_then(|segment| segment.checked_add(1))
+                .and_then(|segment| hasher.generate_proof(&packed, segment).ok())
+        };
+
+        let predecessor = at.checked_sub(1).map(open);
+        let successor = (at < self.items.len()).then(|| open(at));
+        let proof = NonInclusionProof {
+            predecessor: predecessor.flatten(),
+            successor: successor.flatten(),
+        };
+        (proof.predecessor.is_some() || proof.successor.is_some()).then_some(proof)
+    }
+
+    /// A hasher spanning the packed items.
+    fn hasher(&self) -> Hasher<DEFAULT_BODY_SIZE> {
+        let mut hasher = Hasher::new();
+        // Two hashes per item stay within the body, so the span is small.
+        let span = self
+            .items
+            .len()
+            .saturating_mul(2 * HASH_SIZE)
+            .try_into()
+            .unwrap_or(u64::MAX);
+        hasher.set_span(span);
+        hasher
+    }
+
+    /// Offer one item, keeping the lowest [`SAMPLE_SIZE`] distinct chunks.
+    fn offer(&mut self, item: SampleItem) {
+        if self.items.iter().any(|held| held.address == item.address) {
+            return;
+        }
+        let at = self
+            .items
+            .partition_point(|held| held.transformed_address <= item.transformed_address);
+        if at < SAMPLE_SIZE {
+            self.items.insert(at, item);
+            self.items.truncate(SAMPLE_SIZE);
+        }
+    }
+}
+
+/// Proof that a transformed address is absent from a committed
+/// [`ReserveSample`].
+///
+/// Each neighbour is a BMT inclusion proof of one item's transformed-address
+/// segment in the packed sample chunk, so it verifies against
+/// [`ReserveSample::hash`] alone. The proven segment gives the item's rank
+/// and the span gives the sample's length; adjacent ranks with the target
+/// strictly between them leave no room for it in the sample.
+#[derive(Debug, Clone)]
+pub struct NonInclusionProof {
+    /// The item ranked just below the target; `None` when the target sorts
+    /// below the whole sample.
+    pub predecessor: Option<Proof>,
+    /// The item ranked just above the target; `None` when the target sorts
+    /// past the whole sample.
+    pub successor: Option<Proof>,
+}
+
+impl NonInclusionProof {
+    /// Verify that `target` is absent from the sample committed to by
+    /// `commitment`.
+    pub fn verify(&self, commitment: &ChunkAddress, target: &ChunkAddress) -> bool {
+        // Each present neighbour as (rank, sample length), once it opens
+        // against the commitment on the correct side of the target.
+        let open = |proof: &Proof, below: bool| {
+            let segment = ChunkAddress::from(proof.segment);
+            let sorted = if below {
+                segment < *target
+            } else {
+                segment > *target
+            };
+            let item_size = 2 * HASH_SIZE;
+            let len = usize::try_from(proof.span).ok()?;
+            let well_formed = sorted
+                && proof.prefix.is_none()
+                && len.is_multiple_of(item_size)
+                && ChunkAddress::from(proof.root()) == *commitment;
+            // Only odd segments hold transformed addresses.
+            let rank = proof.segment_index.checked_sub(1)?;
+            (well_formed && rank.is_multiple_of(2)).then_some((rank / 2, len / item_size))
+        };
+
+        let predecessor = self.predecessor.as_ref().map(|proof| open(proof, true));
+        let successor = self.successor.as_ref().map(|proof| open(proof, false));
+        match (predecessor, successor) {
+            (Some(Some((below, len))), Some(Some((above, other_len)))) => {
+                len == other_len && below.checked_add(1) == Some(above) && above < len
+            }
+            (None, Some(Some((above, len)))) => above == 0 && above < len,
+            (Some(Some((below, len))), None) => below.checked_add(1) == Some(len),
+            _ => false,
+        }
+    }
+}
+
+/// Sample a reserve for the round keyed by `anchor`.
+///
+/// Only chunks within proximity `depth` of the anchor take part: the
+/// neighbourhood the anchor selects at the storage depth. Each is re-hashed
+/// under the anchor, and the [`SAMPLE_SIZE`] distinct chunks with the lowest
+/// transformed addresses form the sample.
+pub fn sample<C: ChunkOps>(
+    chunks: impl IntoIterator<Item = C>,
+    anchor: &B256,
+    depth: u8,
+) -> ReserveSample {
+    let neighbourhood = ChunkAddress::from(*anchor);
+    let mut sample = ReserveSample::default();
+    for chunk in chunks {
+        let address = *chunk.address();
+        if address.proximity(&neighbourhood).get() < depth {
+            continue;
+        }
+        sample.offer(SampleItem {
+            address,
+            transformed_address: chunk.transformed_address(anchor.as_slice()),
+        });
+    }
+    sample
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk::ContentChunk;
+
+    type DefaultContentChunk = ContentChunk<DEFAULT_BODY_SIZE>;
+
+    fn reserve(count: u32) -> Vec<DefaultContentChunk> {
+        (0..count)
+            .map(|i| DefaultContentChunk::new(i.to_be_bytes().to_vec()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn sample_keeps_the_lowest_transformed_addresses() {
+        let chunks = reserve(100);
+        let anchor = B256::repeat_byte(0x5a);
+
+        let got = sample(chunks.clone(), &anchor, 0);
+        assert!(got.is_full());
+
+        let mut expected: Vec<_> = chunks
+            .iter()
+            .map(|chunk| SampleItem {
+                address: *chunk.address(),
+                transformed_address: chunk.transformed_address(anchor.as_slice()),
+            })
+            .collect();
+        expected.sort_by_key(|item| item.transformed_address);
+        expected.truncate(SAMPLE_SIZE);
+        assert_eq!(got.items(), expected.as_slice());
+
+        // A different anchor draws a different sample.
+        assert_ne!(sample(chunks, &B256::repeat_byte(0xa5), 0), got);
+    }
+
+    #[test]
+    fn sample_skips_duplicates_and_chunks_outside_the_depth() {
+        let chunks = reserve(100);
+        let anchor = B256::repeat_byte(0x5a);
+
+        // Offering every chunk twice changes nothing.
+        let once = sample(chunks.clone(), &anchor, 0);
+        let twice = sample(chunks.iter().chain(&chunks).cloned(), &anchor, 0);
+        assert_eq!(once, twice);
+
+        // At depth 2 only chunks sharing the anchor's two leading bits count.
+        let deep = sample(chunks.clone(), &anchor, 2);
+        let inside = chunks
+            .iter()
+            .filter(|chunk| chunk.address().as_bytes()[0] >> 6 == 0x5a >> 6)
+            .count();
+        assert_eq!(deep.items().len(), inside.min(SAMPLE_SIZE));
+        for item in deep.items() {
+            assert_eq!(item.address.as_bytes()[0] >> 6, 0x5a >> 6);
+        }
+
+        assert_eq!(
+            sample(Vec::<DefaultContentChunk>::new(), &anchor, 0),
+            ReserveSample::default()
+        );
+    }
+
+    #[test]
+    fn non_inclusion_opens_the_neighbours_of_an_absent_address() {
+        let reserve_sample = sample(reserve(40), &B256::repeat_byte(0x33), 0);
+        let commitment = reserve_sample.hash();
+        let items = reserve_sample.items();
+        let below = |address: &ChunkAddress| {
+            let mut bytes: [u8; 32] = (*address).into();
+            let last = bytes.iter_mut().rev().find(|byte| **byte > 0).unwrap();
+            *last -= 1;
+            ChunkAddress::new(bytes)
+        };
+
+        // Between two sampled items, below the first and past the last.
+        let between = below(&items[5].transformed_address);
+        let lowest = below(&items[0].transformed_address);
+        let past = ChunkAddress::new([0xff; 32]);
+        for target in [between, lowest, past] {
+            let proof = reserve_sample.non_inclusion_proof(&target).unwrap();
+            assert!(proof.verify(&commitment, &target));
+        }
+        let lowest_proof = reserve_sample.non_inclusion_proof(&lowest).unwrap();
+        assert!(lowest_proof.predecessor.is_none());
+
+        // A sampled address has no proof, and a proof does not transfer to
+        // it, to another commitment, or with a neighbour dropped.
+        let sampled = items[5].transformed_address;
+        assert!(reserve_sample.non_inclusion_proof(&sampled).is_none());
+        let mut proof = reserve_sample.non_inclusion_proof(&between).unwrap();
+        assert!(!proof.verify(&commitment, &sampled));
+        assert!(!proof.verify(&items[0].address, &between));
+        proof.predecessor = None;
+        assert!(!proof.verify(&commitment, &between));
+
+        // Neighbours that are not adjacent in rank leave a gap.
+        let gapped = NonInclusionProof {
+            predecessor: reserve_sample
+                .non_inclusion_proof(&between)
+                .unwrap()
+                .predecessor,
+            successor: reserve_sample
+                .non_inclusion_proof(&below(&items[7].transformed_address))
+                .unwrap()
+                .successor,
+        };
+        assert!(!gapped.verify(&commitment, &between));
+
+        assert!(
+            ReserveSample::default()
+                .non_inclusion_proof(&between)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn hash_is_the_packed_sample_chunk_address() {
+        let got = sample(reserve(20), &B256::repeat_byte(0x01), 0);
+        let packed: Vec<u8> = got
+            .items()
+            .iter()
+            .flat_map(|item| {
+                [item.address.as_bytes(), item.transformed_address.as_bytes()].concat()
+            })
+            .collect();
+        let chunk = DefaultContentChunk::new(packed).unwrap();
+        assert_eq!(got.hash(), *chunk.address());
+    }
+}
@@ -7,7 +7,7 @@ use parking_lot::RwLock;
 use crate::chunk::{Chunk, ChunkAddress, ChunkRegistry, StandardChunkSet, Verified};
 
 use super::ChunkStoreError;
-use super::typed::{ChunkGet, ChunkHas, ChunkPut};
+use super::typed::{ChunkDelete, ChunkGet, ChunkHas, ChunkPut};
 
 /// In-memory chunk storage using a `RwLock<HashMap>`.
 ///
@@ -99,6 +99,14 @@ impl<R: ChunkRegistry> ChunkHas for MemoryStore<R> {
     }
 }
 
+impl<R: ChunkRegistry> ChunkDelete for MemoryStore<R> {
+    type Error = std::convert::Infallible;
+
+    async fn delete(&self, address: &ChunkAddress) -> Result<bool, Self::Error> {
+        Ok(self.chunks.write().remove(address).is_some())
+    }
+}
+
 impl<R: ChunkRegistry> ChunkGet<R> for HashMap<ChunkAddress, Chunk<Verified, R>> {
     type Trust = Verified;
     type Error = ChunkStoreError;
@@ -135,5 +143,10 @@ mod tests {
         assert_eq!(store.len(), 1);
         assert!(run(ChunkHas::has(&store, &addr)));
         assert_eq!(store.get(&addr).map(|c| *c.address()), Some(addr));
+
+        assert_eq!(run(ChunkDelete::delete(&store, &addr)), Ok(true));
+        assert_eq!(run(ChunkDelete::delete(&store, &addr)), Ok(false));
+        assert!(!run(ChunkHas::has(&store, &addr)));
+        assert!(store.is_empty());
     }
 }
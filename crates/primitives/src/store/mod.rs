@@ -1,8 +1,8 @@
 //! Chunk storage traits and implementations.
 //!
-//! `ChunkGet`, `ChunkPut`, and `ChunkHas` are async and carry `MaybeSend`/
-//! `MaybeSync` bounds so a store may be `!Send` on single-threaded targets
-//! (wasm32, or any target under the `unsync` feature).
+//! `ChunkGet`, `ChunkPut`, `ChunkHas`, and `ChunkDelete` are async and carry
+//! `MaybeSend`/`MaybeSync` bounds so a store may be `!Send` on single-threaded
+//! targets (wasm32, or any target under the `unsync` feature).
 
 mod memory;
 mod retry;
@@ -11,7 +11,7 @@ mod typed;
 pub use crate::marker::{MaybeSend, MaybeSync};
 pub use memory::MemoryStore;
 pub use retry::{RetryConfig, RetryingChunkGet, Sleeper};
-pub use typed::{ChunkGet, ChunkHas, ChunkPut, TrustedGet};
+pub use typed::{ChunkDelete, ChunkGet, ChunkHas, ChunkPut, TrustedGet};
 
 use crate::chunk::{Chunk, ChunkAddress, ChunkRegistry, Verified};
 
@@ -15,7 +15,7 @@ use std::fmt;
 use std::future::Future;
 use std::time::Duration;
 
-use super::typed::{ChunkGet, ChunkHas, ChunkPut};
+use super::typed::{ChunkDelete, ChunkGet, ChunkHas, ChunkPut};
 use crate::chunk::{Chunk, ChunkAddress, ChunkRegistry, Verified};
 use crate::marker::{MaybeSend, MaybeSync};
 
@@ -162,6 +162,14 @@ impl<G: ChunkHas, S: MaybeSend + MaybeSync> ChunkHas for RetryingChunkGet<G, S>
     }
 }
 
+impl<G: ChunkDelete, S: MaybeSend + MaybeSync> ChunkDelete for RetryingChunkGet<G, S> {
+    type Error = G::Error;
+
+    async fn delete(&self, address: &ChunkAddress) -> Result<bool, Self::Error> {
+        self.inner.delete(address).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,10 +1,11 @@
 //! Typed chunk storage traits.
 //!
-//! `ChunkGet`, `ChunkPut`, and `ChunkHas` are async and carry `MaybeSend`/
-//! `MaybeSync` bounds (on the traits and their error types) so a store may be
-//! `!Send` on single-threaded targets. Writes are uniformly sealed ([`ChunkPut`] only accepts
-//! `Chunk<Verified, R>`); trust is a property of the read medium, declared
-//! once per backend through [`ChunkGet::Trust`].
+//! `ChunkGet`, `ChunkPut`, `ChunkHas`, and `ChunkDelete` are async and carry
+//! `MaybeSend`/`MaybeSync` bounds (on the traits and their error types) so a
+//! store may be `!Send` on single-threaded targets. Writes are uniformly
+//! sealed ([`ChunkPut`] only accepts `Chunk<Verified, R>`); trust is a
+//! property of the read medium, declared once per backend through
+//! [`ChunkGet::Trust`].
 
 use std::future::Future;
 
@@ -110,6 +111,43 @@ impl<R: ChunkRegistry, T: ChunkPut<R> + ?Sized> ChunkPut<R> for alloc::sync::Arc
     }
 }
 
+/// Async chunk removal (`&self`).
+///
+/// Separate from [`ChunkPut`] so a write-once medium need not pretend to
+/// support it. Implementors should use interior mutability.
+pub trait ChunkDelete: MaybeSend + MaybeSync {
+    /// Error type for delete operations.
+    type Error: core::error::Error + MaybeSend + MaybeSync + 'static;
+
+    /// Remove a chunk, returning whether it was present.
+    fn delete(
+        &self,
+        address: &ChunkAddress,
+    ) -> impl Future<Output = Result<bool, Self::Error>> + MaybeSend;
+}
+
+impl<T: ChunkDelete + ?Sized> ChunkDelete for &T {
+    type Error = T::Error;
+
+    fn delete(
+        &self,
+        address: &ChunkAddress,
+    ) -> impl Future<Output = Result<bool, Self::Error>> + MaybeSend {
+        (**self).delete(address)
+    }
+}
+
+impl<T: ChunkDelete + ?Sized> ChunkDelete for alloc::sync::Arc<T> {
+    type Error = T::Error;
+
+    fn delete(
+        &self,
+        address: &ChunkAddress,
+    ) -> impl Future<Output = Result<bool, Self::Error>> + MaybeSend {
+        (**self).delete(address)
+    }
+}
+
 /// Marker for getters whose read medium hands back exactly what was sealed:
 /// [`ChunkGet`] with `Trust = Verified`.
 ///
@@ -124,6 +124,17 @@ pub trait XorMetric {
         Ordering::Equal
     }
 
+    /// Orders points `x` and `y` by ascending distance from `self`.
+    ///
+    /// The sort-order counterpart of [`distance_cmp`](Self::distance_cmp):
+    /// `Less` when `x` is closer, so `sort_by` puts the nearest point first.
+    /// See [`sort_by_proximity`] for the slice helper.
+    #[inline(always)]
+    #[must_use]
+    fn distance_order(&self, x: &impl XorMetric, y: &impl XorMetric) -> Ordering {
+        self.distance_cmp(x, y).reverse()
+    }
+
     /// Determine if `self` is closer to `x` than to `y`.
     ///
     /// Returns `true` if `distance(self, x) < distance(self, y)`.
@@ -204,6 +215,89 @@ impl<T: XorMetric> XorMetric for &T {
     }
 }
 
+/// Sort `points` by ascending XOR distance from `pivot`, nearest first.
+///
+/// Equidistant points are equal points, so the order is total and the sort
+/// is stable for duplicates.
+///
+/// ```
+/// use alloy_primitives::B256;
+/// use nectar_primitives::{ChunkAddress, OverlayAddress, xor_metric::sort_by_proximity};
+///
+/// let pivot = OverlayAddress::zero();
+/// let mut chunks = [0x80, 0x01, 0x10].map(|b| ChunkAddress::from(B256::repeat_byte(b)));
+/// sort_by_proximity(&mut chunks, &pivot);
+/// assert_eq!(chunks[0], ChunkAddress::from(B256::repeat_byte(0x01)));
+/// ```
+pub fn sort_by_proximity<T: XorMetric>(points: &mut [T], pivot: &impl XorMetric) {
+    points.sort_by(|x, y| pivot.distance_order(x, y));
+}
+
+/// Index of the point in `peers` nearest to `target` by XOR distance, or
+/// `None` for no peers. The first of equidistant peers wins.
+///
+/// The routing hot path: the target is split into four big-endian words
+/// once, and each peer costs four XORs and a lexicographic compare of the
+/// word arrays, with no per-byte branching.
+///
+/// ```
+/// use alloy_primitives::B256;
+/// use nectar_primitives::OverlayAddress;
+/// use nectar_primitives::xor_metric::nearest_peer;
+///
+/// let target = OverlayAddress::from(B256::repeat_byte(0x40));
+/// let peers = [0xff, 0x41, 0x00].map(|b| OverlayAddress::from(B256::repeat_byte(b)));
+/// assert_eq!(nearest_peer(&target, &peers), Some(1));
+/// assert_eq!(nearest_peer::<OverlayAddress>(&target, &[]), None);
+/// ```
+#[must_use]
+pub fn nearest_peer<T: XorMetric>(target: &impl XorMetric, peers: &[T]) -> Option<usize> {
+    let target = words(target.point());
+    let mut nearest: Option<(usize, [u64; 4])> = None;
+    for (index, peer) in peers.iter().enumerate() {
+        let mut distance = words(peer.point());
+        for (word, t) in distance.iter_mut().zip(target) {
+            *word ^= t;
+        }
+        if nearest.is_none_or(|(_, best)| distance < best) {
+            nearest = Some((index, distance));
+        }
+    }
+    nearest.map(|(index, _)| index)
+}
+
+/// Whether `chunk` falls in the neighbourhood a node at `node` stores at
+/// `reserve_depth`.
+///
+/// As in bee's reserve, the chunk is in when its proximity order to the
+/// node's overlay is at least the node's storage radius. Proximity caps at
+/// [`MAX_PO`], so a radius above it is measured at `MAX_PO`.
+///
+/// ```
+/// use nectar_primitives::{ChunkAddress, OverlayAddress, xor_metric::is_within_depth};
+///
+/// let node = OverlayAddress::with_first_byte(0b1010_0000);
+/// // Shares the first three bits with the node: proximity order 3.
+/// let chunk = ChunkAddress::new([0b1011_0000; 32]);
+/// assert!(is_within_depth(&chunk, &node, 3));
+/// assert!(!is_within_depth(&chunk, &node, 4));
+/// ```
+#[inline]
+#[must_use]
+pub fn is_within_depth(chunk: &impl XorMetric, node: &impl XorMetric, reserve_depth: u8) -> bool {
+    chunk.proximity(node).get() >= reserve_depth.min(MAX_PO)
+}
+
+/// A point as four big-endian words, so word-array order is numeric order.
+#[inline(always)]
+fn words(point: &[u8; 32]) -> [u64; 4] {
+    let mut words = [0u64; 4];
+    for (word, bytes) in words.iter_mut().zip(point.as_chunks::<8>().0) {
+        *word = u64::from_be_bytes(*bytes);
+    }
+    words
+}
+
 /// Count of leading matching bits between two points, capped at `max`.
 #[allow(
     clippy::arithmetic_side_effects,
@@ -246,6 +340,35 @@ mod tests {
     use super::*;
     use crate::{ChunkAddress, OverlayAddress};
     use alloy_primitives::B256;
+    use proptest::prelude::*;
+
+    #[test]
+    fn is_within_depth_follows_proximity_to_the_node() {
+        let node = OverlayAddress::with_first_byte(0b0110_1100);
+        // (chunk first byte, proximity order to the node)
+        for (first, po) in [(0b1110_1100, 0), (0b0100_0000, 2), (0b0110_1000, 5)] {
+            let mut bytes = [0u8; 32];
+            bytes[0] = first;
+            let chunk = ChunkAddress::new(bytes);
+            assert_eq!(chunk.proximity(&node).get(), po);
+            assert!(is_within_depth(&chunk, &node, po));
+            assert!(!is_within_depth(&chunk, &node, po + 1));
+        }
+
+        // Radius 0 is the whole network; the node's own address is always in.
+        let far = ChunkAddress::new([0xff; 32]);
+        assert!(is_within_depth(&far, &node, 0));
+        let mut bytes = [0u8; 32];
+        bytes[0] = 0b0110_1100;
+        let own = ChunkAddress::new(bytes);
+        assert!(is_within_depth(&own, &node, MAX_PO));
+
+        // Radii past the proximity cap still admit the node's own address.
+        for depth in [MAX_PO + 1, u8::MAX] {
+            assert!(is_within_depth(&own, &node, depth));
+            assert!(!is_within_depth(&far, &node, depth));
+        }
+    }
 
     #[test]
     fn proximity_counts_leading_matching_bits() {
@@ -287,6 +410,47 @@ mod tests {
         assert!(target.closer(&near, &far));
     }
 
+    #[test]
+    fn sort_by_proximity_puts_the_nearest_first() {
+        let pivot = OverlayAddress::from(B256::repeat_byte(0x40));
+        let mut points =
+            [0xff, 0x41, 0x00, 0x40, 0x50].map(|b| OverlayAddress::from(B256::repeat_byte(b)));
+        sort_by_proximity(&mut points, &pivot);
+        assert_eq!(
+            points,
+            [0x40, 0x41, 0x50, 0x00, 0xff].map(|b| OverlayAddress::from(B256::repeat_byte(b)))
+        );
+        for pair in points.windows(2) {
+            assert_ne!(pivot.distance_order(&pair[0], &pair[1]), Ordering::Greater);
+            assert!(pivot.distance(&pair[0]) <= pivot.distance(&pair[1]));
+        }
+    }
+
+    proptest! {
+        /// The word-wise scan agrees with a brute-force minimum over the full
+        /// U256 distance, taking the first of equals.
+        #[test]
+        fn nearest_peer_matches_brute_force(
+            target in any::<[u8; 32]>(),
+            peers in proptest::collection::vec(any::<[u8; 32]>(), 0..64),
+            duplicate in any::<proptest::sample::Index>(),
+        ) {
+            let target = OverlayAddress::from(target);
+            let mut peers: Vec<_> = peers.into_iter().map(OverlayAddress::from).collect();
+            // Repeat a peer so ties are exercised.
+            if !peers.is_empty() {
+                peers.push(peers[duplicate.index(peers.len())]);
+            }
+
+            let expected = peers
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, peer)| target.distance(*peer))
+                .map(|(index, _)| index);
+            prop_assert_eq!(nearest_peer(&target, &peers), expected);
+        }
+    }
+
     #[test]
     fn cross_kind_proximity_is_legal() {
         // The protocol compares a chunk address against a node overlay
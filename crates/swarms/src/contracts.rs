@@ -0,0 +1,73 @@
+//! Resolving a swarm to its contract deployments.
+
+use nectar_contracts::{Deployments, mainnet, testnet};
+
+use crate::{NamedSwarm, Swarm};
+
+impl NamedSwarm {
+    /// Returns the contract deployments this swarm's chain runs.
+    ///
+    /// `None` for a swarm with no canonical deployment, such as a local
+    /// development network.
+    #[inline]
+    pub const fn contracts(&self) -> Option<Deployments> {
+        match self {
+            Self::Mainnet => Some(mainnet::DEPLOYMENTS),
+            Self::Testnet => Some(testnet::DEPLOYMENTS),
+            Self::Dev => None,
+        }
+    }
+}
+
+impl Swarm {
+    /// Returns the contract deployments of this swarm, if it resolves to a
+    /// named swarm with a canonical deployment.
+    ///
+    /// Resolves like [`NamedSwarm::try_from`], so a known id built through
+    /// [`Swarm::from_id_unchecked`] finds its deployments too.
+    ///
+    /// ```
+    /// use nectar_swarms::{NamedSwarm, Swarm};
+    ///
+    /// let deployments = Swarm::from_named(NamedSwarm::Mainnet).contracts().unwrap();
+    /// assert_eq!(deployments, nectar_contracts::mainnet::DEPLOYMENTS);
+    /// assert!(Swarm::from_id(1234).contracts().is_none());
+    /// ```
+    #[inline]
+    pub fn contracts(self) -> Option<Deployments> {
+        NamedSwarm::try_from(self)
+            .ok()
+            .and_then(|named| named.contracts())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named_swarms_resolve_to_their_chain_deployments() {
+        assert_eq!(
+            Swarm::from_named(NamedSwarm::Mainnet).contracts(),
+            Some(mainnet::DEPLOYMENTS)
+        );
+        assert_eq!(Swarm::from_id(10).contracts(), Some(testnet::DEPLOYMENTS));
+        assert_eq!(NamedSwarm::Dev.contracts(), None);
+    }
+
+    #[test]
+    fn unchecked_known_ids_resolve_like_named_swarm() {
+        for id in [1, 10] {
+            let swarm = Swarm::from_id_unchecked(id);
+            assert_eq!(
+                swarm.contracts(),
+                NamedSwarm::try_from(swarm).unwrap().contracts()
+            );
+        }
+        assert_eq!(
+            Swarm::from_id_unchecked(1).contracts(),
+            Some(mainnet::DEPLOYMENTS)
+        );
+        assert_eq!(Swarm::from_id_unchecked(1234).contracts(), None);
+    }
+}
@@ -8,6 +8,8 @@
 //! - `std` (default): Enable standard library support
 //! - `serde`: Enable serde serialization/deserialization
 //! - `arbitrary`: Enable arbitrary trait implementations for testing
+//! - `contracts`: Resolve a swarm to its `nectar-contracts` deployments via
+//!   `Swarm::contracts`
 
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -30,6 +32,9 @@
     )
 )]
 
+#[cfg(feature = "contracts")]
+#[cfg_attr(docsrs, doc(cfg(feature = "contracts")))]
+mod contracts;
 mod named;
 mod swarm;
 
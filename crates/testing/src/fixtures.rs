@@ -1,12 +1,13 @@
-//! Shared fixtures: whole-buffer splits into a fresh memory store, and spec
-//! doubles that move the collision-bucket floor off mainnet's 16.
+//! Shared fixtures: whole-buffer splits into a fresh memory store, spec
+//! doubles that move the collision-bucket floor off mainnet's 16, and a stamp
+//! validator with no policy of its own.
 
 use core::num::NonZeroU8;
 use std::error::Error;
 use std::sync::Arc;
 
 use nectar_file::{Plain, Split, SplitMode};
-use nectar_postage::BucketDepth;
+use nectar_postage::{BucketDepth, PostageContext, Stamp, StampError, StampValidator};
 use nectar_primitives::chunk::{AnyChunkSet, ChunkAddress};
 use nectar_primitives::store::MemoryStore;
 use nectar_primitives::{DEFAULT_BODY_SIZE, NetworkId, SwarmSpec};
@@ -72,3 +73,16 @@ impl SwarmSpec for HighFloor {
 pub fn low_floor(depth: u8) -> BucketDepth<LowFloor> {
     BucketDepth::new(depth).unwrap()
 }
+
+/// A stamp validator that accepts everything, so only the batch-aware checks
+/// of [`StampValidator::validate_with_state`] run.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Permissive;
+
+impl StampValidator for Permissive {
+    type Error = StampError;
+
+    fn validate(&self, _: &Stamp, _: &ChunkAddress, _: &PostageContext) -> Result<(), StampError> {
+        Ok(())
+    }
+}
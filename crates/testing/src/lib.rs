@@ -37,7 +37,9 @@
 //! 7. Derive hygiene: crate-side impls are hand-written (the two-tier
 //!    pattern is semantic, not derivable); only the fuzz workspace pulls the
 //!    `arbitrary` derive, for its target-local input grammars.
-//! Behind `fixtures`: the shared split fixtures and spec doubles. Behind
+//!
+//! Behind `fixtures`: the shared split fixtures, spec doubles and the
+//! permissive stamp validator. Behind
 //! `alloc`: the allocation witness.
 
 mod seeds;